@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Arbitrary input must be rejected with an error, never a panic.
+fuzz_target!(|s: &str| {
+    let _ = openrtb2::parse_bid_request(s);
+    let _ = openrtb2::parse_bid_response(s);
+    let _ = openrtb2::from_str_lenient::<openrtb2::BidRequest>(s);
+});
@@ -0,0 +1,18 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use openrtb2::{Auction, BidRequest, BidResponse};
+
+fuzz_target!(|auction: Auction| {
+    let Auction { request, response } = auction;
+
+    let json = serde_json::to_string(&request).unwrap();
+    assert_eq!(serde_json::from_str::<BidRequest>(&json).unwrap(), request);
+    let json = serde_json::to_string(&response).unwrap();
+    assert_eq!(serde_json::from_str::<BidResponse>(&json).unwrap(), response);
+
+    let bytes = openrtb2::to_msgpack(&request).unwrap();
+    assert_eq!(openrtb2::from_msgpack::<BidRequest>(&bytes).unwrap(), request);
+    let bytes = openrtb2::to_cbor(&response).unwrap();
+    assert_eq!(openrtb2::from_cbor::<BidResponse>(&bytes).unwrap(), response);
+});
@@ -9,6 +9,12 @@ use serde_repr::{Deserialize_repr, Serialize_repr};
 /// Practitioners should keep in sync with updates to the IQG values as published on IAB.com. Values
 /// “4” - “7” apply to apps per the mobile addendum to IQG version 2.1.
 #[derive(Serialize_repr, Deserialize_repr, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::repr::<Self>)
+)]
 #[repr(i32)]
 pub enum AdPosition {
     /// Unknown
@@ -0,0 +1,287 @@
+/// Returns the registrable domain (the public suffix plus one label) of a domain or URL, e.g.
+/// `ford.com` for `https://WWW.Ford.com/cars` and `ford.co.uk` for `ford.co.uk`.
+///
+/// The scheme, user info, port, path, query and fragment are stripped and the domain is lower
+/// cased. IP addresses are returned as is. Returns `None` if there is no valid host or the host
+/// is itself a public suffix.
+///
+/// Public suffixes come from the ICANN section of the Public Suffix List, which is bundled.
+///
+/// ```
+/// # use openrtb2::registrable_domain;
+/// assert_eq!(registrable_domain("https://WWW.Ford.com/").as_deref(), Some("ford.com"));
+/// assert_eq!(registrable_domain("shop.ford.co.uk:443").as_deref(), Some("ford.co.uk"));
+/// assert_eq!(registrable_domain("co.uk"), None);
+/// ```
+pub fn registrable_domain(s: &str) -> Option<String> {
+    let host = host(s)?;
+    if host.bytes().all(|b| b.is_ascii_digit() || b == b'.') || host.contains(':') {
+        return Some(host);
+    }
+    let suffix = crate::public_suffix::public_suffix(&host);
+    if suffix.len() == host.len() {
+        return None;
+    }
+    let rest = &host[..host.len() - suffix.len() - 1];
+    let start = rest.rfind('.').map_or(0, |i| i + 1);
+    Some(host[start..].to_owned())
+}
+
+/// Returns `true` if two advertiser domains, domains or URLs, belong to the same registrable
+/// domain (see [`registrable_domain`]), so `www.ford.com` matches `https://Ford.com/` but not
+/// `ford.co.uk`.
+pub fn domains_match(a: &str, b: &str) -> bool {
+    match (registrable_domain(a), registrable_domain(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// An app bundle or store ID, normalised for comparing `Bid.bundle` and `BidRequest.bapp`.
+///
+/// ```
+/// # use openrtb2::AppBundle;
+/// assert_eq!(
+///     AppBundle::parse("https://apps.apple.com/us/app/example/id284882215"),
+///     Some(AppBundle::AppStore(284882215))
+/// );
+/// assert_eq!(
+///     AppBundle::parse("https://play.google.com/store/apps/details?id=com.Example.App&hl=en"),
+///     Some(AppBundle::Package("com.example.app".into()))
+/// );
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum AppBundle {
+    /// A numeric Apple App Store ID, written as `284882215` or `id284882215`.
+    AppStore(u64),
+    /// A package name or bundle identifier, e.g. `com.example.app`, in lower case.
+    Package(String),
+}
+
+/// A reason why a bid is blocked by a request, as returned by [`BidRequest::block_reasons`].
+///
+/// [`BidRequest::block_reasons`]: ./struct.BidRequest.html#method.block_reasons
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum BlockReason {
+    /// A domain of `Bid.adomain` matches this entry of `BidRequest.badv`.
+    BlockedAdvertiser(String),
+    /// No domain of `Bid.adomain` matches `Deal.wadomain` of the deal of the bid.
+    AdvertiserNotAllowed,
+    /// `Bid.bundle` matches this entry of `BidRequest.bapp`.
+    BlockedApp(String),
+}
+
+impl AppBundle {
+    /// Parses a bundle, store ID, or App Store or Google Play URL. Returns `None` if empty.
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        let s = match s.find("://") {
+            Some(i) => {
+                let url = &s[i + 3..];
+                let (path, query) = url.split_at(url.find('?').unwrap_or(url.len()));
+                // Google Play: ...?id=<package>; App Store: .../id<number>
+                let id = query
+                    .trim_start_matches('?')
+                    .split('&')
+                    .find_map(|v| v.strip_prefix("id="));
+                match id {
+                    Some(id) => id,
+                    None => path.trim_end_matches('/').rsplit('/').next()?,
+                }
+            }
+            None => s,
+        };
+        let digits = s.strip_prefix("id").unwrap_or(s);
+        if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) {
+            return digits.parse().ok().map(Self::AppStore);
+        }
+        (!s.is_empty()).then(|| Self::Package(s.to_ascii_lowercase()))
+    }
+}
+
+impl BlockReason {
+    /// Returns the loss reason reported for a bid blocked for this reason.
+    pub fn loss_reason(&self) -> crate::LossReason {
+        match self {
+            Self::BlockedAdvertiser(_) => crate::LossReason::CreativeFiltered_AdvertiserExclusions,
+            Self::AdvertiserNotAllowed => crate::LossReason::CreativeFiltered_NotAllowedPmpDeal,
+            Self::BlockedApp(_) => crate::LossReason::CreativeFiltered_AppBundleExclusions,
+        }
+    }
+}
+
+impl std::fmt::Display for BlockReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BlockedAdvertiser(v) => write!(f, "advertiser {} is blocked", v),
+            Self::AdvertiserNotAllowed => f.write_str("advertiser is not allowed by the deal"),
+            Self::BlockedApp(v) => write!(f, "app {} is blocked", v),
+        }
+    }
+}
+
+/// Returns the lower case host of a domain or URL without a trailing dot, or `None` if it is not
+/// a valid host.
+fn host(s: &str) -> Option<String> {
+    let s = s.trim();
+    let s = s.find("://").map_or(s, |i| &s[i + 3..]);
+    let s = s.trim_start_matches("//");
+    let s = &s[..s.find(['/', '?', '#']).unwrap_or(s.len())];
+    let s = s.rfind('@').map_or(s, |i| &s[i + 1..]);
+    let s = if s.starts_with('[') {
+        // IPv6 literal
+        &s[1..s.find(']')?]
+    } else {
+        s.rfind(':')
+            .filter(|&i| s[i + 1..].bytes().all(|b| b.is_ascii_digit()))
+            .map_or(s, |i| &s[..i])
+    };
+    let s = s.strip_suffix('.').unwrap_or(s);
+    let valid = !s.is_empty()
+        && s.split('.').all(|label| {
+            !label.is_empty()
+                && label
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_' || b == b':')
+        });
+    valid.then(|| s.to_ascii_lowercase())
+}
+
+pub(crate) fn block_reasons(req: &crate::BidRequest, bid: &crate::Bid) -> Vec<BlockReason> {
+    let mut reasons = Vec::new();
+    let adomain = bid.adomain.as_deref().unwrap_or_default();
+
+    if let Some(badv) = req.badv.as_deref().and_then(|badv| {
+        badv.iter()
+            .find(|b| adomain.iter().any(|a| domains_match(a, b)))
+    }) {
+        reasons.push(BlockReason::BlockedAdvertiser(badv.clone()));
+    }
+
+    let deal = bid.dealid.as_ref().and_then(|id| {
+        let imp = req.imp.iter().find(|imp| imp.id == bid.impid)?;
+        let deals = imp.pmp.as_ref()?.deals.as_ref()?;
+        deals.iter().find(|d| &d.id == id)
+    });
+    if let Some(wadomain) = deal.and_then(|d| d.wadomain.as_ref()) {
+        if !adomain
+            .iter()
+            .any(|a| wadomain.iter().any(|w| domains_match(a, w)))
+        {
+            reasons.push(BlockReason::AdvertiserNotAllowed);
+        }
+    }
+
+    if let Some(bundle) = bid.bundle.as_deref().and_then(AppBundle::parse) {
+        if let Some(bapp) = req
+            .bapp
+            .iter()
+            .flatten()
+            .find(|b| AppBundle::parse(b).as_ref() == Some(&bundle))
+        {
+            reasons.push(BlockReason::BlockedApp(bapp.clone()));
+        }
+    }
+    reasons
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn domains() {
+        let r = |s: &str| registrable_domain(s);
+        assert_eq!(r("ford.com").as_deref(), Some("ford.com"));
+        assert_eq!(
+            r(" https://user@WWW.Ford.com.:8080/x?y#z").as_deref(),
+            Some("ford.com")
+        );
+        assert_eq!(r("//ads.ford.co.uk/").as_deref(), Some("ford.co.uk"));
+        assert_eq!(r("a.b.example.github.io").as_deref(), Some("github.io"));
+        assert_eq!(
+            r("www.shop.kawasaki.jp").as_deref(),
+            Some("www.shop.kawasaki.jp")
+        );
+        assert_eq!(
+            r("www.city.kawasaki.jp").as_deref(),
+            Some("city.kawasaki.jp")
+        );
+        assert_eq!(r("192.0.2.1:80").as_deref(), Some("192.0.2.1"));
+        assert_eq!(r("[2001:db8::1]:443").as_deref(), Some("2001:db8::1"));
+        assert_eq!(r("localhost"), None);
+        assert_eq!(r("shop.kawasaki.jp"), None);
+        assert_eq!(r("a..com"), None);
+        assert_eq!(r("ford com"), None);
+        assert_eq!(r(""), None);
+
+        assert!(domains_match("www.ford.com", "https://Ford.com/"));
+        assert!(!domains_match("ford.com", "ford.co.uk"));
+        assert!(!domains_match("co.uk", "co.uk"));
+    }
+
+    #[test]
+    fn bundles() {
+        let p = |s: &str| AppBundle::parse(s);
+        assert_eq!(p("284882215"), Some(AppBundle::AppStore(284882215)));
+        assert_eq!(p("id284882215"), Some(AppBundle::AppStore(284882215)));
+        assert_eq!(
+            p("https://itunes.apple.com/app/id284882215?mt=8"),
+            Some(AppBundle::AppStore(284882215))
+        );
+        assert_eq!(
+            p("com.Example.App"),
+            Some(AppBundle::Package("com.example.app".into()))
+        );
+        assert_eq!(p(" "), None);
+    }
+
+    #[test]
+    fn block() -> serde_json::Result<()> {
+        let req: crate::BidRequest = serde_json::from_str(
+            r#"{
+                "id": "1",
+                "imp": [{"id": "1", "pmp": {"deals": [{"id": "d", "wadomain": ["acme.com"]}]}}],
+                "badv": ["Ford.com"],
+                "bapp": ["https://play.google.com/store/apps/details?id=com.example.game", "id1"]
+            }"#,
+        )?;
+        let bid = |json: &str| serde_json::from_str::<crate::Bid>(json);
+
+        let b = bid(r#"{"id":"1","impid":"1","price":1,"adomain":["https://www.ford.com/"]}"#)?;
+        let reasons = req.block_reasons(&b);
+        assert_eq!(
+            reasons,
+            vec![BlockReason::BlockedAdvertiser("Ford.com".into())]
+        );
+        assert_eq!(
+            reasons[0].loss_reason(),
+            crate::LossReason::CreativeFiltered_AdvertiserExclusions
+        );
+
+        let b = bid(r#"{"id":"1","impid":"1","price":1,"adomain":["ford.co.uk"],"bundle":"1"}"#)?;
+        assert_eq!(
+            req.block_reasons(&b),
+            vec![BlockReason::BlockedApp("id1".into())]
+        );
+
+        let b = bid(
+            r#"{"id":"1","impid":"1","price":1,"dealid":"d","adomain":["ford.co.uk"],"bundle":"COM.example.game"}"#,
+        )?;
+        assert_eq!(
+            req.block_reasons(&b),
+            vec![
+                BlockReason::AdvertiserNotAllowed,
+                BlockReason::BlockedApp(
+                    "https://play.google.com/store/apps/details?id=com.example.game".into()
+                )
+            ]
+        );
+
+        let b =
+            bid(r#"{"id":"1","impid":"1","price":1,"dealid":"d","adomain":["shop.acme.com"]}"#)?;
+        assert!(req.block_reasons(&b).is_empty());
+
+        Ok(())
+    }
+}
@@ -5,6 +5,12 @@
 /// Site object. At a minimum, it is useful to provide an App ID or bundle, but this is not strictly
 /// required.
 #[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::recommended)
+)]
 pub struct App {
     /// string; recommended
     /// Exchange-specific app ID.
@@ -62,6 +68,10 @@ pub struct App {
         skip_serializing_if = "Option::is_none",
         with = "crate::serde::i32_as_opt_bool"
     )]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(schema_with = "crate::serde::i32_as_opt_bool::schema")
+    )]
     pub privacypolicy: Option<bool>,
 
     /// integer
@@ -71,6 +81,10 @@ pub struct App {
         skip_serializing_if = "Option::is_none",
         with = "crate::serde::i32_as_opt_bool"
     )]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(schema_with = "crate::serde::i32_as_opt_bool::schema")
+    )]
     pub paid: Option<bool>,
 
     /// object
@@ -91,6 +105,7 @@ pub struct App {
     /// object
     /// Placeholder for exchange-specific extensions to OpenRTB.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::ext))]
     pub ext: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
@@ -1,14 +1,20 @@
 /// 3.2.1 [`BidRequest#at`], 3.2.12 [`Deal#at`]
 ///
 /// Auction type, where 1 = First Price, 2 = Second Price Plus. Exchange-specific auction types can
-/// be defined using values greater than 500.
+/// be defined using values greater than 500. For a deal, 3 = the value passed in bidfloor is the
+/// agreed upon deal price.
 ///
 /// [`BidRequest#at`]: ./struct.BidRequest.html#structfield.at
 /// [`Deal#at`]: ./struct.Deal.html#structfield.at
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum AuctionType {
+    /// First Price
     FirstPrice,
+    /// Second Price Plus
     SecondPricePlus,
+    /// Deal price: the bidfloor of the deal is the agreed upon price (Deal only)
+    DealPrice,
+    /// Exchange-specific auction type (value greater than 500)
     ExchangeSpecific(i32),
 }
 
@@ -26,6 +32,7 @@ impl serde::Serialize for AuctionType {
         let v = match self {
             Self::FirstPrice => 1,
             Self::SecondPricePlus => 2,
+            Self::DealPrice => 3,
             Self::ExchangeSpecific(v) => *v,
         };
         serializer.serialize_i32(v)
@@ -40,9 +47,10 @@ impl<'de> serde::Deserialize<'de> for AuctionType {
         let v = match i32::deserialize(deserializer)? {
             1 => Self::FirstPrice,
             2 => Self::SecondPricePlus,
+            3 => Self::DealPrice,
             v if v > 500 => Self::ExchangeSpecific(v),
             v => {
-                let s = format!("invalid value: {}, expected 1 or 2 or greater than 500", v);
+                let s = format!("invalid value: {}, expected 1, 2, 3 or greater than 500", v);
                 return Err(serde::de::Error::custom(s));
             }
         };
@@ -50,15 +58,68 @@ impl<'de> serde::Deserialize<'de> for AuctionType {
     }
 }
 
+/// Deserializes [`BidRequest#at`], which cannot be [`AuctionType::DealPrice`]: code 3 is only
+/// defined for a deal.
+///
+/// [`BidRequest#at`]: ./struct.BidRequest.html#structfield.at
+pub(crate) fn deserialize_request<'de, D>(deserializer: D) -> Result<AuctionType, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::Deserialize;
+
+    match AuctionType::deserialize(deserializer)? {
+        AuctionType::DealPrice => Err(serde::de::Error::custom(
+            "invalid value: 3, expected 1 or 2 or greater than 500",
+        )),
+        v => Ok(v),
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for AuctionType {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let v = match u.int_in_range(0..=3)? {
+            0 => Self::FirstPrice,
+            1 => Self::SecondPricePlus,
+            2 => Self::DealPrice,
+            _ => Self::ExchangeSpecific(u.int_in_range(501..=i32::MAX)?),
+        };
+        Ok(v)
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for AuctionType {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "AuctionType".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "Auction type, where 1 = First Price, 2 = Second Price Plus. \
+                Exchange-specific auction types can be defined using values greater than 500. \
+                For a deal, 3 = the value passed in bidfloor is the agreed upon deal price.",
+            "type": "integer",
+            "oneOf": [
+                { "const": 1, "description": "First Price" },
+                { "const": 2, "description": "Second Price Plus" },
+                { "const": 3, "description": "Deal price (Deal only)" },
+                { "minimum": 501, "description": "Exchange-specific auction type" }
+            ]
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     #[test]
     fn json() -> serde_json::Result<()> {
-        assert!(serde_json::from_str::<AuctionType>("3").is_err());
+        assert!(serde_json::from_str::<AuctionType>("4").is_err());
         assert!(serde_json::from_str::<AuctionType>("500").is_err());
 
-        let json = "[1,2,501]";
+        let json = "[1,2,3,501]";
         let e1: Vec<AuctionType> = serde_json::from_str(json)?;
         assert_eq!(serde_json::to_string(&e1)?, json);
         assert_eq!(
@@ -66,6 +127,7 @@ mod test {
             vec![
                 AuctionType::FirstPrice,
                 AuctionType::SecondPricePlus,
+                AuctionType::DealPrice,
                 AuctionType::ExchangeSpecific(501)
             ]
         );
@@ -11,6 +11,12 @@
 /// also be offered as banner, video, and/or native by also including as Imp subordinates objects of
 /// those types. However, any given bid for the impression must conform to one of the offered types.
 #[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::recommended)
+)]
 pub struct Audio {
     /// string array; required
     /// Content MIME types supported (e.g., “audio/mp4”).
@@ -45,8 +51,12 @@ pub struct Audio {
 
     /// integer array
     /// Blocked creative attributes. Refer to List 5.3.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub battr: Option<crate::CreativeAttribute>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::serde::one_or_many::deserialize"
+    )]
+    pub battr: Option<Vec<crate::CreativeAttribute>>,
 
     /// integer
     /// Maximum extended ad duration if extension is allowed. If blank or 0, extension is not
@@ -107,6 +117,10 @@ pub struct Audio {
         skip_serializing_if = "Option::is_none",
         with = "crate::serde::i32_as_opt_bool"
     )]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(schema_with = "crate::serde::i32_as_opt_bool::schema")
+    )]
     pub stitched: Option<bool>,
 
     /// integer
@@ -117,6 +131,7 @@ pub struct Audio {
     /// object
     /// Placeholder for exchange-specific extensions to OpenRTB.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::ext))]
     pub ext: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
@@ -133,6 +148,24 @@ mod test {
         assert_eq!(serde_json::to_string(&o1)?, json);
         assert_eq!(o1, serde_json::from_str::<Audio>(json)?);
 
+        let json = r#"{"mimes":[],"battr":[1,2]}"#;
+        let o2 = serde_json::from_str::<Audio>(json)?;
+        assert_eq!(serde_json::to_string(&o2)?, json);
+        // Earlier versions of this crate wrote a single attribute.
+        assert_eq!(
+            serde_json::from_str::<Audio>(r#"{"mimes":[],"battr":1}"#)?.battr,
+            Some(vec![crate::CreativeAttribute::AudioAd_AutoPlay])
+        );
+        assert_eq!(
+            serde_json::from_str::<Audio>(r#"{"mimes":[],"battr":null}"#)?.battr,
+            None
+        );
+        // The error of the attribute is kept.
+        let err = serde_json::from_str::<Audio>(r#"{"mimes":[],"battr":99}"#).unwrap_err();
+        assert!(err.to_string().starts_with("invalid value: 99"), "{}", err);
+        let err = serde_json::from_str::<Audio>(r#"{"mimes":[],"battr":[1,99]}"#).unwrap_err();
+        assert!(err.to_string().starts_with("invalid value: 99"), "{}", err);
+
         Ok(())
     }
 }
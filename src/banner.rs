@@ -12,6 +12,12 @@
 /// also be offered as video, audio, and/or native by also including as Imp subordinates objects of
 /// those types. However, any given bid for the impression must conform to one of the offered types.
 #[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::recommended)
+)]
 pub struct Banner {
     /// object array; recommended
     /// Array of format objects (Section 3.2.10) representing the banner sizes permitted. If none
@@ -83,6 +89,10 @@ pub struct Banner {
         skip_serializing_if = "Option::is_none",
         with = "crate::serde::i32_as_opt_bool"
     )]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(schema_with = "crate::serde::i32_as_opt_bool::schema")
+    )]
     pub topframe: Option<bool>,
 
     /// integer array
@@ -112,14 +122,74 @@ pub struct Banner {
         skip_serializing_if = "Option::is_none",
         with = "crate::serde::i32_as_opt_bool"
     )]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(schema_with = "crate::serde::i32_as_opt_bool::schema")
+    )]
     pub vcm: Option<bool>,
 
     /// object
     /// Placeholder for exchange-specific extensions to OpenRTB.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::ext))]
     pub ext: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
+impl Banner {
+    /// Returns `true` if a creative of `w`x`h` DIPS is permitted by any of the ways a banner can
+    /// express sizes: a `format` entry (including Flex Ads), the exact `w`/`h`, or the deprecated
+    /// `wmin`/`wmax`/`hmin`/`hmax` range. A banner expressing no size permits any size.
+    ///
+    /// ```
+    /// # use openrtb2::{Banner, Format};
+    /// let banner = Banner {
+    ///     format: Some(vec![
+    ///         Format { w: Some(300), h: Some(250), ..Default::default() },
+    ///         Format { wratio: Some(16), hratio: Some(9), wmin: Some(320), ..Default::default() },
+    ///     ]),
+    ///     ..Default::default()
+    /// };
+    /// assert!(banner.accepts_size(300, 250));
+    /// assert!(banner.accepts_size(640, 360));
+    /// assert!(!banner.accepts_size(160, 90));
+    /// ```
+    #[allow(deprecated)]
+    pub fn accepts_size(&self, w: i32, h: i32) -> bool {
+        let formats = self.format.as_deref().unwrap_or_default();
+        let exact = self.w.is_some() && self.h.is_some();
+        let range = [self.wmin, self.wmax, self.hmin, self.hmax]
+            .iter()
+            .any(Option::is_some);
+        if formats.is_empty() && !exact && !range {
+            return true;
+        }
+
+        formats.iter().any(|f| f.accepts_size(w, h))
+            || (exact && self.w == Some(w) && self.h == Some(h))
+            || (range
+                && self.wmin.is_none_or(|v| w >= v)
+                && self.wmax.is_none_or(|v| w <= v)
+                && self.hmin.is_none_or(|v| h >= v)
+                && self.hmax.is_none_or(|v| h <= v))
+    }
+
+    /// Returns the exact sizes permitted as `(w, h)` pairs, from `format` and `w`/`h`, without
+    /// duplicates. Flex Ad formats and the deprecated ranges do not enumerate sizes and are not
+    /// included.
+    pub fn allowed_sizes(&self) -> Vec<(i32, i32)> {
+        let formats = self.format.iter().flatten().map(|f| (f.w, f.h));
+        let mut out = Vec::new();
+        for size in formats.chain(std::iter::once((self.w, self.h))) {
+            if let (Some(w), Some(h)) = size {
+                if !out.contains(&(w, h)) {
+                    out.push((w, h));
+                }
+            }
+        }
+        out
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -133,4 +203,34 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    #[allow(deprecated)]
+    fn sizes() -> serde_json::Result<()> {
+        assert!(Banner::default().accepts_size(1, 1));
+        assert!(Banner::default().allowed_sizes().is_empty());
+
+        let banner: Banner = serde_json::from_str(
+            r#"{"format":[{"w":300,"h":250},{"w":728,"h":90},{"wratio":2,"hratio":1}],"w":300,"h":250}"#,
+        )?;
+        assert!(banner.accepts_size(728, 90));
+        assert!(banner.accepts_size(640, 320));
+        assert!(!banner.accepts_size(320, 50));
+        assert_eq!(banner.allowed_sizes(), vec![(300, 250), (728, 90)]);
+
+        let banner: Banner = serde_json::from_str(r#"{"w":320,"h":50}"#)?;
+        assert!(banner.accepts_size(320, 50));
+        assert!(!banner.accepts_size(300, 50));
+        assert_eq!(banner.allowed_sizes(), vec![(320, 50)]);
+
+        let banner: Banner =
+            serde_json::from_str(r#"{"w":320,"h":50,"wmin":300,"wmax":400,"hmax":60}"#)?;
+        assert!(banner.accepts_size(320, 50));
+        assert!(banner.accepts_size(400, 1));
+        assert!(!banner.accepts_size(401, 50));
+        assert!(!banner.accepts_size(300, 61));
+        assert_eq!(banner.hmin, None);
+
+        Ok(())
+    }
 }
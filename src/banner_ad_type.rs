@@ -5,6 +5,12 @@ use serde_repr::{Deserialize_repr, Serialize_repr};
 /// The following table indicates the types of ads that can be accepted by the exchange unless
 /// restricted by publisher site settings.
 #[derive(Serialize_repr, Deserialize_repr, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::repr::<Self>)
+)]
 #[repr(i32)]
 pub enum BannerAdType {
     /// XHTML Text Ad (usually mobile)
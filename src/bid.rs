@@ -4,6 +4,12 @@
 /// impression in the bid request via the impid attribute and constitutes an offer to buy that
 /// impression for a given price.
 #[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::recommended)
+)]
 pub struct Bid {
     /// string; required
     /// Bidder generated bid ID to assist with logging/tracking.
@@ -17,6 +23,7 @@ pub struct Bid {
     /// Bid price expressed as CPM although the actual transaction is for a unit impression only.
     /// Note that while the type indicates float, integer math is highly recommended when handling
     /// currencies (e.g., BigDecimal in Java).
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::price))]
     pub price: f64,
 
     /// string
@@ -117,7 +124,10 @@ pub struct Bid {
     /// string
     /// Language of the creative using ISO-639-1-alpha-2. The non- standard code “xx” may also be
     /// used if the creative has no linguistic content (e.g., a banner with just a company logo).
-    // TODO: ISO-639-1-alpha-2
+    /// Parsed as a [`Language`] by [`Bid::language_code`].
+    ///
+    /// [`Language`]: ./struct.Language.html
+    /// [`Bid::language_code`]: ./struct.Bid.html#method.language_code
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub language: Option<String>,
 
@@ -156,9 +166,162 @@ pub struct Bid {
     /// object
     /// Placeholder for bidder-specific extensions to OpenRTB.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::ext))]
     pub ext: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
+impl Bid {
+    /// Parses `language` as an ISO-639-1 code, see [`Language`]. `None` for the non-standard
+    /// code `xx`.
+    ///
+    /// [`Language`]: ./struct.Language.html
+    pub fn language_code(&self) -> Option<crate::Language> {
+        self.language.as_deref().and_then(|v| v.parse().ok())
+    }
+
+    /// Determines which of the media types offered by `imp` this bid targets, narrowing them down
+    /// with each available signal: the `adm` markup (VAST, native JSON or HTML), the `protocol`,
+    /// the `api` frameworks required and, for banners, the `w`/`h` of the creative.
+    ///
+    /// ```
+    /// # use openrtb2::{Bid, Imp, MediaType};
+    /// let imp: Imp = serde_json::from_str(
+    ///     r#"{"id":"1","banner":{"w":300,"h":250},"video":{"mimes":["video/mp4"]}}"#,
+    /// )?;
+    /// let bid = Bid {
+    ///     adm: Some(r#"<VAST version="3.0"></VAST>"#.into()),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(bid.infer_media_type(&imp), Ok(MediaType::Video));
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    pub fn infer_media_type(
+        &self,
+        imp: &crate::Imp,
+    ) -> Result<crate::MediaType, crate::MediaTypeError> {
+        use crate::{MediaType::*, MediaTypes};
+
+        let types = |v: &[crate::MediaType]| v.iter().copied().collect::<MediaTypes>();
+        let offered = imp.media_types();
+        let mut detected = MediaTypes::all();
+
+        if let Some(adm) = self.adm.as_deref().filter(|v| !v.trim().is_empty()) {
+            detected = detected.intersection(crate::media_type::markup_types(adm));
+        }
+        if let Some(protocol) = self.protocol {
+            let v = match protocol {
+                crate::Protocol::Daast1 | crate::Protocol::Daast1Wrapper => types(&[Audio]),
+                _ => types(&[Video, Audio]),
+            };
+            detected = detected.intersection(v);
+        }
+        if let Some(api) = self.api {
+            let v = match api {
+                crate::ApiFramework::Vpaid1 | crate::ApiFramework::Vpaid2 => types(&[Video]),
+                _ => types(&[Banner, Native]),
+            };
+            detected = detected.intersection(v);
+        }
+        if let (Some(w), Some(h)) = (self.w, self.h) {
+            if imp.banner.as_ref().is_some_and(|v| !v.accepts_size(w, h)) {
+                detected.remove(Banner);
+            }
+        }
+
+        let matched = detected.intersection(offered);
+        let mut iter = matched.iter();
+        match (iter.next(), iter.next()) {
+            (Some(v), None) => Ok(v),
+            (None, _) => Err(crate::MediaTypeError::NoMatch { offered, detected }),
+            _ => Err(crate::MediaTypeError::Ambiguous(matched)),
+        }
+    }
+
+    /// Returns `true` if the creative size of this bid is permitted by the banner of `imp`: its
+    /// `w`/`h` per [`Banner::accepts_size`], and for Flex Ads its `wratio`/`hratio` against the
+    /// ratio formats. A bid declaring no size, or an impression without a banner, is not
+    /// constrained.
+    ///
+    /// [`Banner::accepts_size`]: ./struct.Banner.html#method.accepts_size
+    pub fn fits(&self, imp: &crate::Imp) -> bool {
+        let banner = match &imp.banner {
+            Some(v) => v,
+            None => return true,
+        };
+        if let (Some(wratio), Some(hratio)) = (self.wratio, self.hratio) {
+            let formats = banner.format.as_deref().unwrap_or_default();
+            if !formats.iter().any(|f| f.accepts_ratio(wratio, hratio)) {
+                return false;
+            }
+        }
+        match (self.w, self.h) {
+            (Some(w), Some(h)) => banner.accepts_size(w, h),
+            _ => true,
+        }
+    }
+
+    /// Returns the `http` URLs this bid references: its `nurl`, `burl`, `lurl` and `iurl`, and
+    /// the URLs in its HTML, VAST or native markup. Namespace names in markup are ignored.
+    pub fn insecure_urls(&self) -> Vec<crate::InsecureUrl> {
+        crate::secure_creative::insecure_urls(self)
+    }
+
+    /// Returns the banner ad types of the markup of this bid: a JavaScript ad if it has a
+    /// `<script>`, an iframe if it has an `<iframe>`, otherwise an XHTML banner ad, or an XHTML
+    /// text ad if it has no tags at all. Empty if there is no markup.
+    pub fn banner_ad_types(&self) -> Vec<crate::BannerAdType> {
+        self.adm
+            .as_deref()
+            .map(crate::creative_blocking::banner_ad_types)
+            .unwrap_or_default()
+    }
+
+    /// Returns `nurl` parsed as a [`NoticeUrl`], or `None` if the bid has no win notice URL.
+    ///
+    /// ```
+    /// # use openrtb2::{Bid, NoticeUrlError};
+    /// let bid = Bid {
+    ///     nurl: Some("https://dsp.example/win?p=${AUCTION_PRICE}".into()),
+    ///     burl: Some("http: //dsp.example/bill".into()),
+    ///     ..Default::default()
+    /// };
+    /// assert!(bid.nurl_checked().unwrap()?.is_secure());
+    /// assert_eq!(bid.burl_checked(), Some(Err(NoticeUrlError::Scheme)));
+    /// assert_eq!(bid.lurl_checked(), None);
+    /// # Ok::<(), NoticeUrlError>(())
+    /// ```
+    ///
+    /// [`NoticeUrl`]: ./struct.NoticeUrl.html
+    #[cfg(feature = "notice-url")]
+    pub fn nurl_checked(&self) -> Option<Result<crate::NoticeUrl, crate::NoticeUrlError>> {
+        self.nurl.as_deref().map(crate::NoticeUrl::parse)
+    }
+
+    /// Returns `burl` parsed as a [`NoticeUrl`], or `None` if the bid has no billing notice URL.
+    ///
+    /// [`NoticeUrl`]: ./struct.NoticeUrl.html
+    #[cfg(feature = "notice-url")]
+    pub fn burl_checked(&self) -> Option<Result<crate::NoticeUrl, crate::NoticeUrlError>> {
+        self.burl.as_deref().map(crate::NoticeUrl::parse)
+    }
+
+    /// Returns `lurl` parsed as a [`NoticeUrl`], or `None` if the bid has no loss notice URL.
+    ///
+    /// [`NoticeUrl`]: ./struct.NoticeUrl.html
+    #[cfg(feature = "notice-url")]
+    pub fn lurl_checked(&self) -> Option<Result<crate::NoticeUrl, crate::NoticeUrlError>> {
+        self.lurl.as_deref().map(crate::NoticeUrl::parse)
+    }
+
+    /// Returns `iurl` parsed as a [`NoticeUrl`], or `None` if the bid has no image URL.
+    ///
+    /// [`NoticeUrl`]: ./struct.NoticeUrl.html
+    #[cfg(feature = "notice-url")]
+    pub fn iurl_checked(&self) -> Option<Result<crate::NoticeUrl, crate::NoticeUrlError>> {
+        self.iurl.as_deref().map(crate::NoticeUrl::parse)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -174,4 +337,88 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn fits() -> serde_json::Result<()> {
+        let imp: crate::Imp = serde_json::from_str(
+            r#"{"id":"1","banner":{"format":[{"w":300,"h":250},{"wratio":16,"hratio":9,"wmin":320}]}}"#,
+        )?;
+        let bid = |json: &str| serde_json::from_str::<Bid>(json);
+        assert!(bid(r#"{"id":"1","impid":"1","price":1,"w":300,"h":250}"#)?.fits(&imp));
+        assert!(!bid(r#"{"id":"1","impid":"1","price":1,"w":320,"h":50}"#)?.fits(&imp));
+        assert!(bid(r#"{"id":"1","impid":"1","price":1,"wratio":32,"hratio":18}"#)?.fits(&imp));
+        assert!(!bid(r#"{"id":"1","impid":"1","price":1,"wratio":4,"hratio":3}"#)?.fits(&imp));
+        assert!(
+            !bid(r#"{"id":"1","impid":"1","price":1,"w":160,"h":90,"wratio":16,"hratio":9}"#)?
+                .fits(&imp)
+        );
+        assert!(bid(r#"{"id":"1","impid":"1","price":1}"#)?.fits(&imp));
+
+        let imp: crate::Imp = serde_json::from_str(r#"{"id":"1","video":{"mimes":[]}}"#)?;
+        assert!(bid(r#"{"id":"1","impid":"1","price":1,"w":1,"h":1}"#)?.fits(&imp));
+
+        Ok(())
+    }
+
+    #[test]
+    fn infer_media_type() -> serde_json::Result<()> {
+        use crate::{MediaType::*, MediaTypeError, MediaTypes};
+
+        let imp: crate::Imp = serde_json::from_str(
+            r#"{"id":"1","banner":{"w":300,"h":250},"video":{"mimes":[]},"native":{"request":""}}"#,
+        )?;
+        let bid = |json: &str| serde_json::from_str::<Bid>(json);
+        let infer = |json: &str| bid(json).map(|v| v.infer_media_type(&imp));
+
+        assert_eq!(
+            infer(r#"{"id":"1","impid":"1","price":1,"adm":"<div></div>"}"#)?,
+            Ok(Banner)
+        );
+        assert_eq!(
+            infer(r#"{"id":"1","impid":"1","price":1,"adm":"{\"link\":{\"url\":\"\"}}"}"#)?,
+            Ok(Native)
+        );
+        assert_eq!(
+            infer(r#"{"id":"1","impid":"1","price":1,"adm":"<VAST version=\"2.0\"/>"}"#)?,
+            Ok(Video)
+        );
+        assert_eq!(
+            infer(r#"{"id":"1","impid":"1","price":1,"protocol":3}"#)?,
+            Ok(Video)
+        );
+        assert_eq!(
+            infer(r#"{"id":"1","impid":"1","price":1,"api":2}"#)?,
+            Ok(Video)
+        );
+        assert_eq!(
+            infer(r#"{"id":"1","impid":"1","price":1,"api":5,"w":300,"h":250}"#)?,
+            Err(MediaTypeError::Ambiguous(
+                [Banner, Native].iter().copied().collect()
+            ))
+        );
+        assert_eq!(
+            infer(r#"{"id":"1","impid":"1","price":1,"api":5,"w":320,"h":50}"#)?,
+            Ok(Native)
+        );
+        assert_eq!(
+            infer(r#"{"id":"1","impid":"1","price":1,"protocol":9}"#)?,
+            Err(MediaTypeError::NoMatch {
+                offered: [Banner, Video, Native].iter().copied().collect(),
+                detected: [Audio].iter().copied().collect(),
+            })
+        );
+        assert_eq!(
+            infer(r#"{"id":"1","impid":"1","price":1}"#)?,
+            Err(MediaTypeError::Ambiguous(imp.media_types()))
+        );
+
+        let imp = crate::Imp::default();
+        assert_eq!(imp.media_types(), MediaTypes::default());
+        assert!(matches!(
+            bid(r#"{"id":"1","impid":"1","price":1}"#)?.infer_media_type(&imp),
+            Err(MediaTypeError::NoMatch { .. })
+        ));
+
+        Ok(())
+    }
 }
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 /// 3.2.1 Object: BidRequest
 ///
 /// The top-level bid request object contains a globally unique bid request or auction ID. This id
@@ -10,7 +12,13 @@
 /// impression(s) appear. These objects are highly recommended, but only one applies to a given bid
 /// request depending on whether the media is browser-based web content or a non-browser
 /// application, respectively.
-#[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+#[derive(Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::recommended)
+)]
 pub struct BidRequest {
     /// string; required
     /// Unique ID of the bid request, provided by the exchange.
@@ -19,44 +27,58 @@ pub struct BidRequest {
     /// object array; required
     /// Array of Imp objects (Section 3.2.4) representing the impressions offered. At least 1 Imp
     /// object is required.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::imps))]
     pub imp: Vec<crate::Imp>,
 
     /// object; recommended
-    #[serde(flatten, default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(
+            flatten,
+            with = "crate::distribution_channel::flat::Channel<crate::Site, crate::App>"
+        )
+    )]
     pub channel: Option<crate::DistributionChannel>,
 
     /// object; recommended
     /// Details via a Device object (Section 3.2.18) about the user’s device to which the
     /// impression will be delivered.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub device: Option<crate::Device>,
 
     /// object; recommended
     /// Details via a User object (Section 3.2.20) about the human user of the device; the
     /// advertising audience.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub user: Option<crate::User>,
 
     /// integer; default 0
     /// Indicator of test mode in which auctions are not billable, where 0 = live mode, 1 = test
     /// mode.
-    #[serde(
-        default,
-        skip_serializing_if = "default_ext::DefaultExt::is_default",
-        with = "crate::serde::i32_as_bool"
+    #[cfg_attr(
+        feature = "schema",
+        schemars(
+            default,
+            skip_serializing_if = "default_ext::DefaultExt::is_default",
+            schema_with = "crate::serde::i32_as_bool::schema"
+        )
     )]
     pub test: bool,
 
     /// integer; default 2
     /// Auction type, where 1 = First Price, 2 = Second Price Plus. Exchange-specific auction types
-    /// can be defined using values greater than 500.
-    #[serde(default, skip_serializing_if = "default_ext::DefaultExt::is_default")]
+    /// can be defined using values greater than 500. [`AuctionType::DealPrice`] is only valid for
+    /// a deal and is rejected here.
+    ///
+    /// [`AuctionType::DealPrice`]: ./enum.AuctionType.html#variant.DealPrice
+    #[cfg_attr(
+        feature = "schema",
+        schemars(default, skip_serializing_if = "default_ext::DefaultExt::is_default")
+    )]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::request_auction_type))]
     pub at: crate::AuctionType,
 
     /// integer
     /// Maximum time in milliseconds the exchange allows for bids to be received including Internet
     /// latency to avoid timeout. This value supersedes any a priori guidance from the exchange.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tmax: Option<i32>,
 
     /// string array
@@ -64,7 +86,6 @@ pub struct BidRequest {
     /// IDs of seats and knowledge of the buyer’s customers to which they refer must be coordinated
     /// between bidders and the exchange a priori. At most, only one of wseat and bseat should be
     /// used in the same request. Omission of both implies no seat restrictions.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub wseat: Option<Vec<String>>,
 
     /// string array
@@ -72,7 +93,6 @@ pub struct BidRequest {
     /// impression. IDs of seats and knowledge of the buyer’s customers to which they refer must be
     /// coordinated between bidders and the exchange a priori. At most, only one of wseat and bseat
     /// should be used in the same request. Omission of both implies no seat restrictions.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub bseat: Option<Vec<String>>,
 
     /// integer; default 0
@@ -80,10 +100,13 @@ pub struct BidRequest {
     /// impressions available in context (e.g., all on the web page, all video spots such as
     /// pre/mid/post roll) to support road-blocking. 0 = no or unknown, 1 = yes, the impressions
     /// offered represent all that are available.
-    #[serde(
-        default,
-        skip_serializing_if = "default_ext::DefaultExt::is_default",
-        with = "crate::serde::i32_as_bool"
+    #[cfg_attr(
+        feature = "schema",
+        schemars(
+            default,
+            skip_serializing_if = "default_ext::DefaultExt::is_default",
+            schema_with = "crate::serde::i32_as_bool::schema"
+        )
     )]
     pub allimps: bool,
 
@@ -91,52 +114,230 @@ pub struct BidRequest {
     /// Array of allowed currencies for bids on this bid request using ISO-4217 alpha codes.
     /// Recommended only if the exchange accepts multiple currencies.
     // TODO: ISO-4217 alpha
-    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cur: Option<Vec<String>>,
 
     /// string array
     /// White list of languages for creatives using ISO-639-1-alpha-2. Omission implies no specific
     /// restrictions, but buyers would be advised to consider language attribute in the Device
-    /// and/or Content objects if available.
-    // TODO: ISO-639-1-alpha-2
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// and/or Content objects if available. See [`BidRequest::allows_language`].
+    ///
+    /// [`BidRequest::allows_language`]: ./struct.BidRequest.html#method.allows_language
     pub wlang: Option<Vec<String>>,
 
     /// string array
     /// Blocked advertiser categories using the IAB content categories. Refer to List 5.1.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub bcat: Option<Vec<crate::ContentCategory>>,
 
     /// string array
     /// Block list of advertisers by their domains (e.g., “ford.com”).
-    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub badv: Option<Vec<String>>,
 
     /// string array
     /// Block list of applications by their platform-specific exchange- independent application
     /// identifiers. On Android, these should be bundle or package names (e.g., com.foo.mygame). On
     /// iOS, these are numeric IDs.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub bapp: Option<Vec<String>>,
 
     /// object
     /// A Sorce object (Section 3.2.2) that provides data about the inventory source and which
     /// entity makes the final decision.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub source: Option<crate::Source>,
 
     /// object
     /// A Regs object (Section 3.2.3) that specifies any industry, legal, or governmental
     /// regulations in force for this request.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub regs: Option<crate::Regs>,
 
     /// object
     /// Placeholder for exchange-specific extensions to OpenRTB.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::ext))]
     pub ext: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
+impl BidRequest {
+    /// Groups the video impressions into ad pods, see [`Pod`].
+    ///
+    /// [`Pod`]: ./struct.Pod.html
+    pub fn pods(&self) -> Vec<crate::Pod<'_>> {
+        crate::Pod::group(&self.imp)
+    }
+
+    /// Returns why `bid` is blocked by this request: an advertiser in `badv`, an advertiser not in
+    /// the `wadomain` of its deal, or an app in `bapp`. Advertiser domains are compared by
+    /// registrable domain (see [`registrable_domain`]) and bundles as [`AppBundle`]s.
+    ///
+    /// [`registrable_domain`]: ./fn.registrable_domain.html
+    /// [`AppBundle`]: ./enum.AppBundle.html
+    pub fn block_reasons(&self, bid: &crate::Bid) -> Vec<crate::BlockReason> {
+        crate::advertiser_blocking::block_reasons(self, bid)
+    }
+
+    /// Returns `true` if the language of `bid` is allowed by `wlang`: if `wlang` is absent or
+    /// empty, if the bid declares no language or the code `xx` (no linguistic content), or if
+    /// its language is listed. Languages are compared as [`Language`]s, so `EN-us` matches `en`.
+    ///
+    /// A bid that is not allowed is lost with
+    /// `LossReason::CreativeFiltered_LanguageExclusions`.
+    ///
+    /// [`Language`]: ./struct.Language.html
+    pub fn allows_language(&self, bid: &crate::Bid) -> bool {
+        let wlang = match self.wlang.as_deref() {
+            Some(v) if !v.is_empty() => v,
+            _ => return true,
+        };
+        let language = match bid.language.as_deref().map(str::trim) {
+            None => return true,
+            Some(v) if v.eq_ignore_ascii_case("xx") => return true,
+            Some(v) => crate::Language::parse(v),
+        };
+        language.is_ok_and(|language| {
+            wlang
+                .iter()
+                .any(|v| crate::Language::parse(v) == Ok(language))
+        })
+    }
+}
+
+/// The serialized form of a [`BidRequest`], in which the distribution channel is a `site` or `app`
+/// member of its own. Fields are borrowed when serializing and owned when deserializing.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Wire<'a> {
+    id: Cow<'a, str>,
+
+    imp: Cow<'a, [crate::Imp]>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    site: Option<Cow<'a, crate::Site>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    app: Option<Cow<'a, crate::App>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    device: Option<Cow<'a, crate::Device>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    user: Option<Cow<'a, crate::User>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "default_ext::DefaultExt::is_default",
+        with = "crate::serde::i32_as_bool"
+    )]
+    test: bool,
+
+    #[serde(
+        default,
+        skip_serializing_if = "default_ext::DefaultExt::is_default",
+        deserialize_with = "crate::auction_type::deserialize_request"
+    )]
+    at: crate::AuctionType,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tmax: Option<i32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    wseat: Option<Cow<'a, [String]>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bseat: Option<Cow<'a, [String]>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "default_ext::DefaultExt::is_default",
+        with = "crate::serde::i32_as_bool"
+    )]
+    allimps: bool,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cur: Option<Cow<'a, [String]>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    wlang: Option<Cow<'a, [String]>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bcat: Option<Cow<'a, [crate::ContentCategory]>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    badv: Option<Cow<'a, [String]>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bapp: Option<Cow<'a, [String]>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source: Option<Cow<'a, crate::Source>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    regs: Option<Cow<'a, crate::Regs>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ext: Option<Cow<'a, serde_json::Map<String, serde_json::Value>>>,
+}
+
+impl serde::Serialize for BidRequest {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let (site, app) = crate::distribution_channel::flat::split(self.channel.as_ref());
+        let wire = Wire {
+            id: Cow::Borrowed(&self.id),
+            imp: Cow::Borrowed(&self.imp),
+            site: site.map(Cow::Borrowed),
+            app: app.map(Cow::Borrowed),
+            device: self.device.as_ref().map(Cow::Borrowed),
+            user: self.user.as_ref().map(Cow::Borrowed),
+            test: self.test,
+            at: self.at,
+            tmax: self.tmax,
+            wseat: self.wseat.as_deref().map(Cow::Borrowed),
+            bseat: self.bseat.as_deref().map(Cow::Borrowed),
+            allimps: self.allimps,
+            cur: self.cur.as_deref().map(Cow::Borrowed),
+            wlang: self.wlang.as_deref().map(Cow::Borrowed),
+            bcat: self.bcat.as_deref().map(Cow::Borrowed),
+            badv: self.badv.as_deref().map(Cow::Borrowed),
+            bapp: self.bapp.as_deref().map(Cow::Borrowed),
+            source: self.source.as_ref().map(Cow::Borrowed),
+            regs: self.regs.as_ref().map(Cow::Borrowed),
+            ext: self.ext.as_ref().map(Cow::Borrowed),
+        };
+        wire.serialize(serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for BidRequest {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let v = Wire::deserialize(deserializer)?;
+        let site = v.site.map(Cow::into_owned);
+        let app = v.app.map(Cow::into_owned);
+        let channel = crate::distribution_channel::flat::join(site, app);
+        Ok(Self {
+            id: v.id.into_owned(),
+            imp: v.imp.into_owned(),
+            channel,
+            device: v.device.map(Cow::into_owned),
+            user: v.user.map(Cow::into_owned),
+            test: v.test,
+            at: v.at,
+            tmax: v.tmax,
+            wseat: v.wseat.map(Cow::into_owned),
+            bseat: v.bseat.map(Cow::into_owned),
+            allimps: v.allimps,
+            cur: v.cur.map(Cow::into_owned),
+            wlang: v.wlang.map(Cow::into_owned),
+            bcat: v.bcat.map(Cow::into_owned),
+            badv: v.badv.map(Cow::into_owned),
+            bapp: v.bapp.map(Cow::into_owned),
+            source: v.source.map(Cow::into_owned),
+            regs: v.regs.map(Cow::into_owned),
+            ext: v.ext.map(Cow::into_owned),
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -150,6 +351,50 @@ mod test {
         assert_eq!(serde_json::to_string(&o1)?, json);
         assert_eq!(o1, serde_json::from_str::<BidRequest>(json)?);
 
+        let json = r#"{"id":"","imp":[],"app":{"id":"1"}}"#;
+        let o2 = serde_json::from_str::<BidRequest>(json)?;
+        assert!(matches!(
+            o2.channel,
+            Some(crate::DistributionChannel::App(_))
+        ));
+        assert_eq!(serde_json::to_string(&o2)?, json);
+
+        assert!(
+            serde_json::from_str::<BidRequest>(r#"{"id":"","imp":[],"site":{"mobile":2}}"#)
+                .is_err()
+        );
+        let err = serde_json::from_str::<BidRequest>(r#"{"id":"","imp":[],"at":3}"#).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("expected 1 or 2 or greater than 500"));
+
+        let o3 = serde_json::from_str::<BidRequest>(r#"{"id":"","imp":[],"site":{},"app":{}}"#)?;
+        assert!(matches!(
+            o3.channel,
+            Some(crate::DistributionChannel::Site(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn allows_language() -> serde_json::Result<()> {
+        let mut req: BidRequest =
+            serde_json::from_str(r#"{"id":"1","imp":[],"wlang":["EN","de"]}"#)?;
+        let bid = |language: Option<&str>| crate::Bid {
+            language: language.map(Into::into),
+            ..Default::default()
+        };
+
+        assert!(req.allows_language(&bid(Some("en-US"))));
+        assert!(req.allows_language(&bid(Some("XX"))));
+        assert!(req.allows_language(&bid(None)));
+        assert!(!req.allows_language(&bid(Some("fr"))));
+        assert!(!req.allows_language(&bid(Some("english"))));
+
+        req.wlang = Some(vec![]);
+        assert!(req.allows_language(&bid(Some("fr"))));
+
         Ok(())
     }
 }
@@ -10,6 +10,12 @@
 /// the bidder wishes to convey to the exchange a reason for not bidding, just a BidResponse object
 /// is returned with a reason code in the nbr attribute.
 #[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::recommended)
+)]
 pub struct BidResponse {
     /// string; required
     /// ID of the bid request to which this is a response.
@@ -46,6 +52,7 @@ pub struct BidResponse {
     /// object
     /// Placeholder for bidder-specific extensions to OpenRTB.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::ext))]
     pub ext: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
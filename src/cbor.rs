@@ -0,0 +1,17 @@
+/// Serializes the given value as CBOR.
+pub fn to_cbor<T>(value: &T) -> Result<Vec<u8>, ciborium::ser::Error<std::io::Error>>
+where
+    T: serde::Serialize + ?Sized,
+{
+    let mut buf = Vec::new();
+    ciborium::into_writer(value, &mut buf)?;
+    Ok(buf)
+}
+
+/// Deserializes an instance of type `T` from CBOR.
+pub fn from_cbor<T>(v: &[u8]) -> Result<T, ciborium::de::Error<std::io::Error>>
+where
+    T: serde::de::DeserializeOwned,
+{
+    ciborium::from_reader(v)
+}
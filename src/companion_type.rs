@@ -6,6 +6,12 @@ use serde_repr::{Deserialize_repr, Serialize_repr};
 /// apply to video and audio ads. This table is derived from VAST 2.0+ and DAAST 1.0 specifications.
 /// Refer to www.iab.com/guidelines/digital-video-suite for more information.
 #[derive(Serialize_repr, Deserialize_repr, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::repr::<Self>)
+)]
 #[repr(i32)]
 pub enum CompanionType {
     /// Static Resource
@@ -7,6 +7,12 @@
 /// syndication method. For example might be a video impression embedded in an iframe on an unknown
 /// web property or device.
 #[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::recommended)
+)]
 pub struct Content {
     /// string
     /// ID uniquely identifying the content.
@@ -119,6 +125,10 @@ pub struct Content {
         skip_serializing_if = "Option::is_none",
         with = "crate::serde::i32_as_opt_bool"
     )]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(schema_with = "crate::serde::i32_as_opt_bool::schema")
+    )]
     pub livestream: Option<bool>,
 
     /// integer
@@ -128,6 +138,10 @@ pub struct Content {
         skip_serializing_if = "Option::is_none",
         with = "crate::serde::i32_as_opt_bool"
     )]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(schema_with = "crate::serde::i32_as_opt_bool::schema")
+    )]
     pub sourcerelationship: Option<bool>,
 
     /// integer
@@ -137,9 +151,17 @@ pub struct Content {
 
     /// string
     /// Content language using ISO-639-1-alpha-2.
-    // TODO: ISO-639-1-alpha-2
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub language: Option<i32>,
+    /// Parsed as a [`Language`] by [`Content::language_code`]. An integer, as written by earlier
+    /// versions of this crate, is read as `None`.
+    ///
+    /// [`Language`]: ./struct.Language.html
+    /// [`Content::language_code`]: ./struct.Content.html#method.language_code
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::serde::string_or_i32::deserialize"
+    )]
+    pub language: Option<String>,
 
     /// integer
     /// Indicator of whether or not the content is embeddable (e.g., an embeddable video player),
@@ -149,6 +171,10 @@ pub struct Content {
         skip_serializing_if = "Option::is_none",
         with = "crate::serde::i32_as_opt_bool"
     )]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(schema_with = "crate::serde::i32_as_opt_bool::schema")
+    )]
     pub embeddable: Option<bool>,
 
     /// object array
@@ -160,9 +186,35 @@ pub struct Content {
     /// object
     /// Placeholder for exchange-specific extensions to OpenRTB.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::ext))]
     pub ext: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
+impl Content {
+    /// Parses `language` as an ISO-639-1 code, see [`Language`].
+    ///
+    /// [`Language`]: ./struct.Language.html
+    pub fn language_code(&self) -> Option<crate::Language> {
+        self.language.as_deref().and_then(|v| v.parse().ok())
+    }
+
+    /// Indexes the segments of `data`.
+    pub fn segments(&self) -> crate::SegmentIndex<'_> {
+        crate::SegmentIndex::new(self.data.as_deref().unwrap_or_default())
+    }
+
+    /// Returns the first `Data` of `provider`, adding one if there is none.
+    pub fn data_mut(&mut self, provider: crate::DataProvider<'_>) -> &mut crate::Data {
+        crate::segment_index::data_mut(&mut self.data, provider)
+    }
+
+    /// Removes the segment with the given id from every `Data` of `provider`. Returns `true` if
+    /// any was removed.
+    pub fn remove_segment(&mut self, provider: crate::DataProvider<'_>, id: &str) -> bool {
+        crate::segment_index::remove_segment(&mut self.data, provider, id)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -174,6 +226,21 @@ mod test {
         assert_eq!(serde_json::to_string(&o1)?, json);
         assert_eq!(o1, serde_json::from_str::<Content>(json)?);
 
+        let json = r#"{"language":"en"}"#;
+        let o2 = serde_json::from_str::<Content>(json)?;
+        assert_eq!(serde_json::to_string(&o2)?, json);
+        // Earlier versions of this crate read and wrote an integer, which names no language.
+        assert_eq!(
+            serde_json::from_str::<Content>(r#"{"language":1}"#)?.language,
+            None
+        );
+        assert_eq!(
+            serde_json::from_str::<Content>(r#"{"language":null}"#)?.language,
+            None
+        );
+        let err = serde_json::from_str::<Content>(r#"{"language":true}"#).unwrap_err();
+        assert!(err.to_string().contains("expected a string or an integer"));
+
         Ok(())
     }
 }
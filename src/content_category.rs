@@ -5,6 +5,8 @@
 /// various objects. This OpenRTB table has values derived from the IAB Tech Lab Content Taxonomy.
 /// Practitioners should keep in sync with updates as published on www.iab.com.
 #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "SCREAMING-KEBAB-CASE")]
 pub enum ContentCategory {
     /// Arts & Entertainment
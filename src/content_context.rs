@@ -7,6 +7,12 @@ use serde_repr::{Deserialize_repr, Serialize_repr};
 /// from the Inventory Quality Guidelines (IQG). Practitioners should keep in sync with updates to
 /// the IQG values.
 #[derive(Serialize_repr, Deserialize_repr, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::repr::<Self>)
+)]
 #[repr(i32)]
 pub enum ContentContext {
     /// Video (i.e., video file or stream such as Internet TV broadcasts)
@@ -6,6 +6,12 @@ use serde_repr::{Deserialize_repr, Serialize_repr};
 /// being served or serve as restrictions of thereof.
 #[allow(non_camel_case_types)]
 #[derive(Serialize_repr, Deserialize_repr, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::repr::<Self>)
+)]
 #[repr(i32)]
 pub enum CreativeAttribute {
     /// Audio Ad (Auto-Play)
@@ -0,0 +1,241 @@
+/// A restriction of the media object targeted by a bid that the bid violates, as returned by
+/// [`Imp::creative_blocks`].
+///
+/// [`Imp::creative_blocks`]: ./struct.Imp.html#method.creative_blocks
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CreativeBlock {
+    /// An attribute of `Bid.attr` is in the `battr` of the media object.
+    BlockedAttribute(crate::CreativeAttribute),
+    /// `Bid.api` is not in the `api` of the media object.
+    ApiNotSupported(crate::ApiFramework),
+    /// A banner type of the markup of the bid is in `Banner.btype`.
+    BlockedBannerType(crate::BannerAdType),
+}
+
+impl CreativeBlock {
+    /// Returns the loss reason reported for a bid blocked for this reason.
+    pub fn loss_reason(&self) -> crate::LossReason {
+        match self {
+            Self::BlockedAttribute(_) => crate::LossReason::CreativeFiltered_AttributeExclusions,
+            Self::ApiNotSupported(_) | Self::BlockedBannerType(_) => {
+                crate::LossReason::CreativeFiltered_AdTypeExclusions
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for CreativeBlock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BlockedAttribute(v) => write!(f, "creative attribute {:?} is blocked", v),
+            Self::ApiNotSupported(v) => write!(f, "API framework {:?} is not supported", v),
+            Self::BlockedBannerType(v) => write!(f, "banner type {:?} is blocked", v),
+        }
+    }
+}
+
+/// Classifies banner markup: a `<script>` is a JavaScript ad and an `<iframe>` an iframe ad;
+/// other markup is an XHTML banner ad, and text without tags an XHTML text ad.
+pub(crate) fn banner_ad_types(adm: &str) -> Vec<crate::BannerAdType> {
+    use crate::BannerAdType::*;
+
+    let lower = adm.to_ascii_lowercase();
+    let has_tag = |name: &str| {
+        lower.match_indices(name).any(|(i, _)| {
+            lower[i + name.len()..].starts_with(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        })
+    };
+    let mut types = Vec::new();
+    if has_tag("<script") {
+        types.push(JavaScriptAd);
+    }
+    if has_tag("<iframe") {
+        types.push(Iframe);
+    }
+    if types.is_empty() {
+        let is_markup = lower
+            .match_indices('<')
+            .any(|(i, _)| lower[i + 1..].starts_with(|c: char| c.is_ascii_alphabetic()));
+        types.push(if is_markup {
+            XhtmlBannerAd
+        } else {
+            XhtmlTextAd
+        });
+    }
+    types
+}
+
+/// The restrictions of one media object of an impression.
+struct Restrictions<'a> {
+    battr: &'a [crate::CreativeAttribute],
+    api: &'a [crate::ApiFramework],
+    btype: &'a [crate::BannerAdType],
+}
+
+fn restrictions(imp: &crate::Imp, media: crate::MediaType) -> Option<Restrictions<'_>> {
+    match media {
+        crate::MediaType::Banner => imp.banner.as_ref().map(|v| Restrictions {
+            battr: v.battr.as_deref().unwrap_or_default(),
+            api: v.api.as_deref().unwrap_or_default(),
+            btype: v.btype.as_deref().unwrap_or_default(),
+        }),
+        crate::MediaType::Video => imp.video.as_ref().map(|v| Restrictions {
+            battr: v.battr.as_deref().unwrap_or_default(),
+            api: v.api.as_deref().unwrap_or_default(),
+            btype: &[],
+        }),
+        crate::MediaType::Audio => imp.audio.as_ref().map(|v| Restrictions {
+            battr: v.battr.as_deref().unwrap_or_default(),
+            api: v.api.as_deref().unwrap_or_default(),
+            btype: &[],
+        }),
+        crate::MediaType::Native => imp.native.as_ref().map(|v| Restrictions {
+            battr: v.battr.as_deref().unwrap_or_default(),
+            api: v.api.as_deref().unwrap_or_default(),
+            btype: &[],
+        }),
+    }
+}
+
+fn blocks(bid: &crate::Bid, r: &Restrictions<'_>, media: crate::MediaType) -> Vec<CreativeBlock> {
+    let mut blocks = Vec::new();
+    for attr in bid.attr.iter().flatten() {
+        let block = CreativeBlock::BlockedAttribute(*attr);
+        if r.battr.contains(attr) && !blocks.contains(&block) {
+            blocks.push(block);
+        }
+    }
+    if let Some(api) = bid.api {
+        if !r.api.contains(&api) {
+            blocks.push(CreativeBlock::ApiNotSupported(api));
+        }
+    }
+    if media == crate::MediaType::Banner && !r.btype.is_empty() {
+        if let Some(adm) = bid.adm.as_deref() {
+            for v in banner_ad_types(adm) {
+                if r.btype.contains(&v) {
+                    blocks.push(CreativeBlock::BlockedBannerType(v));
+                }
+            }
+        }
+    }
+    blocks
+}
+
+pub(crate) fn creative_blocks(imp: &crate::Imp, bid: &crate::Bid) -> Vec<CreativeBlock> {
+    let candidates = match bid.infer_media_type(imp) {
+        Ok(v) => [v].iter().copied().collect(),
+        Err(crate::MediaTypeError::Ambiguous(v)) => v,
+        Err(crate::MediaTypeError::NoMatch { offered, .. }) => offered,
+    };
+    // A bid whose media type is unknown is blocked only for what every candidate blocks.
+    let mut found: Option<Vec<CreativeBlock>> = None;
+    for media in candidates.iter() {
+        let r = match restrictions(imp, media) {
+            Some(v) => v,
+            None => continue,
+        };
+        let v = blocks(bid, &r, media);
+        found = Some(match found {
+            Some(found) => found.into_iter().filter(|b| v.contains(b)).collect(),
+            None => v,
+        });
+    }
+    found.unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ApiFramework, BannerAdType, CreativeAttribute};
+
+    #[test]
+    fn banner_types() {
+        assert_eq!(
+            banner_ad_types(r#"<SCRIPT src="https://a.example/t.js"></SCRIPT>"#),
+            vec![BannerAdType::JavaScriptAd]
+        );
+        assert_eq!(
+            banner_ad_types(r#"<iframe src="https://a.example"/><script>x()</script>"#),
+            vec![BannerAdType::JavaScriptAd, BannerAdType::Iframe]
+        );
+        assert_eq!(
+            banner_ad_types(r#"<a href="https://a.example"><img src="a.png"></a>"#),
+            vec![BannerAdType::XhtmlBannerAd]
+        );
+        assert_eq!(
+            banner_ad_types("Buy now <3 <scripted>"),
+            vec![BannerAdType::XhtmlBannerAd]
+        );
+        assert_eq!(
+            banner_ad_types("Buy now <3"),
+            vec![BannerAdType::XhtmlTextAd]
+        );
+    }
+
+    #[test]
+    fn check() -> serde_json::Result<()> {
+        let imp: crate::Imp = serde_json::from_str(
+            r#"{
+                "id": "1",
+                "banner": {"w": 300, "h": 250, "battr": [6, 7], "btype": [3], "api": [3, 5]},
+                "video": {"mimes": ["video/mp4"], "battr": [16], "api": [2]}
+            }"#,
+        )?;
+        let bid = |json: &str| serde_json::from_str::<crate::Bid>(json);
+
+        let b = bid(
+            r#"{"id":"1","impid":"1","price":1,"attr":[7,1,7],"api":3,"adm":"<script src=\"https://a.example/t.js\"></script>"}"#,
+        )?;
+        let blocks = imp.creative_blocks(&b);
+        assert_eq!(
+            blocks,
+            vec![
+                CreativeBlock::BlockedAttribute(CreativeAttribute::InBannerVideoAd_UserInitiated),
+                CreativeBlock::BlockedBannerType(BannerAdType::JavaScriptAd),
+            ]
+        );
+        assert_eq!(
+            blocks[0].loss_reason(),
+            crate::LossReason::CreativeFiltered_AttributeExclusions
+        );
+        assert_eq!(
+            blocks[1].loss_reason(),
+            crate::LossReason::CreativeFiltered_AdTypeExclusions
+        );
+
+        let b = bid(
+            r#"{"id":"1","impid":"1","price":1,"attr":[16],"api":1,"adm":"<VAST version=\"3.0\"></VAST>"}"#,
+        )?;
+        assert_eq!(
+            imp.creative_blocks(&b),
+            vec![
+                CreativeBlock::BlockedAttribute(CreativeAttribute::AdProvidesSkipButton),
+                CreativeBlock::ApiNotSupported(ApiFramework::Vpaid1),
+            ]
+        );
+
+        let b = bid(r#"{"id":"1","impid":"1","price":1,"api":5,"adm":"<img src=a.png>"}"#)?;
+        assert!(imp.creative_blocks(&b).is_empty());
+
+        // Without markup the bid may be a banner or video: only common blocks apply.
+        let b = bid(r#"{"id":"1","impid":"1","price":1,"attr":[6,16]}"#)?;
+        assert!(imp.creative_blocks(&b).is_empty());
+        let imp = crate::Imp { video: None, ..imp };
+        assert_eq!(
+            imp.creative_blocks(&b),
+            vec![CreativeBlock::BlockedAttribute(
+                CreativeAttribute::InBannerVideoAd_AutoPlay
+            )]
+        );
+
+        // VPAID where only MRAID is allowed.
+        let b = bid(r#"{"id":"1","impid":"1","price":1,"api":1,"adm":"<div>ad</div>"}"#)?;
+        assert_eq!(
+            imp.creative_blocks(&b),
+            vec![CreativeBlock::ApiNotSupported(ApiFramework::Vpaid1)]
+        );
+
+        Ok(())
+    }
+}
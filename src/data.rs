@@ -6,6 +6,12 @@
 /// multiple providers. The specific data providers in use should be published by the exchange a
 /// priori to its bidders.
 #[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::recommended)
+)]
 pub struct Data {
     /// string
     /// Exchange-specific ID for the data provider.
@@ -25,9 +31,54 @@ pub struct Data {
     /// object
     /// Placeholder for exchange-specific extensions to OpenRTB.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::ext))]
     pub ext: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
+impl Data {
+    /// Returns the Seller-Defined Audiences taxonomy of the segments, `ext.segtax`.
+    pub fn segtax(&self) -> Option<i32> {
+        let segtax = self.ext.as_ref()?.get("segtax")?.as_i64()?;
+        std::convert::TryFrom::try_from(segtax).ok()
+    }
+
+    /// Sets the Seller-Defined Audiences taxonomy of the segments, `ext.segtax`.
+    pub fn set_segtax(&mut self, segtax: i32) {
+        self.ext
+            .get_or_insert_with(Default::default)
+            .insert("segtax".to_owned(), segtax.into());
+    }
+
+    /// Iterates over the ids of the segments.
+    pub fn segment_ids(&self) -> impl Iterator<Item = &str> {
+        self.segment
+            .iter()
+            .flatten()
+            .filter_map(|v| v.id.as_deref())
+    }
+
+    /// Returns `true` if a segment has the given id.
+    pub fn has_segment(&self, id: &str) -> bool {
+        self.segment_ids().any(|v| v == id)
+    }
+
+    /// Adds a segment, unless one with the same id is present. Returns `true` if it was added.
+    pub fn add_segment(&mut self, segment: crate::Segment) -> bool {
+        if segment.id.as_deref().is_some_and(|id| self.has_segment(id)) {
+            return false;
+        }
+        self.segment.get_or_insert_with(Vec::new).push(segment);
+        true
+    }
+
+    /// Removes and returns the segment with the given id.
+    pub fn remove_segment(&mut self, id: &str) -> Option<crate::Segment> {
+        let segments = self.segment.as_mut()?;
+        let i = segments.iter().position(|v| v.id.as_deref() == Some(id))?;
+        Some(segments.remove(i))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -41,4 +92,33 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn segments() -> serde_json::Result<()> {
+        let mut o1: Data = serde_json::from_str(
+            r#"{"name":"publisher.example","segment":[{"id":"1"},{"id":"2"}],"ext":{"segtax":4}}"#,
+        )?;
+        assert_eq!(o1.segtax(), Some(4));
+        assert!(o1.has_segment("2"));
+
+        assert!(!o1.add_segment(crate::Segment {
+            id: Some("1".into()),
+            ..Default::default()
+        }));
+        assert!(o1.add_segment(crate::Segment {
+            id: Some("3".into()),
+            ..Default::default()
+        }));
+        assert_eq!(o1.remove_segment("1").and_then(|v| v.id), Some("1".into()));
+        assert_eq!(o1.remove_segment("1"), None);
+        assert_eq!(o1.segment_ids().collect::<Vec<_>>(), vec!["2", "3"]);
+
+        o1.set_segtax(6);
+        assert_eq!(
+            serde_json::to_string(&o1)?,
+            r#"{"name":"publisher.example","segment":[{"id":"2"},{"id":"3"}],"ext":{"segtax":6}}"#
+        );
+
+        Ok(())
+    }
 }
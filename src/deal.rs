@@ -4,6 +4,12 @@
 /// Its presence with the Pmp collection indicates that this impression is available under the terms
 /// of that deal. Refer to Section 7.3 for more details.
 #[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::recommended)
+)]
 pub struct Deal {
     /// string; required
     /// A unique identifier for the direct deal.
@@ -12,6 +18,7 @@ pub struct Deal {
     /// float; default 0
     /// Minimum bid for this impression expressed in CPM.
     #[serde(default, skip_serializing_if = "default_ext::DefaultExt::is_default")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::price))]
     pub bidfloor: f64,
 
     /// string; default ”USD”
@@ -25,8 +32,8 @@ pub struct Deal {
     /// Optional override of the overall auction type of the bid request, where 1 = First Price, 2
     /// = Second Price Plus, 3 = the value passed in bidfloor is the agreed upon deal price.
     /// Additional auction types can be defined by the exchange.
-    #[serde(default, skip_serializing_if = "default_ext::DefaultExt::is_default")]
-    pub at: crate::AuctionType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub at: Option<crate::AuctionType>,
 
     /// string array
     /// Whitelist of buyer seats (e.g., advertisers, agencies) allowed to bid on this deal. IDs of
@@ -44,6 +51,7 @@ pub struct Deal {
     /// object
     /// Placeholder for exchange-specific extensions to OpenRTB.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::ext))]
     pub ext: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
@@ -60,6 +68,13 @@ mod test {
         assert_eq!(serde_json::to_string(&o1)?, json);
         assert_eq!(o1, serde_json::from_str::<Deal>(json)?);
 
+        let json = r#"{"id":"1","at":2}"#;
+        let o2 = serde_json::from_str::<Deal>(json)?;
+        assert_eq!(o2.at, Some(crate::AuctionType::SecondPricePlus));
+        assert_eq!(serde_json::to_string(&o2)?, json);
+        let o3 = serde_json::from_str::<Deal>(r#"{"id":"1","at":3}"#)?;
+        assert_eq!(o3.at, Some(crate::AuctionType::DealPrice));
+
         Ok(())
     }
 }
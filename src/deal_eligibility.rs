@@ -0,0 +1,69 @@
+/// The terms under which a bid takes part in the auction of an impression, as returned by
+/// [`Imp::deal_eligibility`].
+///
+/// [`Imp::deal_eligibility`]: ./struct.Imp.html#method.deal_eligibility
+#[derive(Debug, PartialEq, Clone)]
+pub struct DealEligibility<'a> {
+    /// The deal referenced by `Bid.dealid`, if the impression offers it.
+    pub deal: Option<&'a crate::Deal>,
+
+    /// Why the bid may not take part; empty if it may.
+    pub reasons: Vec<Ineligibility>,
+
+    /// Whether bids outside of the deals are accepted at all, i.e. `Pmp.private_auction` is not
+    /// set.
+    pub open_auction: bool,
+
+    /// The floor that applies: that of the deal, or of the impression for an open auction bid.
+    pub bidfloor: f64,
+
+    /// The currency of `bidfloor`.
+    pub bidfloorcur: &'a str,
+
+    /// The auction type that clears the bid: `Deal.at` if the deal overrides it, else the auction
+    /// type of the request.
+    pub at: crate::AuctionType,
+}
+
+/// A reason why a bid may not take part in the auction of an impression.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Ineligibility {
+    /// The bid references no deal, but the impression is restricted to its deals.
+    PrivateAuction,
+    /// The bid references a deal the impression does not offer.
+    UnknownDeal(String),
+    /// The seat of the bid is not in `Deal.wseat`.
+    SeatNotAllowed,
+    /// No domain of `Bid.adomain` is in `Deal.wadomain`.
+    AdvertiserNotAllowed,
+}
+
+impl DealEligibility<'_> {
+    /// Returns `true` if the bid may take part in the auction.
+    pub fn is_eligible(&self) -> bool {
+        self.reasons.is_empty()
+    }
+
+    /// Returns whether `price` in `cur` meets the floor, or `None` if the currencies differ.
+    pub fn meets_floor(&self, price: f64, cur: &str) -> Option<bool> {
+        if !cur.eq_ignore_ascii_case(self.bidfloorcur) {
+            return None;
+        }
+        Some(match self.at {
+            // The agreed price is the only acceptable one.
+            crate::AuctionType::DealPrice => (price - self.bidfloor).abs() < 1e-9,
+            _ => price >= self.bidfloor,
+        })
+    }
+}
+
+impl std::fmt::Display for Ineligibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PrivateAuction => f.write_str("impression is restricted to its deals"),
+            Self::UnknownDeal(id) => write!(f, "deal {} is not offered", id),
+            Self::SeatNotAllowed => f.write_str("seat is not allowed by the deal"),
+            Self::AdvertiserNotAllowed => f.write_str("advertiser is not allowed by the deal"),
+        }
+    }
+}
@@ -6,6 +6,12 @@ use serde::{Deserialize, Serialize};
 /// Device information includes its hardware, platform, location, and carrier data. The device can
 /// refer to a mobile handset, a desktop computer, set top box, or other digital device.
 #[derive(Serialize, Deserialize, Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::recommended)
+)]
 pub struct Device {
     /// string; recommended
     /// Browser user agent string.
@@ -26,6 +32,10 @@ pub struct Device {
         skip_serializing_if = "Option::is_none",
         with = "crate::serde::i32_as_opt_bool"
     )]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(schema_with = "crate::serde::i32_as_opt_bool::schema")
+    )]
     pub dnt: Option<bool>,
 
     /// integer; recommended
@@ -36,6 +46,10 @@ pub struct Device {
         skip_serializing_if = "Option::is_none",
         with = "crate::serde::i32_as_opt_bool"
     )]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(schema_with = "crate::serde::i32_as_opt_bool::schema")
+    )]
     pub lmt: Option<bool>,
 
     /// string; recommended
@@ -105,6 +119,10 @@ pub struct Device {
         skip_serializing_if = "Option::is_none",
         with = "crate::serde::i32_as_opt_bool"
     )]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(schema_with = "crate::serde::i32_as_opt_bool::schema")
+    )]
     pub js: Option<bool>,
 
     /// integer
@@ -115,6 +133,10 @@ pub struct Device {
         skip_serializing_if = "Option::is_none",
         with = "crate::serde::i32_as_opt_bool"
     )]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(schema_with = "crate::serde::i32_as_opt_bool::schema")
+    )]
     pub geofetch: Option<bool>,
 
     /// string
@@ -124,7 +146,10 @@ pub struct Device {
 
     /// string
     /// Browser language using ISO-639-1-alpha-2.
-    // TODO: ISO-639-1-alpha-2
+    /// Parsed as a [`Language`] by [`Device::language_code`].
+    ///
+    /// [`Language`]: ./struct.Language.html
+    /// [`Device::language_code`]: ./struct.Device.html#method.language_code
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub language: Option<String>,
 
@@ -185,9 +210,48 @@ pub struct Device {
     /// object
     /// Placeholder for exchange-specific extensions to OpenRTB.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::ext))]
     pub ext: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
+impl Device {
+    /// Parses `language` as an ISO-639-1 code, see [`Language`].
+    ///
+    /// [`Language`]: ./struct.Language.html
+    pub fn language_code(&self) -> Option<crate::Language> {
+        self.language.as_deref().and_then(|v| v.parse().ok())
+    }
+
+    /// Returns the type of `ifa`: `ext.ifa_type` if set, otherwise the type inferred from `os`
+    /// (see [`IfaType::from_os`]) if there is an `ifa`.
+    ///
+    /// [`IfaType::from_os`]: ./enum.IfaType.html#method.from_os
+    #[cfg(feature = "device-ids")]
+    pub fn ifa_type(&self) -> Option<crate::IfaType> {
+        let ext = self.ext.as_ref().and_then(|v| v.get("ifa_type"));
+        if let Some(v) = ext.and_then(|v| v.as_str()) {
+            return crate::IfaType::parse(v);
+        }
+        self.ifa.as_ref()?;
+        self.os.as_deref().and_then(crate::IfaType::from_os)
+    }
+
+    /// Returns `true` if ad tracking is limited: `lmt` is 1 or `ifa` is the all-zero UUID.
+    #[cfg(feature = "device-ids")]
+    pub fn is_tracking_limited(&self) -> bool {
+        self.lmt == Some(true) || self.ifa.as_deref().is_some_and(crate::is_zero_ifa)
+    }
+
+    /// Fills the missing `devicetype`, `make`, `model`, `os`, `osv` and `js` from `ua`, see
+    /// [`UserAgent`]. Fields already set are never overwritten. The names of the filled fields
+    /// are returned and added to the array `ext.ua_inferred`.
+    ///
+    /// [`UserAgent`]: ./struct.UserAgent.html
+    pub fn enrich_from_ua(&mut self) -> Vec<&'static str> {
+        crate::user_agent::enrich(self)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -201,4 +265,22 @@ mod test {
 
         Ok(())
     }
+
+    #[cfg(feature = "device-ids")]
+    #[test]
+    fn ifa() -> serde_json::Result<()> {
+        let device: Device = serde_json::from_str(
+            r#"{"os":"Roku","ifa":"00000000-0000-0000-0000-000000000000","lmt":0}"#,
+        )?;
+        assert_eq!(device.ifa_type(), Some(crate::IfaType::Rida));
+        assert!(device.is_tracking_limited());
+
+        let device: Device =
+            serde_json::from_str(r#"{"os":"Android","ifa":"x","ext":{"ifa_type":"ppid"}}"#)?;
+        assert_eq!(device.ifa_type(), Some(crate::IfaType::Ppid));
+        assert!(!device.is_tracking_limited());
+        assert_eq!(Device::default().ifa_type(), None);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,462 @@
+use md5::Md5;
+use sha1::{Digest, Sha1};
+
+/// The raw device IDs from which the ID fields of a [`Device`] are derived: the advertising ID
+/// `ifa`, and the IDs hashed into `didsha1`/`didmd5`, `dpidsha1`/`dpidmd5` and `macsha1`/`macmd5`.
+///
+/// Raw IDs are trimmed of whitespace and hashed as given, except the IFA which is normalised, see
+/// [`normalize_ifa`].
+///
+/// ```
+/// # use openrtb2::{Device, DeviceIds, HashedId, IdError};
+/// let ids = DeviceIds {
+///     ifa: Some("6D92078A-8246-4BA4-AE5B-76104861E7DC".into()),
+///     dpid: Some("9774d56d682e549c".into()),
+///     ..Default::default()
+/// };
+/// let mut device = Device::default();
+/// ids.apply(&mut device);
+/// assert_eq!(device.ifa.as_deref(), Some("6d92078a-8246-4ba4-ae5b-76104861e7dc"));
+/// assert_eq!(device.dpidsha1.as_deref(), Some("41e29575c7361b2924f701502ca6d932b45b9e51"));
+/// assert!(ids.check(&device).is_empty());
+///
+/// device.dpidmd5 = Some("not a hash".into());
+/// assert_eq!(ids.check(&device), vec![IdError::Malformed(HashedId::DpidMd5)]);
+/// ```
+///
+/// [`Device`]: ./struct.Device.html
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct DeviceIds {
+    /// Advertising ID, e.g. an IDFA or AAID.
+    pub ifa: Option<String>,
+    /// Hardware device ID, e.g. an IMEI.
+    pub did: Option<String>,
+    /// Platform device ID, e.g. an Android ID.
+    pub dpid: Option<String>,
+    /// MAC address.
+    pub mac: Option<String>,
+}
+
+/// A hashed ID field of [`Device`].
+///
+/// [`Device`]: ./struct.Device.html
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum HashedId {
+    /// `didsha1`
+    DidSha1,
+    /// `didmd5`
+    DidMd5,
+    /// `dpidsha1`
+    DpidSha1,
+    /// `dpidmd5`
+    DpidMd5,
+    /// `macsha1`
+    MacSha1,
+    /// `macmd5`
+    MacMd5,
+}
+
+/// An inconsistency between [`DeviceIds`] and the ID fields of a [`Device`], as returned by
+/// [`DeviceIds::check`].
+///
+/// [`Device`]: ./struct.Device.html
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum IdError {
+    /// `ifa` is not a UUID, though its type is a UUID-based advertising ID.
+    MalformedIfa,
+    /// `ifa` differs from the raw IFA.
+    IfaMismatch,
+    /// The field is not a lower or upper case hex digest of the right length.
+    Malformed(HashedId),
+    /// The field is not the hash of the raw ID.
+    Mismatch(HashedId),
+}
+
+/// The type of a [`Device#ifa`], as carried in `Device.ext.ifa_type` per the IAB Guidelines for
+/// Identifier for Advertising (IFA) on CTV/OTT platforms.
+///
+/// [`Device#ifa`]: ./struct.Device.html#structfield.ifa
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum IfaType {
+    /// `idfa`: Apple Identifier for Advertisers.
+    Idfa,
+    /// `aaid`: Android Advertising ID.
+    Aaid,
+    /// `rida`: Roku ID for Advertisers.
+    Rida,
+    /// `tifa`: Samsung Tizen Identifier for Advertising.
+    Tifa,
+    /// `afai`: Amazon Fire Advertising Identifier.
+    Afai,
+    /// `vida`: Vizio Advertising ID.
+    Vida,
+    /// `lgudid`: LG Unique Device ID.
+    Lgudid,
+    /// `msai`: Microsoft Advertising ID.
+    Msai,
+    /// `oaid`: Huawei Open Advertising ID.
+    Oaid,
+    /// `ppid`: Publisher provided ID.
+    Ppid,
+    /// `sspid`: SSP provided ID.
+    Sspid,
+    /// `dpid`: Generic device provided ID.
+    Dpid,
+    /// `sessionid`: Short-lived ID for the session.
+    SessionId,
+}
+
+impl DeviceIds {
+    /// Sets `ifa` of `device` to the normalised IFA, or the trimmed raw IFA if it is not a UUID,
+    /// and the hashed ID fields to the hashes of the raw IDs. Fields without a raw ID are left as
+    /// is.
+    pub fn apply(&self, device: &mut crate::Device) {
+        if let Some(ifa) = self.ifa.as_deref().map(str::trim) {
+            device.ifa = Some(normalize_ifa(ifa).unwrap_or_else(|| ifa.to_owned()));
+        }
+        for &id in HashedId::ALL.iter() {
+            if let Some(raw) = self.raw(id) {
+                *id.field_mut(device) = Some(id.hash(raw));
+            }
+        }
+    }
+
+    /// Checks the ID fields of `device` against these raw IDs: every hashed field must be a
+    /// well-formed digest and, if its raw ID is known, the hash of it; `ifa` must be the raw IFA,
+    /// ignoring the differences [`normalize_ifa`] removes, and a UUID unless its type (see
+    /// [`Device::ifa_type`]) is not UUID-based.
+    ///
+    /// [`Device::ifa_type`]: ./struct.Device.html#method.ifa_type
+    pub fn check(&self, device: &crate::Device) -> Vec<IdError> {
+        let mut errors = Vec::new();
+        if let Some(ifa) = device.ifa.as_deref() {
+            let normalized = normalize_ifa(ifa);
+            if normalized.is_none() && device.ifa_type().is_none_or(IfaType::is_uuid) {
+                errors.push(IdError::MalformedIfa);
+            }
+            if let Some(raw) = self.ifa.as_deref() {
+                let raw = normalize_ifa(raw).unwrap_or_else(|| raw.trim().to_owned());
+                if normalized.as_deref().unwrap_or(ifa) != raw {
+                    errors.push(IdError::IfaMismatch);
+                }
+            }
+        }
+        for &id in HashedId::ALL.iter() {
+            let value = match id.field(device) {
+                Some(v) => v,
+                None => continue,
+            };
+            if value.len() != id.len() || !value.bytes().all(|b| b.is_ascii_hexdigit()) {
+                errors.push(IdError::Malformed(id));
+            } else if let Some(raw) = self.raw(id) {
+                if !value.eq_ignore_ascii_case(&id.hash(raw)) {
+                    errors.push(IdError::Mismatch(id));
+                }
+            }
+        }
+        errors
+    }
+
+    fn raw(&self, id: HashedId) -> Option<&str> {
+        let raw = match id {
+            HashedId::DidSha1 | HashedId::DidMd5 => &self.did,
+            HashedId::DpidSha1 | HashedId::DpidMd5 => &self.dpid,
+            HashedId::MacSha1 | HashedId::MacMd5 => &self.mac,
+        };
+        raw.as_deref().map(str::trim)
+    }
+}
+
+impl HashedId {
+    const ALL: [Self; 6] = [
+        Self::DidSha1,
+        Self::DidMd5,
+        Self::DpidSha1,
+        Self::DpidMd5,
+        Self::MacSha1,
+        Self::MacMd5,
+    ];
+
+    /// Returns the name of the field.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::DidSha1 => "didsha1",
+            Self::DidMd5 => "didmd5",
+            Self::DpidSha1 => "dpidsha1",
+            Self::DpidMd5 => "dpidmd5",
+            Self::MacSha1 => "macsha1",
+            Self::MacMd5 => "macmd5",
+        }
+    }
+
+    /// Returns the lower case hex digest of `raw` with the hash function of the field.
+    pub fn hash(self, raw: &str) -> String {
+        match self {
+            Self::DidSha1 | Self::DpidSha1 | Self::MacSha1 => sha1_hex(raw),
+            Self::DidMd5 | Self::DpidMd5 | Self::MacMd5 => md5_hex(raw),
+        }
+    }
+
+    /// Returns the length of the hex digest.
+    fn len(self) -> usize {
+        match self {
+            Self::DidSha1 | Self::DpidSha1 | Self::MacSha1 => 40,
+            Self::DidMd5 | Self::DpidMd5 | Self::MacMd5 => 32,
+        }
+    }
+
+    fn field(self, device: &crate::Device) -> Option<&str> {
+        let field = match self {
+            Self::DidSha1 => &device.didsha1,
+            Self::DidMd5 => &device.didmd5,
+            Self::DpidSha1 => &device.dpidsha1,
+            Self::DpidMd5 => &device.dpidmd5,
+            Self::MacSha1 => &device.macsha1,
+            Self::MacMd5 => &device.macmd5,
+        };
+        field.as_deref()
+    }
+
+    fn field_mut(self, device: &mut crate::Device) -> &mut Option<String> {
+        match self {
+            Self::DidSha1 => &mut device.didsha1,
+            Self::DidMd5 => &mut device.didmd5,
+            Self::DpidSha1 => &mut device.dpidsha1,
+            Self::DpidMd5 => &mut device.dpidmd5,
+            Self::MacSha1 => &mut device.macsha1,
+            Self::MacMd5 => &mut device.macmd5,
+        }
+    }
+}
+
+impl IfaType {
+    /// Parses an `ifa_type` value, ignoring ASCII case.
+    pub fn parse(s: &str) -> Option<Self> {
+        let v = match s.trim().to_ascii_lowercase().as_str() {
+            "idfa" => Self::Idfa,
+            "aaid" => Self::Aaid,
+            "rida" => Self::Rida,
+            "tifa" => Self::Tifa,
+            "afai" => Self::Afai,
+            "vida" => Self::Vida,
+            "lgudid" => Self::Lgudid,
+            "msai" => Self::Msai,
+            "oaid" => Self::Oaid,
+            "ppid" => Self::Ppid,
+            "sspid" => Self::Sspid,
+            "dpid" => Self::Dpid,
+            "sessionid" => Self::SessionId,
+            _ => return None,
+        };
+        Some(v)
+    }
+
+    /// Returns the `ifa_type` value.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Idfa => "idfa",
+            Self::Aaid => "aaid",
+            Self::Rida => "rida",
+            Self::Tifa => "tifa",
+            Self::Afai => "afai",
+            Self::Vida => "vida",
+            Self::Lgudid => "lgudid",
+            Self::Msai => "msai",
+            Self::Oaid => "oaid",
+            Self::Ppid => "ppid",
+            Self::Sspid => "sspid",
+            Self::Dpid => "dpid",
+            Self::SessionId => "sessionid",
+        }
+    }
+
+    /// Returns `true` if IDs of this type are UUIDs, i.e. it is neither `ppid`, `sspid`,
+    /// `sessionid`, `dpid` nor `lgudid`.
+    pub fn is_uuid(self) -> bool {
+        !matches!(
+            self,
+            Self::Ppid | Self::Sspid | Self::SessionId | Self::Dpid | Self::Lgudid
+        )
+    }
+
+    /// Returns the advertising ID type of an operating system (`Device.os`), ignoring ASCII case:
+    /// `idfa` for iOS, iPadOS and tvOS, `aaid` for Android, `rida` for Roku, `tifa` for Tizen,
+    /// `afai` for Fire OS, `vida` for SmartCast and `lgudid` for webOS.
+    pub fn from_os(os: &str) -> Option<Self> {
+        let v = match os.trim().to_ascii_lowercase().as_str() {
+            "ios" | "ipados" | "tvos" => Self::Idfa,
+            "android" => Self::Aaid,
+            "roku" | "roku os" => Self::Rida,
+            "tizen" => Self::Tifa,
+            "fire os" | "fireos" | "amazon" => Self::Afai,
+            "smartcast" => Self::Vida,
+            "webos" => Self::Lgudid,
+            _ => return None,
+        };
+        Some(v)
+    }
+}
+
+impl std::fmt::Display for HashedId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::fmt::Display for IdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MalformedIfa => f.write_str("ifa is not a UUID"),
+            Self::IfaMismatch => f.write_str("ifa differs from the raw IFA"),
+            Self::Malformed(id) => write!(f, "{} is not a hex digest", id),
+            Self::Mismatch(id) => write!(f, "{} is not the hash of the raw ID", id),
+        }
+    }
+}
+
+impl std::fmt::Display for IfaType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Normalises an IFA to a lower case, hyphenated UUID, e.g. `6d92078a-8246-4ba4-ae5b-76104861e7dc`.
+/// Accepts any ASCII case, surrounding braces and a missing hyphenation. Returns `None` if the
+/// value is not a UUID.
+pub fn normalize_ifa(ifa: &str) -> Option<String> {
+    let s = ifa.trim();
+    let s = s
+        .strip_prefix('{')
+        .and_then(|s| s.strip_suffix('}'))
+        .unwrap_or(s);
+    let hex: String = if s.len() == 36 {
+        let hyphens = [8, 13, 18, 23];
+        if !hyphens.iter().all(|&i| s.as_bytes()[i] == b'-') {
+            return None;
+        }
+        s.chars().filter(|&c| c != '-').collect()
+    } else {
+        s.to_owned()
+    };
+    if hex.len() != 32 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let hex = hex.to_ascii_lowercase();
+    Some(format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    ))
+}
+
+/// Returns `true` if `ifa` is the all-zero UUID that iOS and Android report when the user limits
+/// ad tracking.
+pub fn is_zero_ifa(ifa: &str) -> bool {
+    normalize_ifa(ifa).is_some_and(|v| v.bytes().all(|b| b == b'0' || b == b'-'))
+}
+
+/// Returns the lower case hex SHA1 digest of `s`.
+pub fn sha1_hex(s: &str) -> String {
+    hex(&Sha1::digest(s.as_bytes()))
+}
+
+/// Returns the lower case hex MD5 digest of `s`.
+pub fn md5_hex(s: &str) -> String {
+    hex(&Md5::digest(s.as_bytes()))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn hashes() {
+        assert_eq!(sha1_hex("abc"), "a9993e364706816aba3e25717850c26c9cd0d89d");
+        assert_eq!(md5_hex("abc"), "900150983cd24fb0d6963f7d28e17f72");
+        assert_eq!(
+            HashedId::MacMd5.hash(""),
+            "d41d8cd98f00b204e9800998ecf8427e"
+        );
+    }
+
+    #[test]
+    fn ifa() {
+        let ifa = Some("6d92078a-8246-4ba4-ae5b-76104861e7dc".to_owned());
+        assert_eq!(normalize_ifa(" 6D92078A-8246-4BA4-AE5B-76104861E7DC "), ifa);
+        assert_eq!(normalize_ifa("{6d92078a82464ba4ae5b76104861e7dc}"), ifa);
+        assert_eq!(normalize_ifa("6d92078a-8246-4ba4-ae5b-76104861e7d"), None);
+        assert_eq!(normalize_ifa("6d92078a-8246-4ba4-ae5b-76104861e7dx"), None);
+        assert_eq!(normalize_ifa("6d92078a8-246-4ba4-ae5b-76104861e7dc"), None);
+        assert_eq!(
+            normalize_ifa("6d92078a-8246-4ba4-ae5b-76104861e7dc0000"),
+            None
+        );
+
+        assert!(is_zero_ifa("00000000-0000-0000-0000-000000000000"));
+        assert!(!is_zero_ifa("00000000-0000-0000-0000-000000000001"));
+        assert!(!is_zero_ifa("0"));
+
+        assert_eq!(IfaType::parse("RIDA"), Some(IfaType::Rida));
+        assert_eq!(IfaType::parse("other"), None);
+        assert_eq!(IfaType::from_os("iOS"), Some(IfaType::Idfa));
+        assert_eq!(IfaType::from_os("Linux"), None);
+    }
+
+    #[test]
+    fn check() {
+        let ids = DeviceIds {
+            ifa: Some("6D92078A82464BA4AE5B76104861E7DC".into()),
+            did: Some(" 490154203237518 ".into()),
+            mac: Some("00:1A:2B:3C:4D:5E".into()),
+            ..Default::default()
+        };
+        let mut device = crate::Device {
+            dpidmd5: Some("D41D8CD98F00B204E9800998ECF8427E".into()),
+            ..Default::default()
+        };
+        ids.apply(&mut device);
+        assert_eq!(
+            device.ifa.as_deref(),
+            Some("6d92078a-8246-4ba4-ae5b-76104861e7dc")
+        );
+        assert_eq!(device.didsha1, Some(sha1_hex("490154203237518")));
+        assert_eq!(device.macmd5, Some(md5_hex("00:1A:2B:3C:4D:5E")));
+        assert_eq!(device.dpidsha1, None);
+        assert!(ids.check(&device).is_empty());
+
+        device.ifa = Some("not-an-ifa".into());
+        device.didmd5 = Some(md5_hex("490154203237519"));
+        device.macsha1 = Some("abc".into());
+        assert_eq!(
+            ids.check(&device),
+            vec![
+                IdError::MalformedIfa,
+                IdError::IfaMismatch,
+                IdError::Mismatch(HashedId::DidMd5),
+                IdError::Malformed(HashedId::MacSha1),
+            ]
+        );
+        let mut ext = serde_json::Map::new();
+        ext.insert("ifa_type".into(), "ppid".into());
+        device.ext = Some(ext);
+        assert_eq!(ids.check(&device)[0], IdError::IfaMismatch);
+        device.os = Some("webOS".into());
+        device.ext = None;
+        assert_eq!(ids.check(&device)[0], IdError::IfaMismatch);
+        device.os = Some("Android".into());
+        assert_eq!(ids.check(&device)[0], IdError::MalformedIfa);
+        assert!(!IfaType::Sspid.is_uuid());
+        assert!(IfaType::Rida.is_uuid());
+        assert_eq!(
+            IdError::Mismatch(HashedId::DidMd5).to_string(),
+            "didmd5 is not the hash of the raw ID"
+        );
+    }
+}
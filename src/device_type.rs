@@ -13,6 +13,12 @@ use serde_repr::{Deserialize_repr, Serialize_repr};
 /// This OpenRTB table has values derived from the Inventory Quality Guidelines (IQG). Practitioners
 /// should keep in sync with updates to the IQG values.
 #[derive(Serialize_repr, Deserialize_repr, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::repr::<Self>)
+)]
 #[repr(i32)]
 pub enum DeviceType {
     /// Mobile/Tablet
@@ -25,6 +31,14 @@ pub enum DeviceType {
     PersonalComputer,
     /// Connected TV
     ConnectedTv,
+    /// Phone
+    Phone,
+    /// Tablet
+    Tablet,
+    /// Connected Device
+    ConnectedDevice,
+    /// Set Top Box
+    SetTopBox,
 }
 
 #[cfg(test)]
@@ -40,6 +54,20 @@ mod test {
         assert_eq!(e1, vec![DeviceType::Mobile, DeviceType::PersonalComputer]);
         assert_eq!(serde_json::to_string(&e1)?, json);
 
+        let json = "[4,5,6,7]";
+        let e2: Vec<DeviceType> = serde_json::from_str(json)?;
+        assert_eq!(
+            e2,
+            vec![
+                DeviceType::Phone,
+                DeviceType::Tablet,
+                DeviceType::ConnectedDevice,
+                DeviceType::SetTopBox
+            ]
+        );
+        assert_eq!(serde_json::to_string(&e2)?, json);
+        assert!(serde_json::from_str::<DeviceType>("8").is_err());
+
         Ok(())
     }
 }
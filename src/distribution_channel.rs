@@ -3,6 +3,8 @@
 /// [`Site`]: ./struct.Site.html
 /// [`App`]: ./struct.App.html
 #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum DistributionChannel {
     /// object; recommended
@@ -75,6 +77,55 @@ impl DistributionChannel {
     }
 }
 
+/// The distribution channel of a [`BidRequest`] as separate `site` and `app` members, the way
+/// it is written in a bid request.
+///
+/// [`BidRequest`]: ./struct.BidRequest.html
+pub(crate) mod flat {
+    use super::DistributionChannel;
+
+    /// The schema of the `site` and `app` members of a bid request.
+    #[cfg(feature = "schema")]
+    #[derive(schemars::JsonSchema)]
+    #[allow(dead_code)]
+    pub(crate) struct Channel<S, A> {
+        /// object; recommended
+        /// Details via a Site object (Section 3.2.13) about the publisher’s website. Only
+        /// applicable and recommended for websites.
+        #[schemars(default)]
+        site: Option<S>,
+
+        /// object; recommended
+        /// Details via an App object (Section 3.2.14) about the publisher’s app (i.e., non-browser
+        /// applications). Only applicable and recommended for apps.
+        #[schemars(default)]
+        app: Option<A>,
+    }
+
+    /// Returns the `site` and `app` members of `channel`, of which at most one is present.
+    pub(crate) fn split(
+        channel: Option<&DistributionChannel>,
+    ) -> (Option<&crate::Site>, Option<&crate::App>) {
+        match channel {
+            Some(DistributionChannel::Site(site)) => (Some(site), None),
+            Some(DistributionChannel::App(app)) => (None, Some(app)),
+            None => (None, None),
+        }
+    }
+
+    /// Returns the channel given by the `site` and `app` members; `site` wins if both are present.
+    pub(crate) fn join(
+        site: Option<crate::Site>,
+        app: Option<crate::App>,
+    ) -> Option<DistributionChannel> {
+        match (site, app) {
+            (Some(site), _) => Some(DistributionChannel::Site(site)),
+            (None, Some(app)) => Some(DistributionChannel::App(app)),
+            (None, None) => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
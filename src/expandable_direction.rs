@@ -5,6 +5,12 @@ use serde_repr::{Deserialize_repr, Serialize_repr};
 /// The following table lists the directions in which an expandable ad may expand, given the
 /// positioning of the ad unit on the page and constraints imposed by the content.
 #[derive(Serialize_repr, Deserialize_repr, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::repr::<Self>)
+)]
 #[repr(i32)]
 pub enum ExpandableDirection {
     /// Left
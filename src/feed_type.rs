@@ -4,6 +4,12 @@ use serde_repr::{Deserialize_repr, Serialize_repr};
 ///
 /// The following table lists the types of feeds, typically for audio.
 #[derive(Serialize_repr, Deserialize_repr, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::repr::<Self>)
+)]
 #[repr(i32)]
 pub enum FeedType {
     /// Music Service
@@ -0,0 +1,397 @@
+/// A bid floor: a CPM in a currency.
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct Floor {
+    /// Minimum bid for the impression expressed in CPM.
+    pub value: f64,
+
+    /// Currency of `value` using ISO-4217 alpha codes; empty means "USD".
+    pub cur: String,
+}
+
+/// What a [`FloorRule`] may key on when pricing an impression.
+#[derive(Debug, Clone, Copy)]
+pub struct FloorContext<'a> {
+    /// The request of the impression.
+    pub request: &'a crate::BidRequest,
+
+    /// The impression being priced.
+    pub imp: &'a crate::Imp,
+
+    /// The deal being priced, or `None` for the open auction.
+    pub deal: Option<&'a crate::Deal>,
+
+    /// The seat the floor is resolved for, if any.
+    pub seat: Option<&'a str>,
+
+    /// `Site.domain`.
+    pub domain: Option<&'a str>,
+
+    /// `App.bundle`.
+    pub bundle: Option<&'a str>,
+
+    /// `Device.devicetype`.
+    pub devicetype: Option<crate::DeviceType>,
+
+    /// `Geo.country` of the device, else of the user.
+    pub country: Option<&'a str>,
+
+    /// The media types the impression offers.
+    pub media_types: crate::MediaTypes,
+}
+
+/// A dynamic floor rule: returns the floor it imposes on an impression, if any.
+///
+/// Implemented for closures and for [`FloorMatch`].
+pub trait FloorRule {
+    /// Returns the floor imposed in `ctx`, or `None` if the rule does not apply.
+    fn floor(&self, ctx: &FloorContext<'_>) -> Option<Floor>;
+}
+
+impl<F> FloorRule for F
+where
+    F: Fn(&FloorContext<'_>) -> Option<Floor>,
+{
+    fn floor(&self, ctx: &FloorContext<'_>) -> Option<Floor> {
+        self(ctx)
+    }
+}
+
+/// A [`FloorRule`] imposing `floor` where every given key matches; strings compare ignoring ASCII
+/// case, and `media_type` matches impressions offering it.
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct FloorMatch {
+    /// `Site.domain` to match.
+    pub domain: Option<String>,
+
+    /// `App.bundle` to match.
+    pub bundle: Option<String>,
+
+    /// `Device.devicetype` to match.
+    pub devicetype: Option<crate::DeviceType>,
+
+    /// `Geo.country` to match.
+    pub country: Option<String>,
+
+    /// Media type the impression must offer.
+    pub media_type: Option<crate::MediaType>,
+
+    /// The floor imposed.
+    pub floor: Floor,
+}
+
+impl FloorRule for FloorMatch {
+    fn floor(&self, ctx: &FloorContext<'_>) -> Option<Floor> {
+        let eq = |key: &Option<String>, value: Option<&str>| match key {
+            Some(key) => value.is_some_and(|v| v.eq_ignore_ascii_case(key)),
+            None => true,
+        };
+        let matches = eq(&self.domain, ctx.domain)
+            && eq(&self.bundle, ctx.bundle)
+            && eq(&self.country, ctx.country)
+            && self.devicetype.is_none_or(|v| ctx.devicetype == Some(v))
+            && self.media_type.is_none_or(|v| ctx.media_types.contains(v));
+        matches.then(|| self.floor.clone())
+    }
+}
+
+/// Resolves the effective floor of an impression or deal: the highest of its own floor and the
+/// floors of all applicable rules, converted to a common currency.
+///
+/// ```
+/// # use openrtb2::{Floor, FloorMatch, FloorResolver, MediaType};
+/// let resolver = FloorResolver::new()
+///     .with_rule(FloorMatch {
+///         media_type: Some(MediaType::Video),
+///         floor: Floor { value: 8.0, cur: "USD".into() },
+///         ..Default::default()
+///     })
+///     .with_rates(|from, to| match (from, to) {
+///         ("USD", "EUR") => Some(0.5),
+///         _ => None,
+///     });
+///
+/// let mut req: openrtb2::BidRequest = serde_json::from_str(
+///     r#"{"id":"1","imp":[{"id":"1","video":{"mimes":[]},"bidfloor":2,"bidfloorcur":"EUR"}]}"#,
+/// )?;
+/// resolver.apply(&mut req)?;
+/// assert_eq!(req.imp[0].bidfloor, 4.0);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct FloorResolver<'r> {
+    rules: Vec<Box<dyn FloorRule + 'r>>,
+    rate: Box<Rate<'r>>,
+}
+
+type Rate<'r> = dyn Fn(&str, &str) -> Option<f64> + 'r;
+
+/// An error returned by [`FloorResolver`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum FloorError {
+    /// No exchange rate is known between the currencies.
+    NoRate {
+        /// Currency converted from.
+        from: String,
+        /// Currency converted to.
+        to: String,
+    },
+}
+
+impl<'r> FloorResolver<'r> {
+    /// Returns a resolver with no rules that cannot convert between currencies.
+    pub fn new() -> Self {
+        Self {
+            rules: Vec::new(),
+            rate: Box::new(|_, _| None),
+        }
+    }
+
+    /// Adds a dynamic floor rule.
+    pub fn with_rule(mut self, rule: impl FloorRule + 'r) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Sets the exchange rates: `rate(from, to)` returns how many units of `to` one unit of
+    /// `from` is worth. Currencies are passed as upper case ISO-4217 codes.
+    pub fn with_rates(mut self, rate: impl Fn(&str, &str) -> Option<f64> + 'r) -> Self {
+        self.rate = Box::new(rate);
+        self
+    }
+
+    /// Returns the effective floor in `cur` of `imp`, or of `deal` if given, for `seat`.
+    ///
+    /// The `bidfloor` of a deal whose `at` is [`AuctionType::DealPrice`] is the agreed upon
+    /// price, so no rule applies to it; it is only converted to `cur`.
+    ///
+    /// [`AuctionType::DealPrice`]: ./enum.AuctionType.html#variant.DealPrice
+    pub fn resolve(
+        &self,
+        req: &crate::BidRequest,
+        imp: &crate::Imp,
+        deal: Option<&crate::Deal>,
+        seat: Option<&str>,
+        cur: &str,
+    ) -> Result<Floor, FloorError> {
+        let device = req.device.as_ref();
+        let geo = device
+            .and_then(|v| v.geo.as_ref())
+            .filter(|v| v.country.is_some())
+            .or_else(|| req.user.as_ref().and_then(|v| v.geo.as_ref()));
+        let ctx = FloorContext {
+            request: req,
+            imp,
+            deal,
+            seat,
+            domain: req
+                .channel
+                .as_ref()
+                .and_then(|v| v.as_site()?.domain.as_deref()),
+            bundle: req
+                .channel
+                .as_ref()
+                .and_then(|v| v.as_app()?.bundle.as_deref()),
+            devicetype: device.and_then(|v| v.devicetype),
+            country: geo.and_then(|v| v.country.as_deref()),
+            media_types: imp.media_types(),
+        };
+
+        let own = match deal {
+            Some(deal) => (deal.bidfloor, deal.bidfloorcur.as_str()),
+            None => (imp.bidfloor, imp.bidfloorcur.as_str()),
+        };
+        let mut value = self.convert(own.0, own.1, cur)?;
+        let fixed = deal.is_some_and(|v| v.at == Some(crate::AuctionType::DealPrice));
+        for rule in self.rules.iter().filter(|_| !fixed) {
+            if let Some(floor) = rule.floor(&ctx) {
+                value = value.max(self.convert(floor.value, &floor.cur, cur)?);
+            }
+        }
+        Ok(Floor {
+            value,
+            cur: cur.to_owned(),
+        })
+    }
+
+    /// Writes the effective floor of every impression and deal of `req` back into its
+    /// `bidfloor`, keeping its `bidfloorcur`.
+    pub fn apply(&self, req: &mut crate::BidRequest) -> Result<(), FloorError> {
+        let mut floors = Vec::new();
+        for imp in &req.imp {
+            floors.push(self.resolve(req, imp, None, None, &imp.bidfloorcur)?.value);
+            for deal in imp.pmp.iter().flat_map(|v| v.deals.iter().flatten()) {
+                floors.push(
+                    self.resolve(req, imp, Some(deal), None, &deal.bidfloorcur)?
+                        .value,
+                );
+            }
+        }
+
+        let mut floors = floors.into_iter();
+        for imp in &mut req.imp {
+            imp.bidfloor = floors.next().unwrap();
+            for deal in imp
+                .pmp
+                .iter_mut()
+                .flat_map(|v| v.deals.iter_mut().flatten())
+            {
+                deal.bidfloor = floors.next().unwrap();
+            }
+        }
+        Ok(())
+    }
+
+    fn convert(&self, value: f64, from: &str, to: &str) -> Result<f64, FloorError> {
+        let from = currency(from);
+        let to = currency(to);
+        if value == 0.0 || from == to {
+            return Ok(value);
+        }
+        (self.rate)(&from, &to)
+            .map(|rate| value * rate)
+            .ok_or(FloorError::NoRate { from, to })
+    }
+}
+
+impl Default for FloorResolver<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for FloorResolver<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FloorResolver")
+            .field("rules", &self.rules.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl std::fmt::Display for FloorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoRate { from, to } => write!(f, "no exchange rate from {} to {}", from, to),
+        }
+    }
+}
+
+impl std::error::Error for FloorError {}
+
+fn currency(cur: &str) -> String {
+    if cur.is_empty() {
+        "USD".to_owned()
+    } else {
+        cur.to_ascii_uppercase()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn resolve() -> serde_json::Result<()> {
+        let mut req: crate::BidRequest = serde_json::from_str(
+            r#"{
+                "id": "1",
+                "imp": [
+                    {
+                        "id": "1",
+                        "banner": {},
+                        "bidfloor": 1,
+                        "pmp": {"deals": [
+                            {"id": "d", "bidfloor": 3, "bidfloorcur": "EUR"},
+                            {"id": "p", "bidfloor": 1.5, "at": 3}
+                        ]}
+                    },
+                    {"id": "2", "video": {"mimes": []}, "bidfloor": 0.5}
+                ],
+                "site": {"domain": "News.example"},
+                "device": {"devicetype": 3, "geo": {"region": "CA"}},
+                "user": {"geo": {"country": "USA"}}
+            }"#,
+        )?;
+        let resolver = FloorResolver::new()
+            .with_rule(FloorMatch {
+                domain: Some("news.example".into()),
+                country: Some("usa".into()),
+                floor: Floor {
+                    value: 2.0,
+                    cur: "USD".into(),
+                },
+                ..Default::default()
+            })
+            .with_rule(FloorMatch {
+                media_type: Some(crate::MediaType::Video),
+                devicetype: Some(crate::DeviceType::ConnectedTv),
+                floor: Floor {
+                    value: 10.0,
+                    cur: String::new(),
+                },
+                ..Default::default()
+            })
+            .with_rule(|ctx: &FloorContext<'_>| {
+                (ctx.seat == Some("premium")).then(|| Floor {
+                    value: 12.0,
+                    cur: "usd".into(),
+                })
+            });
+
+        let imp = &req.imp[0];
+        let deal = &imp.pmp.as_ref().unwrap().deals.as_ref().unwrap()[0];
+        assert_eq!(
+            resolver.resolve(&req, imp, None, None, "USD"),
+            Ok(Floor {
+                value: 2.0,
+                cur: "USD".into()
+            })
+        );
+        assert_eq!(
+            resolver.resolve(&req, imp, Some(deal), None, "USD"),
+            Err(FloorError::NoRate {
+                from: "EUR".into(),
+                to: "USD".into()
+            })
+        );
+        assert_eq!(
+            resolver
+                .resolve(&req, imp, None, Some("premium"), "USD")
+                .map(|v| v.value),
+            Ok(12.0)
+        );
+        assert_eq!(
+            resolver
+                .resolve(&req, &req.imp[1], None, None, "USD")
+                .map(|v| v.value),
+            Ok(10.0)
+        );
+
+        let resolver = resolver.with_rates(|from, to| match (from, to) {
+            ("USD", "EUR") => Some(0.8),
+            ("EUR", "USD") => Some(1.25),
+            _ => None,
+        });
+        assert_eq!(
+            resolver
+                .resolve(&req, imp, Some(deal), None, "USD")
+                .map(|v| v.value),
+            Ok(3.75)
+        );
+        // The agreed upon price of a deal is not raised by rules.
+        let fixed = &imp.pmp.as_ref().unwrap().deals.as_ref().unwrap()[1];
+        assert_eq!(
+            resolver
+                .resolve(&req, imp, Some(fixed), Some("premium"), "USD")
+                .map(|v| v.value),
+            Ok(1.5)
+        );
+
+        resolver.apply(&mut req).unwrap();
+        let deals = req.imp[0].pmp.as_ref().unwrap().deals.as_ref().unwrap();
+        assert_eq!(req.imp[0].bidfloor, 2.0);
+        assert_eq!(deals[0].bidfloor, 3.0);
+        assert_eq!(deals[1].bidfloor, 1.5);
+        assert_eq!(req.imp[1].bidfloor, 10.0);
+
+        Ok(())
+    }
+}
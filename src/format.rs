@@ -5,6 +5,12 @@
 /// are permitted. It is recommended that either the w/h pair or the wratio/hratio/wmin set (i.e.,
 /// for Flex Ads) be specified.
 #[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::recommended)
+)]
 pub struct Format {
     /// integer
     /// Width in device independent pixels (DIPS).
@@ -35,9 +41,39 @@ pub struct Format {
     /// object
     /// Placeholder for exchange-specific extensions to OpenRTB.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::ext))]
     pub ext: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
+impl Format {
+    /// Returns `true` if a creative of `w`x`h` DIPS is permitted by this format: either the exact
+    /// w/h pair, or for Flex Ads, a size of the wratio/hratio aspect ratio at least wmin wide.
+    /// A format with neither permits no size.
+    pub fn accepts_size(&self, w: i32, h: i32) -> bool {
+        if let (Some(fw), Some(fh)) = (self.w, self.h) {
+            return fw == w && fh == h;
+        }
+        match (self.wratio, self.hratio) {
+            (Some(wratio), Some(hratio)) if wratio > 0 && hratio > 0 => {
+                i64::from(w) * i64::from(hratio) == i64::from(h) * i64::from(wratio)
+                    && self.wmin.map_or(w > 0, |wmin| w >= wmin)
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if a Flex Ad creative of the `wratio`:`hratio` aspect ratio is permitted by
+    /// this format.
+    pub fn accepts_ratio(&self, wratio: i32, hratio: i32) -> bool {
+        match (self.wratio, self.hratio) {
+            (Some(fw), Some(fh)) if fw > 0 && fh > 0 && wratio > 0 && hratio > 0 => {
+                i64::from(wratio) * i64::from(fh) == i64::from(hratio) * i64::from(fw)
+            }
+            _ => false,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -51,4 +87,31 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn accepts_size() {
+        let fixed = Format {
+            w: Some(300),
+            h: Some(250),
+            ..Default::default()
+        };
+        assert!(fixed.accepts_size(300, 250));
+        assert!(!fixed.accepts_size(250, 300));
+        assert!(!fixed.accepts_ratio(6, 5));
+
+        let flex = Format {
+            wratio: Some(6),
+            hratio: Some(5),
+            wmin: Some(300),
+            ..Default::default()
+        };
+        assert!(flex.accepts_size(300, 250));
+        assert!(flex.accepts_size(600, 500));
+        assert!(!flex.accepts_size(120, 100));
+        assert!(!flex.accepts_size(300, 251));
+        assert!(flex.accepts_ratio(12, 10));
+        assert!(!flex.accepts_ratio(16, 9));
+
+        assert!(!Format::default().accepts_size(300, 250));
+    }
 }
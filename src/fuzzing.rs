@@ -0,0 +1,214 @@
+use arbitrary::{Arbitrary, Result, Unstructured};
+use serde_json::{Map, Value};
+
+/// A bid request together with a bid response answering it.
+///
+/// Unlike independently generated objects, the pair is consistent: the response echoes the request
+/// ID and currency, every `Bid.impid` names an `Imp.id` of the request, and every `Bid.dealid`
+/// names a deal offered for that impression.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Auction {
+    pub request: crate::BidRequest,
+    pub response: crate::BidResponse,
+}
+
+impl<'a> Arbitrary<'a> for Auction {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let request = crate::BidRequest::arbitrary(u)?;
+        let mut response = crate::BidResponse::arbitrary(u)?;
+
+        response.id = request.id.clone();
+        response.cur = match request.cur.as_deref() {
+            Some(cur) if !cur.is_empty() => u.choose(cur)?.clone(),
+            _ => Default::default(),
+        };
+        for seatbid in response.seatbid.iter_mut().flatten() {
+            for bid in seatbid.bid.iter_mut() {
+                let imp = u.choose(&request.imp)?;
+                bid.impid = imp.id.clone();
+                bid.dealid = match imp.pmp.as_ref().and_then(|pmp| pmp.deals.as_deref()) {
+                    Some(deals) if !deals.is_empty() && u.arbitrary()? => {
+                        Some(u.choose(deals)?.id.clone())
+                    }
+                    _ => None,
+                };
+            }
+        }
+
+        Ok(Self { request, response })
+    }
+}
+
+/// Returns a proptest strategy generating values of `T` from its `Arbitrary` implementation.
+///
+/// Shrinking operates on the underlying bytes, so failing cases shrink towards smaller objects.
+#[cfg(feature = "proptest")]
+pub fn arbitrary_strategy<T>() -> impl proptest::strategy::Strategy<Value = T>
+where
+    T: for<'a> Arbitrary<'a> + std::fmt::Debug,
+{
+    use proptest::strategy::Strategy;
+
+    proptest::collection::vec(proptest::arbitrary::any::<u8>(), 0..4096)
+        .prop_filter_map("not enough data", |bytes| {
+            T::arbitrary_take_rest(Unstructured::new(&bytes)).ok()
+        })
+}
+
+#[cfg(feature = "proptest")]
+macro_rules! impl_proptest_arbitrary {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl proptest::arbitrary::Arbitrary for $ty {
+                type Parameters = ();
+                type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+                fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                    proptest::strategy::Strategy::boxed(arbitrary_strategy())
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "proptest")]
+impl_proptest_arbitrary!(
+    Auction,
+    crate::BidRequest,
+    crate::Source,
+    crate::Regs,
+    crate::Imp,
+    crate::Metric,
+    crate::Banner,
+    crate::Video,
+    crate::Audio,
+    crate::Native,
+    crate::Format,
+    crate::Pmp,
+    crate::Deal,
+    crate::Site,
+    crate::App,
+    crate::Publisher,
+    crate::Content,
+    crate::Producer,
+    crate::Device,
+    crate::Geo,
+    crate::User,
+    crate::Data,
+    crate::Segment,
+    crate::BidResponse,
+    crate::SeatBid,
+    crate::Bid,
+    crate::ContentCategory,
+    crate::BannerAdType,
+    crate::CreativeAttribute,
+    crate::AdPosition,
+    crate::ExpandableDirection,
+    crate::ApiFramework,
+    crate::VideoLinearity,
+    crate::Protocol,
+    crate::VideoPlacementType,
+    crate::PlaybackMethod,
+    crate::PlaybackCessationMode,
+    crate::StartDelay,
+    crate::ProductionQuality,
+    crate::CompanionType,
+    crate::ContentDeliveryMethod,
+    crate::FeedType,
+    crate::VolumeNormalizationMode,
+    crate::ContentContext,
+    crate::IqgMediaRating,
+    crate::LocationType,
+    crate::DeviceType,
+    crate::ConnectionType,
+    crate::IpLocationService,
+    crate::NoBidReason,
+    crate::LossReason,
+    crate::DistributionChannel,
+    crate::AuctionType,
+    crate::Gender,
+    crate::MaxExtendedAdDuration,
+    crate::MediaType,
+);
+
+/// Impressions with IDs `1`, `2`, ..., each offered as at least one media type.
+pub(crate) fn imps(u: &mut Unstructured) -> Result<Vec<crate::Imp>> {
+    let len = u.int_in_range(1..=4)?;
+    (1..=len)
+        .map(|i| {
+            let mut imp = crate::Imp::arbitrary(u)?;
+            imp.id = i.to_string();
+            if imp.banner.is_none()
+                && imp.video.is_none()
+                && imp.audio.is_none()
+                && imp.native.is_none()
+            {
+                imp.banner = Some(Default::default());
+            }
+            Ok(imp)
+        })
+        .collect()
+}
+
+pub(crate) fn non_empty<'a, T>(u: &mut Unstructured<'a>) -> Result<Vec<T>>
+where
+    T: Arbitrary<'a>,
+{
+    let mut v: Vec<T> = u.arbitrary()?;
+    if v.is_empty() {
+        v.push(u.arbitrary()?);
+    }
+    Ok(v)
+}
+
+/// A non-negative CPM in cents, which survives a round trip through JSON text.
+pub(crate) fn price(u: &mut Unstructured) -> Result<f64> {
+    Ok(f64::from(u.int_in_range(0..=10_000_000)?) / 100.0)
+}
+
+/// An auction type other than `DealPrice`, which only a deal may have.
+pub(crate) fn request_auction_type(u: &mut Unstructured) -> Result<crate::AuctionType> {
+    match u.arbitrary()? {
+        crate::AuctionType::DealPrice => Ok(crate::AuctionType::SecondPricePlus),
+        v => Ok(v),
+    }
+}
+
+pub(crate) fn probability(u: &mut Unstructured) -> Result<f32> {
+    Ok(f32::from(u.int_in_range(0..=100u8)?) / 100.0)
+}
+
+pub(crate) fn latitude(u: &mut Unstructured) -> Result<Option<f32>> {
+    coordinate(u, 90)
+}
+
+pub(crate) fn longitude(u: &mut Unstructured) -> Result<Option<f32>> {
+    coordinate(u, 180)
+}
+
+fn coordinate(u: &mut Unstructured, max: i32) -> Result<Option<f32>> {
+    if !u.arbitrary()? {
+        return Ok(None);
+    }
+    let v = u.int_in_range(-max * 10_000..=max * 10_000)?;
+    Ok(Some(v as f32 / 10_000.0))
+}
+
+/// An extension object holding a few scalar members.
+pub(crate) fn ext(u: &mut Unstructured) -> Result<Option<Map<String, Value>>> {
+    if !u.arbitrary()? {
+        return Ok(None);
+    }
+    let len = u.int_in_range(0..=3)?;
+    let mut map = Map::new();
+    for _ in 0..len {
+        let v = match u.int_in_range(0..=3)? {
+            0 => Value::Null,
+            1 => Value::Bool(u.arbitrary()?),
+            2 => Value::from(u.arbitrary::<i64>()?),
+            _ => Value::String(u.arbitrary()?),
+        };
+        map.insert(u.arbitrary()?, v);
+    }
+    Ok(Some(map))
+}
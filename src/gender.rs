@@ -2,6 +2,8 @@
 ///
 /// Gender, where “M” = male, “F” = female, “O” = known to be other.
 #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Gender {
     #[serde(rename = "M")]
     Male,
@@ -9,15 +9,23 @@
 /// type attribute. For example, the centroid of a geographic region such as postal code should not
 /// be passed.
 #[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::recommended)
+)]
 pub struct Geo {
     /// float
     /// Latitude from -90.0 to +90.0, where negative is south.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::latitude))]
     pub lat: Option<f32>,
 
     /// float
     /// Longitude from -180.0 to +180.0, where negative is west.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::longitude))]
     pub lon: Option<f32>,
 
     /// integer
@@ -48,7 +56,10 @@ pub struct Geo {
 
     /// string
     /// Country code using ISO-3166-1-alpha-3.
-    // TODO: ISO-3166-1-alpha-3.
+    /// Parsed as a [`Country`] by [`Geo::country_code`].
+    ///
+    /// [`Country`]: ./struct.Country.html
+    /// [`Geo::country_code`]: ./struct.Geo.html#method.country_code
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub country: Option<String>,
 
@@ -92,9 +103,125 @@ pub struct Geo {
     /// object
     /// Placeholder for exchange-specific extensions to OpenRTB.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::ext))]
     pub ext: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
+impl Geo {
+    /// Parses `country` as an ISO-3166-1 alpha-3 or alpha-2 code.
+    pub fn country_code(&self) -> Option<crate::Country> {
+        self.country.as_deref().and_then(|v| v.parse().ok())
+    }
+
+    /// Rewrites `country` as an upper case alpha-3 code if it is a valid alpha-3 or alpha-2 code,
+    /// and returns the country. An invalid code is left as is.
+    pub fn normalize_country(&mut self) -> Option<crate::Country> {
+        let country = self.country_code()?;
+        self.country = Some(country.alpha3().to_owned());
+        Some(country)
+    }
+
+    /// Returns `lat` and `lon` as a point if both are set and in range.
+    pub fn point(&self) -> Option<crate::GeoPoint> {
+        let point = crate::GeoPoint::new(self.lat? as f64, self.lon? as f64);
+        point.is_valid().then_some(point)
+    }
+
+    /// Returns the distance in meters from this location to `point`, see [`GeoPoint::distance`].
+    ///
+    /// [`GeoPoint::distance`]: ./struct.GeoPoint.html#method.distance
+    pub fn distance(&self, point: crate::GeoPoint) -> Option<f64> {
+        self.point().map(|v| v.distance(point))
+    }
+
+    /// Returns `true` if this location is within `radius` meters of `center`.
+    pub fn is_within(&self, center: crate::GeoPoint, radius: f64) -> bool {
+        self.distance(center).is_some_and(|v| v <= radius)
+    }
+
+    /// Returns `true` if this location is inside `polygon`, see [`GeoPoint::is_in_polygon`].
+    ///
+    /// [`GeoPoint::is_in_polygon`]: ./struct.GeoPoint.html#method.is_in_polygon
+    pub fn is_in_polygon(&self, polygon: &[crate::GeoPoint]) -> bool {
+        self.point().is_some_and(|v| v.is_in_polygon(polygon))
+    }
+
+    /// Returns the estimated error of `lat`/`lon` in meters: `accuracy` if set, otherwise a
+    /// typical value for the source in `type`, 100 m for GPS, 5 km for user provided locations
+    /// (e.g., a postal code), 25 km for IP addresses and 50 km if unknown. `None` without a
+    /// location.
+    pub fn uncertainty(&self) -> Option<f64> {
+        self.point()?;
+        if let Some(accuracy) = self.accuracy.filter(|&v| v > 0) {
+            return Some(accuracy as f64);
+        }
+        Some(match self.r#type {
+            Some(crate::LocationType::GpsLocation) => 100.0,
+            Some(crate::LocationType::UserProvided) => 5_000.0,
+            Some(crate::LocationType::IpAddress) => 25_000.0,
+            None => 50_000.0,
+        })
+    }
+
+    /// Returns how far `lat`/`lon` can be trusted, from 0 (no location) to 1.
+    ///
+    /// The score is the product of a weight for the source in `type` (1 for GPS, 0.6 for user
+    /// provided, 0.4 for IP address and 0.3 if unknown), a precision factor `1 / (1 + u / 1 km)`
+    /// of the [`uncertainty`] `u`, and a freshness factor `1 / (1 + t / 1 h)` of the age `t` in
+    /// `lastfix`, if set.
+    ///
+    /// ```
+    /// # use openrtb2::{Geo, LocationType};
+    /// let gps = Geo {
+    ///     lat: Some(52.52),
+    ///     lon: Some(13.405),
+    ///     r#type: Some(LocationType::GpsLocation),
+    ///     accuracy: Some(10),
+    ///     lastfix: Some(60),
+    ///     ..Default::default()
+    /// };
+    /// let ip = Geo { r#type: Some(LocationType::IpAddress), accuracy: None, ..gps.clone() };
+    /// assert!(gps.confidence() > 0.95);
+    /// assert!(ip.confidence() < 0.02);
+    /// ```
+    ///
+    /// [`uncertainty`]: #method.uncertainty
+    pub fn confidence(&self) -> f64 {
+        let uncertainty = match self.uncertainty() {
+            Some(v) => v,
+            None => return 0.0,
+        };
+        let source = match self.r#type {
+            Some(crate::LocationType::GpsLocation) => 1.0,
+            Some(crate::LocationType::UserProvided) => 0.6,
+            Some(crate::LocationType::IpAddress) => 0.4,
+            None => 0.3,
+        };
+        let precision = 1.0 / (1.0 + uncertainty / 1_000.0);
+        let freshness = match self.lastfix.filter(|&v| v >= 0) {
+            Some(age) => 1.0 / (1.0 + age as f64 / 3_600.0),
+            None => 1.0,
+        };
+        source * precision * freshness
+    }
+
+    /// Rounds `lat` and `lon` to `decimals` decimal places to reduce their precision, e.g. for
+    /// privacy, see [`GeoPoint::round`]. `accuracy` is raised to cover the rounding, or set to it
+    /// if absent.
+    ///
+    /// [`GeoPoint::round`]: ./struct.GeoPoint.html#method.round
+    pub fn reduce_precision(&mut self, decimals: u32) {
+        let point = match self.point() {
+            Some(v) => v.round(decimals),
+            None => return,
+        };
+        self.lat = Some(point.lat as f32);
+        self.lon = Some(point.lon as f32);
+        let error = crate::geo_point::rounding_error(decimals).ceil() as i32;
+        self.accuracy = Some(self.accuracy.map_or(error, |v| v.max(error)));
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -108,4 +235,60 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn country() {
+        let mut geo = Geo {
+            country: Some("us".into()),
+            ..Default::default()
+        };
+        assert_eq!(geo.normalize_country().map(|v| v.alpha2()), Some("US"));
+        assert_eq!(geo.country.as_deref(), Some("USA"));
+
+        geo.country = Some("UK".into());
+        assert_eq!(geo.normalize_country(), None);
+        assert_eq!(geo.country.as_deref(), Some("UK"));
+    }
+
+    #[test]
+    fn location() {
+        let store = crate::GeoPoint::new(52.52, 13.405);
+        let mut geo = Geo {
+            lat: Some(52.55),
+            lon: Some(13.405),
+            r#type: Some(crate::LocationType::GpsLocation),
+            accuracy: Some(20),
+            ..Default::default()
+        };
+        assert_eq!(geo.distance(store).map(f64::round), Some(3336.0));
+        assert!(geo.is_within(store, 5_000.0));
+        assert!(!geo.is_within(store, 3_000.0));
+        let square = [
+            crate::GeoPoint::new(52.5, 13.3),
+            crate::GeoPoint::new(52.6, 13.3),
+            crate::GeoPoint::new(52.6, 13.5),
+            crate::GeoPoint::new(52.5, 13.5),
+        ];
+        assert!(geo.is_in_polygon(&square));
+
+        assert_eq!(geo.uncertainty(), Some(20.0));
+        let fresh = geo.confidence();
+        geo.lastfix = Some(3_600);
+        assert!((geo.confidence() - fresh / 2.0).abs() < 1e-9);
+
+        geo.reduce_precision(0);
+        assert_eq!((geo.lat, geo.lon), (Some(53.0), Some(13.0)));
+        assert_eq!(geo.accuracy, Some(78_627));
+        assert!(!geo.is_in_polygon(&square));
+
+        geo.accuracy = None;
+        geo.reduce_precision(2);
+        assert_eq!(geo.accuracy, Some(787));
+
+        geo.lat = Some(91.0);
+        assert_eq!(geo.point(), None);
+        assert!(!geo.is_within(store, f64::INFINITY));
+        assert_eq!(geo.uncertainty(), None);
+        assert_eq!(geo.confidence(), 0.0);
+    }
 }
@@ -0,0 +1,138 @@
+/// Mean radius of the Earth in meters.
+const EARTH_RADIUS: f64 = 6_371_008.8;
+
+/// A point on the Earth in decimal degrees, such as the location of a [`Geo`] or a vertex of a
+/// geofence.
+///
+/// ```
+/// # use openrtb2::GeoPoint;
+/// let paris = GeoPoint::new(48.8566, 2.3522);
+/// let london = GeoPoint::new(51.5074, -0.1278);
+/// assert_eq!((paris.distance(london) / 1000.0).round(), 344.0);
+/// ```
+///
+/// [`Geo`]: ./struct.Geo.html
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct GeoPoint {
+    /// Latitude from -90.0 to +90.0, where negative is south.
+    pub lat: f64,
+    /// Longitude from -180.0 to +180.0, where negative is west.
+    pub lon: f64,
+}
+
+impl GeoPoint {
+    /// Creates a point from a latitude and longitude in decimal degrees, which are not checked;
+    /// see [`GeoPoint::is_valid`].
+    pub fn new(lat: f64, lon: f64) -> Self {
+        Self { lat, lon }
+    }
+
+    /// Returns `true` if the latitude and longitude are in range.
+    pub fn is_valid(&self) -> bool {
+        (-90.0..=90.0).contains(&self.lat) && (-180.0..=180.0).contains(&self.lon)
+    }
+
+    /// Returns the great-circle distance to `other` in meters, using the haversine formula.
+    pub fn distance(&self, other: GeoPoint) -> f64 {
+        let (lat1, lat2) = (self.lat.to_radians(), other.lat.to_radians());
+        let dlat = lat2 - lat1;
+        let dlon = (other.lon - self.lon).to_radians();
+        let a = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS * a.sqrt().min(1.0).asin()
+    }
+
+    /// Returns `true` if this point is inside `polygon`, a ring of vertices in order; the ring
+    /// may or may not repeat its first vertex at the end.
+    ///
+    /// Edges are straight lines in latitude and longitude, which is accurate for geofences up to
+    /// a few hundred kilometers across. Polygons crossing the antimeridian are not supported.
+    pub fn is_in_polygon(&self, polygon: &[GeoPoint]) -> bool {
+        // Even-odd rule: count the edges crossed by a ray from the point towards the east.
+        let mut inside = false;
+        let mut j = match polygon.len() {
+            0 => return false,
+            n => n - 1,
+        };
+        for (i, a) in polygon.iter().enumerate() {
+            let b = polygon[j];
+            if (a.lat > self.lat) != (b.lat > self.lat) {
+                let lon = a.lon + (self.lat - a.lat) / (b.lat - a.lat) * (b.lon - a.lon);
+                if self.lon < lon {
+                    inside = !inside;
+                }
+            }
+            j = i;
+        }
+        inside
+    }
+
+    /// Returns this point with its latitude and longitude rounded to `decimals` decimal places.
+    /// One decimal place is about 11 km of latitude, two about 1.1 km and three about 110 m.
+    pub fn round(&self, decimals: u32) -> Self {
+        let scale = 10f64.powi(decimals as i32);
+        Self::new(
+            (self.lat * scale).round() / scale,
+            (self.lon * scale).round() / scale,
+        )
+    }
+}
+
+/// Returns the largest distance in meters between a point and itself rounded to `decimals`
+/// decimal places: half a step of latitude and longitude at the equator, combined.
+pub(crate) fn rounding_error(decimals: u32) -> f64 {
+    let step = 10f64.powi(-(decimals as i32));
+    let half = (step / 2.0).to_radians() * EARTH_RADIUS;
+    half * std::f64::consts::SQRT_2
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn distance() {
+        let p = GeoPoint::new(40.7128, -74.0060);
+        assert_eq!(p.distance(p), 0.0);
+        let la = GeoPoint::new(34.0522, -118.2437);
+        assert_eq!((p.distance(la) / 1000.0).round(), 3936.0);
+        assert_eq!(p.distance(la), la.distance(p));
+
+        // Antipodes are half the circumference apart.
+        let d = GeoPoint::new(0.0, 0.0).distance(GeoPoint::new(0.0, 180.0));
+        assert!((d - std::f64::consts::PI * EARTH_RADIUS).abs() < 1e-6);
+
+        assert!(GeoPoint::new(-90.0, 180.0).is_valid());
+        assert!(!GeoPoint::new(90.5, 0.0).is_valid());
+        assert!(!GeoPoint::new(0.0, f64::NAN).is_valid());
+    }
+
+    #[test]
+    fn polygon() {
+        // A concave "L" shape.
+        let l = [
+            GeoPoint::new(0.0, 0.0),
+            GeoPoint::new(0.0, 2.0),
+            GeoPoint::new(1.0, 2.0),
+            GeoPoint::new(1.0, 1.0),
+            GeoPoint::new(2.0, 1.0),
+            GeoPoint::new(2.0, 0.0),
+            GeoPoint::new(0.0, 0.0),
+        ];
+        assert!(GeoPoint::new(0.5, 0.5).is_in_polygon(&l));
+        assert!(GeoPoint::new(1.5, 0.5).is_in_polygon(&l));
+        assert!(GeoPoint::new(0.5, 1.5).is_in_polygon(&l));
+        assert!(!GeoPoint::new(1.5, 1.5).is_in_polygon(&l));
+        assert!(!GeoPoint::new(-0.5, 0.5).is_in_polygon(&l));
+        assert!(!GeoPoint::new(0.5, 0.5).is_in_polygon(&l[..2]));
+        assert!(!GeoPoint::new(0.5, 0.5).is_in_polygon(&[]));
+    }
+
+    #[test]
+    fn round() {
+        let p = GeoPoint::new(48.85661, -2.35222);
+        assert_eq!(p.round(2), GeoPoint::new(48.86, -2.35));
+        assert_eq!(p.round(0), GeoPoint::new(49.0, -2.0));
+        assert!(p.distance(p.round(3)) <= rounding_error(3));
+        assert_eq!((rounding_error(2)).round(), 786.0);
+    }
+}
@@ -10,6 +10,12 @@
 /// publisher can choose one such type which is the typical case or mix them at their discretion.
 /// However, any given bid for the impression must conform to one of the offered types.
 #[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::recommended)
+)]
 pub struct Imp {
     /// string; required
     /// A unique identifier for this impression within the context of the bid request (typically,
@@ -72,6 +78,10 @@ pub struct Imp {
         skip_serializing_if = "default_ext::DefaultExt::is_default",
         with = "crate::serde::i32_as_bool"
     )]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(schema_with = "crate::serde::i32_as_bool::schema")
+    )]
     pub instl: bool,
 
     /// string
@@ -83,6 +93,7 @@ pub struct Imp {
     /// float; default 0
     /// Minimum bid for this impression expressed in CPM.
     #[serde(default, skip_serializing_if = "default_ext::DefaultExt::is_default")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::price))]
     pub bidfloor: f64,
 
     /// string; default “USD”
@@ -101,6 +112,10 @@ pub struct Imp {
         skip_serializing_if = "Option::is_none",
         with = "crate::serde::i32_as_opt_bool"
     )]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(schema_with = "crate::serde::i32_as_opt_bool::schema")
+    )]
     pub clickbrowser: Option<bool>,
 
     /// integer
@@ -112,6 +127,10 @@ pub struct Imp {
         skip_serializing_if = "Option::is_none",
         with = "crate::serde::i32_as_opt_bool"
     )]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(schema_with = "crate::serde::i32_as_opt_bool::schema")
+    )]
     pub secure: Option<bool>,
 
     /// string array
@@ -128,9 +147,139 @@ pub struct Imp {
     /// object
     /// Placeholder for exchange-specific extensions to OpenRTB.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::ext))]
     pub ext: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
+impl Imp {
+    /// Returns the media types offered by this impression.
+    pub fn media_types(&self) -> crate::MediaTypes {
+        let mut set = crate::MediaTypes::default();
+        let offered = [
+            (self.banner.is_some(), crate::MediaType::Banner),
+            (self.video.is_some(), crate::MediaType::Video),
+            (self.audio.is_some(), crate::MediaType::Audio),
+            (self.native.is_some(), crate::MediaType::Native),
+        ];
+        for (_, v) in offered.iter().filter(|(offered, _)| *offered) {
+            set.insert(*v);
+        }
+        set
+    }
+
+    /// Evaluates the private marketplace terms for a bid on this impression, made on behalf of
+    /// `seat` (`SeatBid.seat`) in a request of auction type `at` (`BidRequest.at`).
+    ///
+    /// Domains in `Deal.wadomain` and `Bid.adomain` are compared by registrable domain (see
+    /// [`registrable_domain`]). An absent `bidfloorcur` is read as its default, USD.
+    ///
+    /// ```
+    /// # use openrtb2::{AuctionType, Bid, Imp, Ineligibility};
+    /// let imp: Imp = serde_json::from_str(r#"{
+    ///     "id": "1",
+    ///     "bidfloor": 0.5,
+    ///     "pmp": {
+    ///         "private_auction": 1,
+    ///         "deals": [{"id": "d", "bidfloor": 2.5, "at": 3, "wseat": ["s"]}]
+    ///     }
+    /// }"#)?;
+    /// let mut bid = Bid { dealid: Some("d".into()), ..Default::default() };
+    ///
+    /// let terms = imp.deal_eligibility(AuctionType::FirstPrice, Some("s"), &bid);
+    /// assert!(terms.is_eligible());
+    /// assert!(!terms.open_auction);
+    /// assert_eq!((terms.bidfloor, terms.bidfloorcur), (2.5, "USD"));
+    /// assert_eq!(terms.at, AuctionType::DealPrice);
+    ///
+    /// bid.dealid = None;
+    /// let terms = imp.deal_eligibility(AuctionType::FirstPrice, Some("s"), &bid);
+    /// assert_eq!(terms.reasons, vec![Ineligibility::PrivateAuction]);
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    ///
+    /// [`registrable_domain`]: ./fn.registrable_domain.html
+    pub fn deal_eligibility<'a>(
+        &'a self,
+        at: crate::AuctionType,
+        seat: Option<&str>,
+        bid: &crate::Bid,
+    ) -> crate::DealEligibility<'a> {
+        use crate::Ineligibility;
+
+        let currency = |s: &'a str| if s.is_empty() { "USD" } else { s };
+        let deals = self.pmp.as_ref().and_then(|v| v.deals.as_deref());
+        let open_auction = !self.pmp.as_ref().is_some_and(|v| v.private_auction);
+
+        let mut reasons = Vec::new();
+        let deal = match &bid.dealid {
+            Some(id) => {
+                let deal = deals.and_then(|v| v.iter().find(|d| &d.id == id));
+                if deal.is_none() {
+                    reasons.push(Ineligibility::UnknownDeal(id.clone()));
+                }
+                deal
+            }
+            None => {
+                if !open_auction {
+                    reasons.push(Ineligibility::PrivateAuction);
+                }
+                None
+            }
+        };
+
+        if let Some(deal) = deal {
+            if let Some(wseat) = &deal.wseat {
+                if !seat.is_some_and(|seat| wseat.iter().any(|v| v == seat)) {
+                    reasons.push(Ineligibility::SeatNotAllowed);
+                }
+            }
+            if let Some(wadomain) = &deal.wadomain {
+                let adomain = bid.adomain.as_deref().unwrap_or_default();
+                if !adomain
+                    .iter()
+                    .any(|a| wadomain.iter().any(|w| crate::domains_match(a, w)))
+                {
+                    reasons.push(Ineligibility::AdvertiserNotAllowed);
+                }
+            }
+        }
+
+        crate::DealEligibility {
+            deal,
+            reasons,
+            open_auction,
+            bidfloor: deal.map_or(self.bidfloor, |v| v.bidfloor),
+            bidfloorcur: currency(deal.map_or(&self.bidfloorcur, |v| &v.bidfloorcur)),
+            at: deal.and_then(|v| v.at).unwrap_or(at),
+        }
+    }
+
+    /// Checks that a bid for this impression references only secure URLs if the impression
+    /// requires it (`secure` is 1): its notice URLs, and the URLs in its HTML, VAST or native
+    /// markup.
+    pub fn check_secure(&self, bid: &crate::Bid) -> crate::SecureCheck {
+        if self.secure != Some(true) {
+            return Default::default();
+        }
+        crate::SecureCheck {
+            insecure: bid.insecure_urls(),
+        }
+    }
+
+    /// Checks a bid against the restrictions of the media object it targets: creative
+    /// attributes of `Bid.attr` blocked by `battr`, a `Bid.api` missing from `api`, and for
+    /// banners the types of the markup (see [`Bid::banner_ad_types`]) blocked by `btype`.
+    ///
+    /// The media object is the one [`Bid::infer_media_type`] selects. If that is ambiguous, only
+    /// the restrictions shared by every candidate apply.
+    ///
+    /// [`Bid::banner_ad_types`]: ./struct.Bid.html#method.banner_ad_types
+    /// [`Bid::infer_media_type`]: ./struct.Bid.html#method.infer_media_type
+    pub fn creative_blocks(&self, bid: &crate::Bid) -> Vec<crate::CreativeBlock> {
+        crate::creative_blocking::creative_blocks(self, bid)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -146,4 +295,58 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn deal_eligibility() -> serde_json::Result<()> {
+        use crate::{AuctionType, Ineligibility};
+
+        let imp: Imp = serde_json::from_str(
+            r#"{
+                "id": "1",
+                "bidfloor": 0.5,
+                "bidfloorcur": "EUR",
+                "pmp": {
+                    "deals": [
+                        {"id": "a", "bidfloor": 1.5, "wseat": ["s1", "s2"]},
+                        {"id": "b", "at": 1, "wadomain": ["acme.com"]}
+                    ]
+                }
+            }"#,
+        )?;
+        let bid = |json: &str| serde_json::from_str::<crate::Bid>(json);
+
+        let b = bid(r#"{"id":"1","impid":"1","price":1}"#)?;
+        let terms = imp.deal_eligibility(AuctionType::SecondPricePlus, None, &b);
+        assert!(terms.is_eligible() && terms.open_auction);
+        assert_eq!(terms.deal, None);
+        assert_eq!((terms.bidfloor, terms.bidfloorcur), (0.5, "EUR"));
+        assert_eq!(terms.at, AuctionType::SecondPricePlus);
+        assert_eq!(terms.meets_floor(1.0, "eur"), Some(true));
+        assert_eq!(terms.meets_floor(1.0, "USD"), None);
+
+        let b = bid(r#"{"id":"1","impid":"1","price":1,"dealid":"a"}"#)?;
+        let terms = imp.deal_eligibility(AuctionType::SecondPricePlus, Some("s2"), &b);
+        assert!(terms.is_eligible());
+        assert_eq!(terms.deal.map(|v| v.id.as_str()), Some("a"));
+        assert_eq!((terms.bidfloor, terms.bidfloorcur), (1.5, "USD"));
+        assert_eq!(terms.meets_floor(1.0, "USD"), Some(false));
+        let terms = imp.deal_eligibility(AuctionType::SecondPricePlus, Some("s3"), &b);
+        assert_eq!(terms.reasons, vec![Ineligibility::SeatNotAllowed]);
+        let terms = imp.deal_eligibility(AuctionType::SecondPricePlus, None, &b);
+        assert_eq!(terms.reasons, vec![Ineligibility::SeatNotAllowed]);
+
+        let b = bid(r#"{"id":"1","impid":"1","price":1,"dealid":"b","adomain":["ACME.com"]}"#)?;
+        let terms = imp.deal_eligibility(AuctionType::SecondPricePlus, None, &b);
+        assert!(terms.is_eligible());
+        assert_eq!(terms.at, AuctionType::FirstPrice);
+        let b = bid(r#"{"id":"1","impid":"1","price":1,"dealid":"b"}"#)?;
+        let terms = imp.deal_eligibility(AuctionType::SecondPricePlus, None, &b);
+        assert_eq!(terms.reasons, vec![Ineligibility::AdvertiserNotAllowed]);
+
+        let b = bid(r#"{"id":"1","impid":"1","price":1,"dealid":"c"}"#)?;
+        let terms = imp.deal_eligibility(AuctionType::SecondPricePlus, None, &b);
+        assert_eq!(terms.reasons, vec![Ineligibility::UnknownDeal("c".into())]);
+
+        Ok(())
+    }
 }
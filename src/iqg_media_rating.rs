@@ -5,6 +5,12 @@ use serde_repr::{Deserialize_repr, Serialize_repr};
 /// The following table lists the media ratings used in describing content based on the IQG 2.1
 /// categorization. Refer to www.iab.com/guidelines/digital-video-suite for more information.
 #[derive(Serialize_repr, Deserialize_repr, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::repr::<Self>)
+)]
 #[repr(i32)]
 pub enum IqgMediaRating {
     /// All Audiences
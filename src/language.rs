@@ -0,0 +1,127 @@
+/// An ISO-639-1 language code, as used by [`BidRequest#wlang`], [`Device#language`],
+/// [`Content#language`] and [`Bid#language`].
+///
+/// Parsing ignores ASCII case and accepts a language tag with a region or script subtag, such as
+/// `en-US`, as its primary language; the code is kept in lower case.
+///
+/// ```
+/// # use openrtb2::Language;
+/// let lang: Language = "EN_us".parse()?;
+/// assert_eq!(lang.as_str(), "en");
+/// assert!("xx".parse::<Language>().is_err());
+/// # Ok::<(), openrtb2::LanguageError>(())
+/// ```
+///
+/// [`BidRequest#wlang`]: ./struct.BidRequest.html#structfield.wlang
+/// [`Device#language`]: ./struct.Device.html#structfield.language
+/// [`Content#language`]: ./struct.Content.html#structfield.language
+/// [`Bid#language`]: ./struct.Bid.html#structfield.language
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy)]
+pub struct Language([u8; 2]);
+
+/// An error returned when parsing a [`Language`]: the value is not an ISO-639-1 code.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LanguageError(pub String);
+
+/// The ISO-639-1 codes, sorted for binary search.
+const CODES: &[&str] = &[
+    "aa", "ab", "ae", "af", "ak", "am", "an", "ar", "as", "av", "ay", "az", "ba", "be", "bg", "bi",
+    "bm", "bn", "bo", "br", "bs", "ca", "ce", "ch", "co", "cr", "cs", "cu", "cv", "cy", "da", "de",
+    "dv", "dz", "ee", "el", "en", "eo", "es", "et", "eu", "fa", "ff", "fi", "fj", "fo", "fr", "fy",
+    "ga", "gd", "gl", "gn", "gu", "gv", "ha", "he", "hi", "ho", "hr", "ht", "hu", "hy", "hz", "ia",
+    "id", "ie", "ig", "ii", "ik", "io", "is", "it", "iu", "ja", "jv", "ka", "kg", "ki", "kj", "kk",
+    "kl", "km", "kn", "ko", "kr", "ks", "ku", "kv", "kw", "ky", "la", "lb", "lg", "li", "ln", "lo",
+    "lt", "lu", "lv", "mg", "mh", "mi", "mk", "ml", "mn", "mr", "ms", "mt", "my", "na", "nb", "nd",
+    "ne", "ng", "nl", "nn", "no", "nr", "nv", "ny", "oc", "oj", "om", "or", "os", "pa", "pi", "pl",
+    "ps", "pt", "qu", "rm", "rn", "ro", "ru", "rw", "sa", "sc", "sd", "se", "sg", "si", "sk", "sl",
+    "sm", "sn", "so", "sq", "sr", "ss", "st", "su", "sv", "sw", "ta", "te", "tg", "th", "ti", "tk",
+    "tl", "tn", "to", "tr", "ts", "tt", "tw", "ty", "ug", "uk", "ur", "uz", "ve", "vi", "vo", "wa",
+    "wo", "xh", "yi", "yo", "za", "zh", "zu",
+];
+
+impl Language {
+    /// Parses a language code or tag.
+    pub fn parse(s: &str) -> Result<Self, LanguageError> {
+        let primary = s.trim().split(['-', '_']).next().unwrap_or_default();
+        let code = primary.to_ascii_lowercase();
+        match code.as_bytes() {
+            &[a, b] if CODES.binary_search(&code.as_str()).is_ok() => Ok(Self([a, b])),
+            _ => Err(LanguageError(s.to_owned())),
+        }
+    }
+
+    /// Returns the code in lower case.
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.0).expect("language codes are ASCII")
+    }
+}
+
+impl std::str::FromStr for Language {
+    type Err = LanguageError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::fmt::Display for LanguageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid ISO-639-1 language code {:?}", self.0)
+    }
+}
+
+impl std::error::Error for LanguageError {}
+
+impl serde::Serialize for Language {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Language {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = std::borrow::Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() -> serde_json::Result<()> {
+        assert!(CODES.windows(2).all(|w| w[0] < w[1]));
+
+        assert_eq!(
+            Language::parse("de").map(|v| v.to_string()),
+            Ok("de".into())
+        );
+        assert_eq!(
+            Language::parse(" ZH-Hant-TW ").map(|v| v.to_string()),
+            Ok("zh".into())
+        );
+        assert_eq!(Language::parse("eng"), Err(LanguageError("eng".into())));
+        assert!(Language::parse("").is_err());
+        assert!(Language::parse("qq").is_err());
+        assert!(Language::parse("é").is_err());
+
+        let v: Vec<Language> = serde_json::from_str(r#"["fr","EN-gb"]"#)?;
+        assert_eq!(serde_json::to_string(&v)?, r#"["fr","en"]"#);
+        assert!(serde_json::from_str::<Language>(r#""xx""#).is_err());
+
+        Ok(())
+    }
+}
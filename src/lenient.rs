@@ -0,0 +1,538 @@
+use serde::de::value::StrDeserializer;
+use serde::de::{DeserializeSeed, Visitor};
+use serde_json::Value;
+use std::cell::RefCell;
+
+/// The outcome of a lenient deserialization: the deserialized value and every coercion that had to
+/// be applied to the input to produce it.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Lenient<T> {
+    /// The deserialized value.
+    pub value: T,
+
+    /// Coercions applied to the input, in the order they were applied.
+    pub warnings: Vec<Coercion>,
+}
+
+/// A single repair applied to the input by lenient deserialization.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Coercion {
+    /// Location of the repaired value, e.g. `imp[0].secure`.
+    pub path: String,
+
+    /// The kind of repair.
+    pub kind: CoercionKind,
+
+    /// The value as it was received.
+    pub from: Value,
+
+    /// The value it was replaced with.
+    pub to: Value,
+}
+
+impl std::fmt::Display for Coercion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} ({} -> {})",
+            self.path, self.kind, self.from, self.to
+        )
+    }
+}
+
+/// The partner mistakes that lenient deserialization recovers from.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CoercionKind {
+    /// A boolean where an integer was expected (e.g., `"secure": true`).
+    BoolAsInteger,
+    /// A numeric string where a number was expected (e.g., `"bidfloor": "0.5"`).
+    StringAsNumber,
+    /// `null` where an array was expected; read as an empty array.
+    NullAsEmptyArray,
+    /// A floating point number where an integer was expected (e.g., `"exp": 1.5`); rounded to the
+    /// nearest integer.
+    FloatAsInteger,
+    /// An integer other than 0 or 1 for a 0/1 flag; any non-zero value is read as 1.
+    FlagOutOfRange,
+}
+
+impl std::fmt::Display for CoercionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::BoolAsInteger => "boolean read as integer",
+            Self::StringAsNumber => "string read as number",
+            Self::NullAsEmptyArray => "null read as empty array",
+            Self::FloatAsInteger => "floating point read as integer",
+            Self::FlagOutOfRange => "out of range flag read as 1",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Deserializes an instance of type `T` from a string of JSON text, coercing common partner
+/// mistakes instead of failing. Each coercion is recorded in [`Lenient::warnings`].
+///
+/// Values are coerced in a single pass, as the type being deserialized asks for them.
+///
+/// ```
+/// # use openrtb2::{BidRequest, CoercionKind};
+/// let json = r#"{"id":"1","imp":[{"id":"1","secure":true,"bidfloor":"0.5"}]}"#;
+/// let lenient = openrtb2::from_str_lenient::<BidRequest>(json)?;
+/// assert_eq!(lenient.value.imp[0].secure, Some(true));
+/// assert_eq!(lenient.value.imp[0].bidfloor, 0.5);
+/// assert_eq!(lenient.warnings[0].path, "imp[0].bidfloor");
+/// assert_eq!(lenient.warnings[0].kind, CoercionKind::StringAsNumber);
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+pub fn from_str_lenient<T>(s: &str) -> serde_json::Result<Lenient<T>>
+where
+    T: serde::de::DeserializeOwned,
+{
+    from_value_lenient(serde_json::from_str(s)?)
+}
+
+/// Deserializes an instance of type `T` from bytes of JSON text, coercing common partner mistakes
+/// instead of failing. See [`from_str_lenient`].
+pub fn from_slice_lenient<T>(v: &[u8]) -> serde_json::Result<Lenient<T>>
+where
+    T: serde::de::DeserializeOwned,
+{
+    from_value_lenient(serde_json::from_slice(v)?)
+}
+
+/// Interprets a `serde_json::Value` as an instance of type `T`, coercing common partner mistakes
+/// instead of failing. See [`from_str_lenient`].
+pub fn from_value_lenient<T>(value: Value) -> serde_json::Result<Lenient<T>>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let warnings = RefCell::new(Vec::new());
+    let value = T::deserialize(Coercing {
+        value,
+        path: Path::Root,
+        warnings: &warnings,
+    })?;
+    Ok(Lenient {
+        value,
+        warnings: warnings.into_inner(),
+    })
+}
+
+/// The location of a value in the input, e.g. `imp[0].bidfloor`.
+#[derive(Clone, Copy)]
+enum Path<'p> {
+    Root,
+    Key(&'p Path<'p>, &'p str),
+    Index(&'p Path<'p>, usize),
+}
+
+impl std::fmt::Display for Path<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Root => Ok(()),
+            Self::Key(Self::Root, key) => f.write_str(key),
+            Self::Key(parent, key) => write!(f, "{}.{}", parent, key),
+            Self::Index(parent, i) => write!(f, "{}[{}]", parent, i),
+        }
+    }
+}
+
+/// A deserializer of a `Value` that coerces it to what the visitor asks for, recording each
+/// coercion, and does the same for the values it contains.
+struct Coercing<'p> {
+    value: Value,
+    path: Path<'p>,
+    warnings: &'p RefCell<Vec<Coercion>>,
+}
+
+impl Coercing<'_> {
+    fn coerce(&mut self, kind: CoercionKind, to: Value) {
+        let from = std::mem::replace(&mut self.value, to.clone());
+        self.warnings.borrow_mut().push(Coercion {
+            path: self.path.to_string(),
+            kind,
+            from,
+            to,
+        });
+    }
+
+    /// Coerces a boolean, numeric string or floating point number to an integer, and an integer
+    /// other than 0 or 1 to 1 if `flag`.
+    fn integer(&mut self, flag: bool) {
+        match &self.value {
+            Value::Bool(b) => {
+                let to = Value::from(*b as i32);
+                self.coerce(CoercionKind::BoolAsInteger, to);
+            }
+            Value::String(_) => self.number(),
+            _ => {}
+        }
+        if let Some(v) = self.value.as_f64().filter(|_| self.value.is_f64()) {
+            self.coerce(CoercionKind::FloatAsInteger, Value::from(v.round() as i64));
+        }
+        match self.value.as_f64() {
+            Some(v) if flag && v != 0.0 && v != 1.0 => {
+                self.coerce(CoercionKind::FlagOutOfRange, Value::from((v != 0.0) as i32));
+            }
+            _ => {}
+        }
+    }
+
+    /// Coerces a numeric string to a number.
+    fn number(&mut self) {
+        if let Some(to) = self.value.as_str().and_then(parse_number) {
+            self.coerce(CoercionKind::StringAsNumber, to);
+        }
+    }
+}
+
+/// Displays what a visitor expects.
+struct Expecting<'a, V>(&'a V);
+
+impl<'de, V: Visitor<'de>> std::fmt::Display for Expecting<'_, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.expecting(f)
+    }
+}
+
+macro_rules! deserialize_integer {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V>(mut self, visitor: V) -> serde_json::Result<V::Value>
+            where
+                V: Visitor<'de>,
+            {
+                self.integer(Expecting(&visitor).to_string() == crate::serde::FLAG);
+                self.value.$method(visitor)
+            }
+        )*
+    };
+}
+
+macro_rules! deserialize_value {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> serde_json::Result<V::Value>
+            where
+                V: Visitor<'de>,
+            {
+                self.value.$method(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> serde::Deserializer<'de> for Coercing<'_> {
+    type Error = serde_json::Error;
+
+    deserialize_integer! {
+        deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64 deserialize_i128
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64 deserialize_u128
+    }
+
+    deserialize_value! {
+        deserialize_bool deserialize_char deserialize_str deserialize_string deserialize_bytes
+        deserialize_byte_buf deserialize_unit deserialize_identifier deserialize_ignored_any
+    }
+
+    fn deserialize_any<V>(self, visitor: V) -> serde_json::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Array(v) => visitor.visit_seq(SeqAccess {
+                path: self.path,
+                warnings: self.warnings,
+                values: v.into_iter().enumerate(),
+            }),
+            Value::Object(v) => visitor.visit_map(MapAccess {
+                path: self.path,
+                warnings: self.warnings,
+                entries: v.into_iter(),
+                value: None,
+            }),
+            v => v.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_f32<V>(self, visitor: V) -> serde_json::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_f64<V>(mut self, visitor: V) -> serde_json::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.number();
+        self.value.deserialize_f64(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> serde_json::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> serde_json::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.value.deserialize_unit_struct(name, visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> serde_json::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(mut self, visitor: V) -> serde_json::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if self.value.is_null() {
+            self.coerce(CoercionKind::NullAsEmptyArray, Value::Array(Vec::new()));
+        }
+        match self.value {
+            Value::Array(_) => self.deserialize_any(visitor),
+            v => v.deserialize_seq(visitor),
+        }
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> serde_json::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> serde_json::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> serde_json::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Object(_) => self.deserialize_any(visitor),
+            v => v.deserialize_map(visitor),
+        }
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> serde_json::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> serde_json::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.value.deserialize_enum(name, variants, visitor)
+    }
+}
+
+struct SeqAccess<'p> {
+    path: Path<'p>,
+    warnings: &'p RefCell<Vec<Coercion>>,
+    values: std::iter::Enumerate<std::vec::IntoIter<Value>>,
+}
+
+impl<'de> serde::de::SeqAccess<'de> for SeqAccess<'_> {
+    type Error = serde_json::Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> serde_json::Result<Option<T::Value>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.values.next() {
+            Some((i, value)) => seed
+                .deserialize(Coercing {
+                    value,
+                    path: Path::Index(&self.path, i),
+                    warnings: self.warnings,
+                })
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.values.len())
+    }
+}
+
+struct MapAccess<'p> {
+    path: Path<'p>,
+    warnings: &'p RefCell<Vec<Coercion>>,
+    entries: serde_json::map::IntoIter,
+    value: Option<(String, Value)>,
+}
+
+impl<'de> serde::de::MapAccess<'de> for MapAccess<'_> {
+    type Error = serde_json::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> serde_json::Result<Option<K::Value>>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        let (key, value) = match self.entries.next() {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        let k = seed.deserialize(StrDeserializer::<serde_json::Error>::new(&key))?;
+        self.value = Some((key, value));
+        Ok(Some(k))
+    }
+
+    fn next_value_seed<T>(&mut self, seed: T) -> serde_json::Result<T::Value>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        let (key, value) = self
+            .value
+            .take()
+            .ok_or_else(|| serde::de::Error::custom("value is missing"))?;
+        seed.deserialize(Coercing {
+            value,
+            path: Path::Key(&self.path, &key),
+            warnings: self.warnings,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+fn parse_number(s: &str) -> Option<Value> {
+    let s = s.trim();
+    match s.parse::<i64>() {
+        Ok(v) => Some(v.into()),
+        Err(_) => s
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn coercions() -> serde_json::Result<()> {
+        let json = r#"{
+            "id": "1",
+            "imp": [
+                {"id": "1", "secure": true, "bidfloor": "0.5", "exp": 1.6, "instl": 2},
+                {"id": "2", "banner": {"topframe": "1", "w": "300", "h": 250.0}}
+            ],
+            "test": false
+        }"#;
+        let lenient = from_str_lenient::<crate::BidRequest>(json)?;
+
+        let imp = &lenient.value.imp;
+        assert_eq!(imp[0].secure, Some(true));
+        assert_eq!(imp[0].bidfloor, 0.5);
+        assert_eq!(imp[0].exp, Some(2));
+        assert!(imp[0].instl);
+        let banner = imp[1].banner.as_ref().unwrap();
+        assert_eq!(banner.topframe, Some(true));
+        assert_eq!((banner.w, banner.h), (Some(300), Some(250)));
+        assert!(!lenient.value.test);
+
+        let warnings: Vec<_> = lenient
+            .warnings
+            .iter()
+            .map(|c| (c.path.as_str(), c.kind))
+            .collect();
+        assert_eq!(
+            warnings,
+            vec![
+                ("imp[0].bidfloor", CoercionKind::StringAsNumber),
+                ("imp[0].exp", CoercionKind::FloatAsInteger),
+                ("imp[0].instl", CoercionKind::FlagOutOfRange),
+                ("imp[0].secure", CoercionKind::BoolAsInteger),
+                ("imp[1].banner.h", CoercionKind::FloatAsInteger),
+                ("imp[1].banner.topframe", CoercionKind::StringAsNumber),
+                ("imp[1].banner.w", CoercionKind::StringAsNumber),
+                ("test", CoercionKind::BoolAsInteger),
+            ]
+        );
+        assert_eq!(
+            lenient.warnings[2].to_string(),
+            "imp[0].instl: out of range flag read as 1 (2 -> 1)"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn null_array() -> serde_json::Result<()> {
+        let lenient = from_str_lenient::<crate::BidRequest>(r#"{"id":"1","imp":null}"#)?;
+        assert!(lenient.value.imp.is_empty());
+        assert_eq!(lenient.warnings[0].kind, CoercionKind::NullAsEmptyArray);
+        assert_eq!(lenient.warnings[0].from, Value::Null);
+
+        Ok(())
+    }
+
+    #[test]
+    fn unrecoverable() {
+        assert!(from_str_lenient::<crate::BidRequest>(r#"{"id":"1"}"#).is_err());
+        assert!(from_str_lenient::<crate::BidRequest>(r#"{"id":1,"imp":[]}"#).is_err());
+        assert!(from_str_lenient::<crate::Imp>(r#"{"id":"1","bidfloor":"abc"}"#).is_err());
+        assert!(from_str_lenient::<crate::Imp>(r#"{"id":"1","banner":{"pos":99}}"#).is_err());
+    }
+
+    #[test]
+    fn many_coercions() -> serde_json::Result<()> {
+        let imp = serde_json::json!({"id":"1","secure":true,"bidfloor":"1","exp":1.5,"instl":2});
+        let req = serde_json::json!({ "id": "1", "imp": vec![imp; 300] });
+        let lenient = from_value_lenient::<crate::BidRequest>(req)?;
+        assert_eq!(lenient.value.imp.len(), 300);
+        assert_eq!(lenient.warnings.len(), 1200);
+        assert_eq!(lenient.warnings[1199].path, "imp[299].secure");
+
+        Ok(())
+    }
+}
@@ -106,6 +106,15 @@ pub use seat_bid::*;
 mod bid;
 pub use bid::*;
 
+// 4.4
+mod substitution_macro;
+pub use substitution_macro::*;
+
+#[cfg(feature = "notice-url")]
+mod notice_url;
+#[cfg(feature = "notice-url")]
+pub use notice_url::*;
+
 // ===== 5 enum =====
 
 // 5.1
@@ -222,6 +231,88 @@ pub use gender::*;
 mod max_extended_ad_duration;
 pub use max_extended_ad_duration::*;
 
+mod media_type;
+pub use media_type::*;
+
+mod deal_eligibility;
+pub use deal_eligibility::*;
+
+mod pod;
+pub use pod::*;
+
+mod floor;
+pub use floor::*;
+
+mod targeting;
+pub use targeting::*;
+
+mod segment_index;
+pub use segment_index::*;
+
+mod secure_creative;
+pub use secure_creative::*;
+
+mod advertiser_blocking;
+pub use advertiser_blocking::*;
+
+mod creative_blocking;
+pub use creative_blocking::*;
+
+mod language;
+pub use language::*;
+
+mod country;
+pub use country::*;
+
+mod geo_point;
+pub use geo_point::*;
+
+#[cfg(feature = "device-ids")]
+mod device_ids;
+#[cfg(feature = "device-ids")]
+pub use device_ids::*;
+
+mod user_agent;
+pub use user_agent::*;
+
+mod public_suffix;
+
+// ===== parsing =====
+
+mod lenient;
+pub use lenient::*;
+
+mod parse;
+pub use parse::*;
+
+// ===== markup =====
+
+pub mod native_ads;
+
+#[cfg(feature = "vast")]
+pub mod vast;
+
+// ===== binary formats =====
+
+#[cfg(feature = "msgpack")]
+mod msgpack;
+#[cfg(feature = "msgpack")]
+pub use msgpack::*;
+
+#[cfg(feature = "cbor")]
+mod cbor;
+#[cfg(feature = "cbor")]
+pub use cbor::*;
+
+// ===== fuzzing =====
+
+#[cfg(feature = "arbitrary")]
+mod fuzzing;
+#[cfg(feature = "arbitrary")]
+pub use fuzzing::*;
+
 // ===== internal =====
 
+#[cfg(feature = "schema")]
+mod schema;
 mod serde;
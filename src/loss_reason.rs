@@ -1,15 +1,12 @@
-use serde_repr::{Deserialize_repr, Serialize_repr};
-
 /// 5.25 Loss Reason Codes
 ///
 /// The following table lists the options for an exchange to inform a bidder as to the reason why
 /// they did not win an impression.
 #[allow(non_camel_case_types)]
-#[derive(Serialize_repr, Deserialize_repr, Debug, PartialEq, Eq, Clone, Copy)]
-#[repr(i32)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum LossReason {
     /// Bid Won
-    BidWon = 0,
+    BidWon,
     /// Internal Error
     InternalError,
     /// Impression Opportunity Expired
@@ -31,7 +28,7 @@ pub enum LossReason {
     /// Missing Minimum Creative Approval Data
     MissingMinCreativeApprovalData,
     /// Bid was Below Auction Floor
-    BidBelowAuctionFloor = 100,
+    BidBelowAuctionFloor,
     /// Bid was Below Deal Floor
     BidBelowDealFloor,
     /// Lost to Higher Bid
@@ -41,7 +38,7 @@ pub enum LossReason {
     /// Buyer Seat Blocked
     BuyerSeatBlocked,
     /// Creative Filtered - General; reason unknown.
-    CreativeFiltered_General = 200,
+    CreativeFiltered_General,
     /// Creative Filtered - Pending processing by Exchange (e.g., approval, transcoding, etc.)
     CreativeFiltered_Pending,
     /// Creative Filtered - Disapproved by Exchange
@@ -68,7 +65,230 @@ pub enum LossReason {
     CreativeFiltered_AnimationTooLong,
     /// Creative Filtered - Not Allowed in PMP Deal
     CreativeFiltered_NotAllowedPmpDeal,
-    // TODO: ≥ 1000 Exchange specific (should be communicated to bidders a priori)
+    /// Exchange specific, 1000 or greater (should be communicated to bidders a priori)
+    ExchangeSpecific(i32),
+}
+
+/// A family of [`LossReason`] codes, as returned by [`LossReason::category`].
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum LossReasonCategory {
+    /// The bid won (code 0).
+    Won,
+    /// The bid was invalid or could not be processed (codes 1 to 10).
+    Error,
+    /// The bid lost the auction (codes 100 to 104).
+    Auction,
+    /// The creative was filtered (codes 200 to 213).
+    CreativeFiltered,
+    /// An exchange-specific reason (codes 1000 and greater).
+    ExchangeSpecific,
+}
+
+impl LossReason {
+    /// All codes defined by the specification, in code order.
+    pub const KNOWN: [Self; 30] = [
+        Self::BidWon,
+        Self::InternalError,
+        Self::ImpressionOpportunityExpired,
+        Self::InvalidBidResponse,
+        Self::InvalidDealId,
+        Self::InvalidAuctionId,
+        Self::InvalidAdvertiserDomain,
+        Self::MissingMarkup,
+        Self::MissingCreativeId,
+        Self::MissingBidPrice,
+        Self::MissingMinCreativeApprovalData,
+        Self::BidBelowAuctionFloor,
+        Self::BidBelowDealFloor,
+        Self::LostHigherBid,
+        Self::LostPmpDeal,
+        Self::BuyerSeatBlocked,
+        Self::CreativeFiltered_General,
+        Self::CreativeFiltered_Pending,
+        Self::CreativeFiltered_Disapproved,
+        Self::CreativeFiltered_SizeNotAllowed,
+        Self::CreativeFiltered_IncorrectFormat,
+        Self::CreativeFiltered_AdvertiserExclusions,
+        Self::CreativeFiltered_AppBundleExclusions,
+        Self::CreativeFiltered_NotSecure,
+        Self::CreativeFiltered_LanguageExclusions,
+        Self::CreativeFiltered_CategoryExclusions,
+        Self::CreativeFiltered_AttributeExclusions,
+        Self::CreativeFiltered_AdTypeExclusions,
+        Self::CreativeFiltered_AnimationTooLong,
+        Self::CreativeFiltered_NotAllowedPmpDeal,
+    ];
+
+    /// Returns the code of the reason.
+    pub fn code(self) -> i32 {
+        match self {
+            Self::BidWon => 0,
+            Self::InternalError => 1,
+            Self::ImpressionOpportunityExpired => 2,
+            Self::InvalidBidResponse => 3,
+            Self::InvalidDealId => 4,
+            Self::InvalidAuctionId => 5,
+            Self::InvalidAdvertiserDomain => 6,
+            Self::MissingMarkup => 7,
+            Self::MissingCreativeId => 8,
+            Self::MissingBidPrice => 9,
+            Self::MissingMinCreativeApprovalData => 10,
+            Self::BidBelowAuctionFloor => 100,
+            Self::BidBelowDealFloor => 101,
+            Self::LostHigherBid => 102,
+            Self::LostPmpDeal => 103,
+            Self::BuyerSeatBlocked => 104,
+            Self::CreativeFiltered_General => 200,
+            Self::CreativeFiltered_Pending => 201,
+            Self::CreativeFiltered_Disapproved => 202,
+            Self::CreativeFiltered_SizeNotAllowed => 203,
+            Self::CreativeFiltered_IncorrectFormat => 204,
+            Self::CreativeFiltered_AdvertiserExclusions => 205,
+            Self::CreativeFiltered_AppBundleExclusions => 206,
+            Self::CreativeFiltered_NotSecure => 207,
+            Self::CreativeFiltered_LanguageExclusions => 208,
+            Self::CreativeFiltered_CategoryExclusions => 209,
+            Self::CreativeFiltered_AttributeExclusions => 210,
+            Self::CreativeFiltered_AdTypeExclusions => 211,
+            Self::CreativeFiltered_AnimationTooLong => 212,
+            Self::CreativeFiltered_NotAllowedPmpDeal => 213,
+            Self::ExchangeSpecific(v) => v,
+        }
+    }
+
+    /// Returns the reason with the given code, or `None` if the code is neither defined by the
+    /// specification nor in the exchange-specific range.
+    pub fn from_code(code: i32) -> Option<Self> {
+        if code >= 1000 {
+            return Some(Self::ExchangeSpecific(code));
+        }
+        Self::KNOWN.iter().copied().find(|v| v.code() == code)
+    }
+
+    /// Returns the family of the reason.
+    pub fn category(self) -> LossReasonCategory {
+        match self.code() {
+            0 => LossReasonCategory::Won,
+            1..=99 => LossReasonCategory::Error,
+            100..=199 => LossReasonCategory::Auction,
+            200..=999 => LossReasonCategory::CreativeFiltered,
+            _ => LossReasonCategory::ExchangeSpecific,
+        }
+    }
+
+    /// Returns `true` if the creative was filtered, i.e. for codes 200 to 213.
+    pub fn is_creative_filtered(self) -> bool {
+        self.category() == LossReasonCategory::CreativeFiltered
+    }
+
+    /// Returns `true` if the bid lost on price: it was below a floor, outbid, or had no price.
+    pub fn is_price_related(self) -> bool {
+        matches!(
+            self,
+            Self::MissingBidPrice
+                | Self::BidBelowAuctionFloor
+                | Self::BidBelowDealFloor
+                | Self::LostHigherBid
+        )
+    }
+}
+
+impl std::fmt::Display for LossReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::BidWon => "Bid Won",
+            Self::InternalError => "Internal Error",
+            Self::ImpressionOpportunityExpired => "Impression Opportunity Expired",
+            Self::InvalidBidResponse => "Invalid Bid Response",
+            Self::InvalidDealId => "Invalid Deal ID",
+            Self::InvalidAuctionId => "Invalid Auction ID",
+            Self::InvalidAdvertiserDomain => "Invalid (i.e., malformed) Advertiser Domain",
+            Self::MissingMarkup => "Missing Markup",
+            Self::MissingCreativeId => "Missing Creative ID",
+            Self::MissingBidPrice => "Missing Bid Price",
+            Self::MissingMinCreativeApprovalData => "Missing Minimum Creative Approval Data",
+            Self::BidBelowAuctionFloor => "Bid was Below Auction Floor",
+            Self::BidBelowDealFloor => "Bid was Below Deal Floor",
+            Self::LostHigherBid => "Lost to Higher Bid",
+            Self::LostPmpDeal => "Lost to a Bid for a PMP Deal",
+            Self::BuyerSeatBlocked => "Buyer Seat Blocked",
+            Self::CreativeFiltered_General => "Creative Filtered - General; reason unknown.",
+            Self::CreativeFiltered_Pending => "Creative Filtered - Pending processing by Exchange (e.g., approval, transcoding, etc.)",
+            Self::CreativeFiltered_Disapproved => "Creative Filtered - Disapproved by Exchange",
+            Self::CreativeFiltered_SizeNotAllowed => "Creative Filtered - Size Not Allowed",
+            Self::CreativeFiltered_IncorrectFormat => "Creative Filtered - Incorrect Creative Format",
+            Self::CreativeFiltered_AdvertiserExclusions => "Creative Filtered - Advertiser Exclusions",
+            Self::CreativeFiltered_AppBundleExclusions => "Creative Filtered – App Bundle Exclusions",
+            Self::CreativeFiltered_NotSecure => "Creative Filtered - Not Secure",
+            Self::CreativeFiltered_LanguageExclusions => "Creative Filtered - Language Exclusions",
+            Self::CreativeFiltered_CategoryExclusions => "Creative Filtered - Category Exclusions",
+            Self::CreativeFiltered_AttributeExclusions => "Creative Filtered - Creative Attribute Exclusions",
+            Self::CreativeFiltered_AdTypeExclusions => "Creative Filtered - Ad Type Exclusions",
+            Self::CreativeFiltered_AnimationTooLong => "Creative Filtered - Animation Too Long",
+            Self::CreativeFiltered_NotAllowedPmpDeal => "Creative Filtered - Not Allowed in PMP Deal",
+            Self::ExchangeSpecific(v) => return write!(f, "Exchange-specific loss reason {}", v),
+        };
+        f.write_str(s)
+    }
+}
+
+impl serde::Serialize for LossReason {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_i32(self.code())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for LossReason {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let v = i32::deserialize(deserializer)?;
+        Self::from_code(v).ok_or_else(|| {
+            let s = format!(
+                "invalid value: {}, expected 0 to 10, 100 to 104, 200 to 213 or at least 1000",
+                v
+            );
+            serde::de::Error::custom(s)
+        })
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for LossReason {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let v = match Self::KNOWN.get(u.int_in_range(0..=Self::KNOWN.len())?) {
+            Some(v) => *v,
+            None => Self::ExchangeSpecific(u.int_in_range(1000..=i32::MAX)?),
+        };
+        Ok(v)
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for LossReason {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "LossReason".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        let mut variants: Vec<_> = Self::KNOWN
+            .iter()
+            .map(|v| serde_json::json!({ "const": v.code(), "description": v.to_string() }))
+            .collect();
+        variants.push(
+            serde_json::json!({ "minimum": 1000, "description": "Exchange-specific loss reason" }),
+        );
+        schemars::json_schema!({
+            "description": "Options for an exchange to inform a bidder as to the reason why they did not win \
+                an impression. Codes of 1000 and greater are exchange specific.",
+            "type": "integer",
+            "oneOf": variants
+        })
+    }
 }
 
 #[cfg(test)]
@@ -78,8 +298,10 @@ mod test {
     #[test]
     fn json() -> serde_json::Result<()> {
         assert!(serde_json::from_str::<LossReason>("-1").is_err());
+        assert!(serde_json::from_str::<LossReason>("11").is_err());
+        assert!(serde_json::from_str::<LossReason>("999").is_err());
 
-        let json = "[0,1,100,101,200,201]";
+        let json = "[0,1,100,101,200,201,1000]";
         let e1: Vec<LossReason> = serde_json::from_str(json)?;
         assert_eq!(
             e1,
@@ -90,6 +312,7 @@ mod test {
                 LossReason::BidBelowDealFloor,
                 LossReason::CreativeFiltered_General,
                 LossReason::CreativeFiltered_Pending,
+                LossReason::ExchangeSpecific(1000),
             ]
         );
         assert_eq!(serde_json::to_string(&e1)?, json);
@@ -98,12 +321,46 @@ mod test {
     }
 
     #[test]
-    fn repr() {
-        assert_eq!(LossReason::BidWon as i32, 0);
-        assert_eq!(LossReason::InternalError as i32, 1);
-        assert_eq!(LossReason::BidBelowAuctionFloor as i32, 100);
-        assert_eq!(LossReason::BidBelowDealFloor as i32, 101);
-        assert_eq!(LossReason::CreativeFiltered_General as i32, 200);
-        assert_eq!(LossReason::CreativeFiltered_Pending as i32, 201);
+    fn code() {
+        assert_eq!(LossReason::BidWon.code(), 0);
+        assert_eq!(LossReason::InternalError.code(), 1);
+        assert_eq!(LossReason::BidBelowAuctionFloor.code(), 100);
+        assert_eq!(LossReason::BidBelowDealFloor.code(), 101);
+        assert_eq!(LossReason::CreativeFiltered_General.code(), 200);
+        assert_eq!(LossReason::CreativeFiltered_NotAllowedPmpDeal.code(), 213);
+        assert_eq!(LossReason::ExchangeSpecific(1234).code(), 1234);
+
+        for v in LossReason::KNOWN.iter() {
+            assert_eq!(LossReason::from_code(v.code()), Some(*v));
+        }
+        assert_eq!(LossReason::from_code(105), None);
+    }
+
+    #[test]
+    fn category() {
+        assert_eq!(LossReason::BidWon.category(), LossReasonCategory::Won);
+        assert_eq!(
+            LossReason::MissingMarkup.category(),
+            LossReasonCategory::Error
+        );
+        assert_eq!(
+            LossReason::BuyerSeatBlocked.category(),
+            LossReasonCategory::Auction
+        );
+        assert_eq!(
+            LossReason::ExchangeSpecific(1000).category(),
+            LossReasonCategory::ExchangeSpecific
+        );
+
+        assert!(LossReason::CreativeFiltered_NotSecure.is_creative_filtered());
+        assert!(!LossReason::LostPmpDeal.is_creative_filtered());
+        assert!(LossReason::BidBelowDealFloor.is_price_related());
+        assert!(!LossReason::CreativeFiltered_SizeNotAllowed.is_price_related());
+
+        assert_eq!(LossReason::LostHigherBid.to_string(), "Lost to Higher Bid");
+        assert_eq!(
+            LossReason::ExchangeSpecific(1001).to_string(),
+            "Exchange-specific loss reason 1001"
+        );
     }
 }
@@ -8,8 +8,11 @@
 /// [`Audio#maxextended`]: ./struct.Audio.html#structfield.maxextended
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum MaxExtendedAdDuration {
+    /// Extension allowed with no time limit
     NoLimit,
+    /// Extension not allowed
     NotAllowed,
+    /// Seconds of extended play beyond maxduration
     Specific(i32),
 }
 
@@ -51,6 +54,40 @@ impl<'de> serde::Deserialize<'de> for MaxExtendedAdDuration {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for MaxExtendedAdDuration {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let v = match u.int_in_range(0..=2)? {
+            0 => Self::NoLimit,
+            1 => Self::NotAllowed,
+            _ => Self::Specific(u.int_in_range(1..=i32::MAX)?),
+        };
+        Ok(v)
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for MaxExtendedAdDuration {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "MaxExtendedAdDuration".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "Maximum extended ad duration if extension is allowed. If blank or 0, \
+                extension is not allowed. If -1, extension is allowed, and there is no time limit \
+                imposed. If greater than 0, then the value represents the number of seconds of \
+                extended play supported beyond the maxduration value.",
+            "type": "integer",
+            "oneOf": [
+                { "const": -1, "description": "Extension allowed with no time limit" },
+                { "const": 0, "description": "Extension not allowed" },
+                { "minimum": 1, "description": "Seconds of extended play beyond maxduration" }
+            ]
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -0,0 +1,246 @@
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+/// [`Imp#banner`], [`Imp#video`], [`Imp#audio`], [`Imp#native`]
+///
+/// The type of creative markup; codes match the `mtype` attribute of OpenRTB 2.6.
+///
+/// [`Imp#banner`]: ./struct.Imp.html#structfield.banner
+/// [`Imp#video`]: ./struct.Imp.html#structfield.video
+/// [`Imp#audio`]: ./struct.Imp.html#structfield.audio
+/// [`Imp#native`]: ./struct.Imp.html#structfield.native
+#[derive(Serialize_repr, Deserialize_repr, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::repr::<Self>)
+)]
+#[repr(i32)]
+pub enum MediaType {
+    /// Banner
+    Banner = 1,
+    /// Video
+    Video,
+    /// Audio
+    Audio,
+    /// Native
+    Native,
+}
+
+/// A set of [`MediaType`]s.
+#[derive(Default, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct MediaTypes(u8);
+
+/// An error returned by [`Bid::infer_media_type`].
+///
+/// [`Bid::infer_media_type`]: ./struct.Bid.html#method.infer_media_type
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MediaTypeError {
+    /// The bid matches none of the types offered by the impression.
+    NoMatch {
+        /// Types offered by the impression.
+        offered: MediaTypes,
+        /// Types the bid can be.
+        detected: MediaTypes,
+    },
+    /// The bid matches more than one offered type and nothing tells them apart.
+    Ambiguous(MediaTypes),
+}
+
+impl MediaType {
+    /// All media types.
+    pub const ALL: [Self; 4] = [Self::Banner, Self::Video, Self::Audio, Self::Native];
+
+    fn bit(self) -> u8 {
+        1 << (self as i32 - 1)
+    }
+}
+
+impl MediaTypes {
+    /// Returns the set of all media types.
+    pub fn all() -> Self {
+        MediaType::ALL.iter().copied().collect()
+    }
+
+    /// Returns `true` if the set contains `v`.
+    pub fn contains(self, v: MediaType) -> bool {
+        self.0 & v.bit() != 0
+    }
+
+    /// Adds `v` to the set.
+    pub fn insert(&mut self, v: MediaType) {
+        self.0 |= v.bit();
+    }
+
+    /// Removes `v` from the set.
+    pub fn remove(&mut self, v: MediaType) {
+        self.0 &= !v.bit();
+    }
+
+    /// Returns the types in both `self` and `other`.
+    pub fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    /// Returns the number of types in the set.
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Returns `true` if the set is empty.
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterates over the types in the set, in code order.
+    pub fn iter(self) -> impl Iterator<Item = MediaType> {
+        MediaType::ALL
+            .iter()
+            .copied()
+            .filter(move |v| self.contains(*v))
+    }
+}
+
+impl std::iter::FromIterator<MediaType> for MediaTypes {
+    fn from_iter<I: IntoIterator<Item = MediaType>>(iter: I) -> Self {
+        let mut set = Self::default();
+        iter.into_iter().for_each(|v| set.insert(v));
+        set
+    }
+}
+
+impl std::fmt::Debug for MediaTypes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl std::fmt::Display for MediaTypeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoMatch { offered, detected } => write!(
+                f,
+                "bid can be {:?} but the impression offers {:?}",
+                detected, offered
+            ),
+            Self::Ambiguous(v) => write!(f, "bid can be any of {:?}", v),
+        }
+    }
+}
+
+impl std::error::Error for MediaTypeError {}
+
+/// Classifies ad markup: VAST is video or audio, native response JSON is native, and anything
+/// else (HTML or JavaScript) is a banner.
+pub(crate) fn markup_types(adm: &str) -> MediaTypes {
+    if is_vast(adm) {
+        // Audio VAST differs from video VAST only in the MIME types of its media files.
+        let audio = adm.contains("type=\"audio/") || adm.contains("type='audio/");
+        let video = adm.contains("type=\"video/") || adm.contains("type='video/");
+        return match (audio, video) {
+            (true, false) => [MediaType::Audio].iter().copied().collect(),
+            (false, true) => [MediaType::Video].iter().copied().collect(),
+            _ => [MediaType::Video, MediaType::Audio]
+                .iter()
+                .copied()
+                .collect(),
+        };
+    }
+    if is_native(adm) {
+        return [MediaType::Native].iter().copied().collect();
+    }
+    [MediaType::Banner].iter().copied().collect()
+}
+
+fn is_vast(adm: &str) -> bool {
+    let mut s = adm.trim_start_matches('\u{feff}').trim_start();
+    // Skip the XML declaration, processing instructions and comments.
+    loop {
+        let end = if s.starts_with("<?") {
+            s.find("?>").map(|i| i + 2)
+        } else if s.starts_with("<!--") {
+            s.find("-->").map(|i| i + 3)
+        } else {
+            break;
+        };
+        match end {
+            Some(i) => s = s[i..].trim_start(),
+            None => return false,
+        }
+    }
+    s.starts_with("<VAST")
+}
+
+fn is_native(adm: &str) -> bool {
+    if !adm.trim_start().starts_with('{') {
+        return false;
+    }
+    let value: serde_json::Value = match serde_json::from_str(adm) {
+        Ok(v) => v,
+        Err(_) => return false,
+    };
+    let value = value.get("native").unwrap_or(&value);
+    ["link", "assets", "assetsurl", "dcourl"]
+        .iter()
+        .any(|k| value.get(k).is_some())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn json() -> serde_json::Result<()> {
+        assert!(serde_json::from_str::<MediaType>("0").is_err());
+
+        let json = "[1,4]";
+        let e1: Vec<MediaType> = serde_json::from_str(json)?;
+        assert_eq!(e1, vec![MediaType::Banner, MediaType::Native]);
+        assert_eq!(serde_json::to_string(&e1)?, json);
+
+        Ok(())
+    }
+
+    #[test]
+    fn set() {
+        let mut set: MediaTypes = [MediaType::Native, MediaType::Banner]
+            .iter()
+            .copied()
+            .collect();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(MediaType::Banner));
+        assert!(!set.contains(MediaType::Video));
+        assert_eq!(format!("{:?}", set), "{Banner, Native}");
+
+        set.remove(MediaType::Banner);
+        set.insert(MediaType::Audio);
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            vec![MediaType::Audio, MediaType::Native]
+        );
+        assert_eq!(set.intersection(MediaTypes::all()), set);
+        assert!(set.intersection(MediaTypes::default()).is_empty());
+    }
+
+    #[test]
+    fn markup() {
+        let types = |v: &[MediaType]| v.iter().copied().collect::<MediaTypes>();
+        assert_eq!(
+            markup_types("\u{feff}<?xml version=\"1.0\"?>\n<!-- x --><VAST version=\"3.0\"/>"),
+            types(&[MediaType::Video, MediaType::Audio])
+        );
+        assert_eq!(
+            markup_types(r#"<VAST><MediaFile type="audio/mpeg"/></VAST>"#),
+            types(&[MediaType::Audio])
+        );
+        assert_eq!(
+            markup_types(r#"{"native":{"link":{"url":"https://acme.example"}}}"#),
+            types(&[MediaType::Native])
+        );
+        assert_eq!(
+            markup_types(r#"<div>{"link":1}</div>"#),
+            types(&[MediaType::Banner])
+        );
+        assert_eq!(markup_types("{}"), types(&[MediaType::Banner]));
+    }
+}
@@ -5,6 +5,12 @@
 /// click-through rate, etc. Each metric is identified by its type, reports the value of the metric,
 /// and optionally identifies the source or vendor measuring the value.
 #[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::recommended)
+)]
 pub struct Metric {
     /// string; required
     /// Type of metric being presented using exchange curated string names which should be
@@ -13,6 +19,7 @@ pub struct Metric {
 
     /// float; required
     /// Number representing the value of the metric. Probabilities must be in the range 0.0 – 1.0.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::probability))]
     pub value: f32,
 
     /// string; recommended
@@ -25,6 +32,7 @@ pub struct Metric {
     /// object
     /// Placeholder for exchange-specific extensions to OpenRTB.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::ext))]
     pub ext: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
@@ -0,0 +1,19 @@
+/// Serializes the given value as MessagePack.
+///
+/// Structs are written as maps keyed by field name. The compact array encoding used by
+/// `rmp_serde::to_vec` cannot be used with this crate: fields holding their default value are
+/// omitted, which shifts the position of every field after them.
+pub fn to_msgpack<T>(value: &T) -> Result<Vec<u8>, rmp_serde::encode::Error>
+where
+    T: serde::Serialize + ?Sized,
+{
+    rmp_serde::to_vec_named(value)
+}
+
+/// Deserializes an instance of type `T` from MessagePack written by [`to_msgpack`].
+pub fn from_msgpack<T>(v: &[u8]) -> Result<T, rmp_serde::decode::Error>
+where
+    T: serde::de::DeserializeOwned,
+{
+    rmp_serde::from_slice(v)
+}
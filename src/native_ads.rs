@@ -0,0 +1,1014 @@
+//! The request and response markup of the [`Dynamic Native Ads API 1.2`] carried by
+//! [`Native::request`] and [`Bid::adm`], and a check of one against the other.
+//!
+//! Only the objects and attributes needed to validate a response are modeled; others are ignored
+//! when decoding.
+//!
+//! ```
+//! use openrtb2::native_ads::{self, Issue};
+//!
+//! let native = openrtb2::Native {
+//!     request: r#"{"ver":"1.2","assets":[{"id":1,"required":1,"title":{"len":25}}]}"#.into(),
+//!     ..Default::default()
+//! };
+//! let bid = openrtb2::Bid {
+//!     adm: Some(r#"{"native":{"link":{"url":"https://acme.example"},"assets":[]}}"#.into()),
+//!     ..Default::default()
+//! };
+//! assert_eq!(native_ads::check(&native, &bid)?, vec![Issue::MissingAsset(1)]);
+//! # Ok::<(), native_ads::Error>(())
+//! ```
+//!
+//! [`Dynamic Native Ads API 1.2`]: https://www.iab.com/wp-content/uploads/2018/03/OpenRTB-Native-Ads-Specification-Final-1.2.pdf
+//! [`Native::request`]: ../struct.Native.html#structfield.request
+//! [`Bid::adm`]: ../struct.Bid.html#structfield.adm
+
+/// 4.1 Native Markup Request Object
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+pub struct Request {
+    /// string; default "1.2"
+    /// Version of the Native Markup version in use.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ver: Option<String>,
+
+    /// array of objects; required
+    /// An array of Asset Objects. Any objects bid response must comply with the array of elements
+    /// expressed in the bid request.
+    pub assets: Vec<AssetRequest>,
+
+    /// array of objects
+    /// Specifies what type of event tracking is supported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eventtrackers: Option<Vec<EventTrackerRequest>>,
+}
+
+/// 4.2 Asset Request Object
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+pub struct AssetRequest {
+    /// int; required
+    /// Unique asset ID, assigned by exchange. Typically a counter for the array.
+    pub id: i32,
+
+    /// int; default 0
+    /// Set to 1 if asset is required (exchange will not accept a bid without it).
+    #[serde(
+        default,
+        skip_serializing_if = "default_ext::DefaultExt::is_default",
+        with = "crate::serde::i32_as_bool"
+    )]
+    pub required: bool,
+
+    /// object
+    /// Title object for title assets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<TitleRequest>,
+
+    /// object
+    /// Image object for image assets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub img: Option<ImageRequest>,
+
+    /// object
+    /// Video object for video assets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub video: Option<VideoRequest>,
+
+    /// object
+    /// Data object for brand name, description, ratings, prices etc.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<DataRequest>,
+}
+
+/// 4.3 Title Request Object
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+pub struct TitleRequest {
+    /// int; required
+    /// Maximum length of the text in the title element.
+    pub len: i32,
+}
+
+/// 4.4 Image Request Object
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+pub struct ImageRequest {
+    /// int
+    /// Type ID of the image element supported by the publisher.
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub type_: Option<ImageAssetType>,
+
+    /// int
+    /// Width of the image in pixels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub w: Option<i32>,
+
+    /// int; recommended
+    /// The minimum requested width of the image in pixels. Either w or wmin should be transmitted.
+    /// If only w is included, it should be considered an exact requirement.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wmin: Option<i32>,
+
+    /// int
+    /// Height of the image in pixels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub h: Option<i32>,
+
+    /// int; recommended
+    /// The minimum requested height of the image in pixels. Either h or hmin should be
+    /// transmitted. If only h is included, it should be considered an exact requirement.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hmin: Option<i32>,
+
+    /// array of strings
+    /// Whitelist of content MIME types supported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mimes: Option<Vec<String>>,
+}
+
+/// 4.5 Video Request Object
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+pub struct VideoRequest {
+    /// array of strings; required
+    /// Content MIME types supported.
+    pub mimes: Vec<String>,
+
+    /// integer; required
+    /// Minimum video ad duration in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minduration: Option<i32>,
+
+    /// integer; required
+    /// Maximum video ad duration in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maxduration: Option<i32>,
+
+    /// array of integers; required
+    /// An array of video protocols the publisher can accept in the bid response. Refer to List
+    /// 5.8.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocols: Option<Vec<crate::Protocol>>,
+}
+
+/// 4.6 Data Request Object
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+pub struct DataRequest {
+    /// int; required
+    /// Type ID of the element supported by the publisher.
+    #[serde(rename = "type")]
+    pub type_: DataAssetType,
+
+    /// int
+    /// Maximum length of the text in the element’s response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub len: Option<i32>,
+}
+
+/// 4.7 Event Trackers Request Object
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+pub struct EventTrackerRequest {
+    /// integer; required
+    /// Type of event available for tracking.
+    pub event: EventType,
+
+    /// array of integers; required
+    /// Array of the types of tracking available for the given event.
+    pub methods: Vec<EventTrackingMethod>,
+}
+
+/// 5.1 Native Markup Response Object
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+pub struct Response {
+    /// string; default "1.2"
+    /// Version of the Native Markup version in use.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ver: Option<String>,
+
+    /// array of objects; recommended
+    /// List of native ad’s assets. Required if no assetsurl.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assets: Option<Vec<AssetResponse>>,
+
+    /// string
+    /// URL of an alternate source for the assets object.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assetsurl: Option<String>,
+
+    /// object; required
+    /// Destination Link. This is default link object for the ad.
+    pub link: LinkResponse,
+
+    /// array of strings
+    /// Array of impression tracking URLs, expected to return a 1x1 image or 204 response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub imptrackers: Option<Vec<String>>,
+
+    /// string
+    /// Optional JavaScript impression tracker.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jstracker: Option<String>,
+
+    /// array of objects
+    /// Array of tracking objects to run with the ad, in response to the declared supported methods
+    /// in the request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eventtrackers: Option<Vec<EventTrackerResponse>>,
+}
+
+/// 5.2 Asset Response Object
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+pub struct AssetResponse {
+    /// int
+    /// Unique asset ID, assigned by exchange, must match one of the asset IDs in request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<i32>,
+
+    /// int; default 0
+    /// Set to 1 if asset is required.
+    #[serde(
+        default,
+        skip_serializing_if = "default_ext::DefaultExt::is_default",
+        with = "crate::serde::i32_as_bool"
+    )]
+    pub required: bool,
+
+    /// object
+    /// Title object for title assets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<TitleResponse>,
+
+    /// object
+    /// Image object for image assets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub img: Option<ImageResponse>,
+
+    /// object
+    /// Video object for video assets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub video: Option<VideoResponse>,
+
+    /// object
+    /// Data object for ratings, prices etc.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<DataResponse>,
+
+    /// object
+    /// Link object for call to actions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<LinkResponse>,
+}
+
+/// 5.3 Title Response Object
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+pub struct TitleResponse {
+    /// string; required
+    /// The text associated with the text element.
+    pub text: String,
+
+    /// integer
+    /// The length of the title being provided.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub len: Option<i32>,
+}
+
+/// 5.4 Image Response Object
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+pub struct ImageResponse {
+    /// integer
+    /// Required for assetsurl or dcourl responses, not required for embedded asset responses. The
+    /// type of image element being submitted.
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub type_: Option<ImageAssetType>,
+
+    /// string; required
+    /// URL of the image asset.
+    pub url: String,
+
+    /// integer; recommended
+    /// Width of the image in pixels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub w: Option<i32>,
+
+    /// integer; recommended
+    /// Height of the image in pixels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub h: Option<i32>,
+}
+
+/// 5.5 Data Response Object
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+pub struct DataResponse {
+    /// integer
+    /// Required for assetsurl/dcourl responses, not required for embedded asset responses. The
+    /// type of data element being submitted.
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub type_: Option<DataAssetType>,
+
+    /// integer
+    /// Required for assetsurl/dcourl responses. The length of the data element being submitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub len: Option<i32>,
+
+    /// string; required
+    /// The formatted string of data to be displayed.
+    pub value: String,
+}
+
+/// 5.6 Video Response Object
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+pub struct VideoResponse {
+    /// string; required
+    /// VAST xml.
+    pub vasttag: String,
+}
+
+/// 5.7 Link Response Object
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+pub struct LinkResponse {
+    /// string; required
+    /// Landing URL of the clickable link.
+    pub url: String,
+
+    /// array of strings
+    /// List of third-party tracker URLs to be fired on click of the URL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clicktrackers: Option<Vec<String>>,
+
+    /// string
+    /// Fallback URL for deeplink.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<String>,
+}
+
+/// 5.8 Event Tracker Response Object
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+pub struct EventTrackerResponse {
+    /// integer; required
+    /// Type of event to track.
+    pub event: EventType,
+
+    /// integer; required
+    /// Type of tracking requested.
+    pub method: EventTrackingMethod,
+
+    /// text
+    /// The URL of the image or js. Required for image or js, optional for custom.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// 7.4 Image Asset Types
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ImageAssetType {
+    /// Icon image
+    #[default]
+    Icon,
+    /// Logo image for the brand/app; deprecated in 1.2.
+    Logo,
+    /// Large image preview for the ad
+    Main,
+    /// Exchange-specific image type (500 or greater)
+    ExchangeSpecific(i32),
+}
+
+impl ImageAssetType {
+    /// Returns the code of the type.
+    pub fn code(self) -> i32 {
+        match self {
+            Self::Icon => 1,
+            Self::Logo => 2,
+            Self::Main => 3,
+            Self::ExchangeSpecific(v) => v,
+        }
+    }
+
+    /// Returns the type with the given code, or `None` if the code is neither defined by the
+    /// specification nor in the exchange-specific range.
+    pub fn from_code(code: i32) -> Option<Self> {
+        let v = match code {
+            1 => Self::Icon,
+            2 => Self::Logo,
+            3 => Self::Main,
+            v if v >= 500 => Self::ExchangeSpecific(v),
+            _ => return None,
+        };
+        Some(v)
+    }
+}
+
+impl serde::Serialize for ImageAssetType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_i32(self.code())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ImageAssetType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let v = i32::deserialize(deserializer)?;
+        Self::from_code(v).ok_or_else(|| {
+            let s = format!("invalid value: {}, expected 1 to 3 or at least 500", v);
+            serde::de::Error::custom(s)
+        })
+    }
+}
+
+/// 7.3 Data Asset Types
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DataAssetType {
+    /// Sponsored By message where response should contain the brand name of the sponsor.
+    #[default]
+    Sponsored,
+    /// Descriptive text associated with the product or service being advertised.
+    Desc,
+    /// Rating of the product being offered to the user.
+    Rating,
+    /// Number of social ratings or “likes” of the product being offered to the user.
+    Likes,
+    /// Number downloads/installs of this product.
+    Downloads,
+    /// Price for product / app / in-app purchase.
+    Price,
+    /// Sale price that can be used together with price to indicate a discounted price compared to
+    /// a regular price.
+    SalePrice,
+    /// Phone number
+    Phone,
+    /// Address
+    Address,
+    /// Additional descriptive text associated with the product or service being advertised.
+    Desc2,
+    /// Display URL for the text ad.
+    DisplayUrl,
+    /// CTA description - descriptive text describing a ‘call to action’ button for the
+    /// destination URL.
+    CtaText,
+    /// Exchange-specific data type (500 or greater)
+    ExchangeSpecific(i32),
+}
+
+impl DataAssetType {
+    /// All codes defined by the specification, in code order.
+    pub const KNOWN: [Self; 12] = [
+        Self::Sponsored,
+        Self::Desc,
+        Self::Rating,
+        Self::Likes,
+        Self::Downloads,
+        Self::Price,
+        Self::SalePrice,
+        Self::Phone,
+        Self::Address,
+        Self::Desc2,
+        Self::DisplayUrl,
+        Self::CtaText,
+    ];
+
+    /// Returns the code of the type.
+    pub fn code(self) -> i32 {
+        match self {
+            Self::Sponsored => 1,
+            Self::Desc => 2,
+            Self::Rating => 3,
+            Self::Likes => 4,
+            Self::Downloads => 5,
+            Self::Price => 6,
+            Self::SalePrice => 7,
+            Self::Phone => 8,
+            Self::Address => 9,
+            Self::Desc2 => 10,
+            Self::DisplayUrl => 11,
+            Self::CtaText => 12,
+            Self::ExchangeSpecific(v) => v,
+        }
+    }
+
+    /// Returns the type with the given code, or `None` if the code is neither defined by the
+    /// specification nor in the exchange-specific range.
+    pub fn from_code(code: i32) -> Option<Self> {
+        if code >= 500 {
+            return Some(Self::ExchangeSpecific(code));
+        }
+        Self::KNOWN.iter().copied().find(|v| v.code() == code)
+    }
+}
+
+impl serde::Serialize for DataAssetType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_i32(self.code())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for DataAssetType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let v = i32::deserialize(deserializer)?;
+        Self::from_code(v).ok_or_else(|| {
+            let s = format!("invalid value: {}, expected 1 to 12 or at least 500", v);
+            serde::de::Error::custom(s)
+        })
+    }
+}
+
+/// 7.6 Event Types Table
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EventType {
+    /// Impression
+    #[default]
+    Impression,
+    /// Visible impression using MRC definition at 50% in view for 1 second
+    ViewableMrc50,
+    /// 100% in view for 1 second (ie GroupM standard)
+    ViewableMrc100,
+    /// Visible impression for video using MRC definition at 50% in view for 2 seconds
+    ViewableVideo50,
+    /// Exchange-specific event type (500 or greater)
+    ExchangeSpecific(i32),
+}
+
+impl EventType {
+    /// Returns the code of the event.
+    pub fn code(self) -> i32 {
+        match self {
+            Self::Impression => 1,
+            Self::ViewableMrc50 => 2,
+            Self::ViewableMrc100 => 3,
+            Self::ViewableVideo50 => 4,
+            Self::ExchangeSpecific(v) => v,
+        }
+    }
+
+    /// Returns the event with the given code, or `None` if the code is neither defined by the
+    /// specification nor in the exchange-specific range.
+    pub fn from_code(code: i32) -> Option<Self> {
+        let v = match code {
+            1 => Self::Impression,
+            2 => Self::ViewableMrc50,
+            3 => Self::ViewableMrc100,
+            4 => Self::ViewableVideo50,
+            v if v >= 500 => Self::ExchangeSpecific(v),
+            _ => return None,
+        };
+        Some(v)
+    }
+}
+
+impl serde::Serialize for EventType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_i32(self.code())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for EventType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let v = i32::deserialize(deserializer)?;
+        Self::from_code(v).ok_or_else(|| {
+            let s = format!("invalid value: {}, expected 1 to 4 or at least 500", v);
+            serde::de::Error::custom(s)
+        })
+    }
+}
+
+/// 7.7 Event Tracking Methods Table
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EventTrackingMethod {
+    /// Image-pixel tracking - URL provided will be inserted as a 1x1 pixel at the time of the
+    /// event.
+    #[default]
+    Img,
+    /// Javascript-based tracking - URL provided will be inserted as a js tag at the time of the
+    /// event.
+    Js,
+    /// Exchange-specific tracking method (500 or greater)
+    ExchangeSpecific(i32),
+}
+
+impl EventTrackingMethod {
+    /// Returns the code of the method.
+    pub fn code(self) -> i32 {
+        match self {
+            Self::Img => 1,
+            Self::Js => 2,
+            Self::ExchangeSpecific(v) => v,
+        }
+    }
+
+    /// Returns the method with the given code, or `None` if the code is neither defined by the
+    /// specification nor in the exchange-specific range.
+    pub fn from_code(code: i32) -> Option<Self> {
+        let v = match code {
+            1 => Self::Img,
+            2 => Self::Js,
+            v if v >= 500 => Self::ExchangeSpecific(v),
+            _ => return None,
+        };
+        Some(v)
+    }
+}
+
+impl serde::Serialize for EventTrackingMethod {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_i32(self.code())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for EventTrackingMethod {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let v = i32::deserialize(deserializer)?;
+        Self::from_code(v).ok_or_else(|| {
+            let s = format!("invalid value: {}, expected 1, 2 or at least 500", v);
+            serde::de::Error::custom(s)
+        })
+    }
+}
+
+/// A way in which a native response does not satisfy the request it answers.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Issue {
+    /// A required asset of the request is absent from the response.
+    MissingAsset(i32),
+    /// A response asset has an id that is not in the request.
+    UnknownAsset(i32),
+    /// A response asset is of a different kind (title, image, video or data) than the request
+    /// asset with the same id.
+    AssetKind(i32),
+    /// A title is longer than the requested maximum length in characters.
+    TitleTooLong {
+        /// Asset id.
+        id: i32,
+        /// Length of the title.
+        len: usize,
+        /// Requested maximum length.
+        max: i32,
+    },
+    /// An image does not have the requested exact or minimum size.
+    ImageSize {
+        /// Asset id.
+        id: i32,
+        /// Width of the image, if given.
+        w: Option<i32>,
+        /// Height of the image, if given.
+        h: Option<i32>,
+    },
+    /// An image declares a type other than the requested one.
+    ImageType(i32),
+    /// A data asset declares a type other than the requested one.
+    DataType(i32),
+    /// A data value is longer than the requested maximum length in characters.
+    DataTooLong {
+        /// Asset id.
+        id: i32,
+        /// Length of the value.
+        len: usize,
+        /// Requested maximum length.
+        max: i32,
+    },
+    /// An event tracker uses an event or method the request does not support.
+    UnsupportedTracker {
+        /// Tracked event.
+        event: EventType,
+        /// Tracking method.
+        method: EventTrackingMethod,
+    },
+}
+
+/// An error returned by [`check`].
+#[derive(Debug)]
+pub enum Error {
+    /// `Native.request` is not a native request.
+    Request(serde_json::Error),
+    /// `Bid.adm` is absent.
+    NoMarkup,
+    /// `Bid.adm` is not a native response.
+    Response(serde_json::Error),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Request(e) => write!(f, "invalid native request: {}", e),
+            Self::NoMarkup => f.write_str("bid has no adm"),
+            Self::Response(e) => write!(f, "invalid native response: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Request(e) | Self::Response(e) => Some(e),
+            Self::NoMarkup => None,
+        }
+    }
+}
+
+impl Request {
+    /// Decodes a request payload, either bare (1.1+) or enclosed in a `native` object (1.0).
+    pub fn decode(s: &str) -> serde_json::Result<Self> {
+        decode(s)
+    }
+}
+
+impl Response {
+    /// Decodes response markup, either bare (1.2) or enclosed in a `native` object (1.0, 1.1).
+    pub fn decode(s: &str) -> serde_json::Result<Self> {
+        decode(s)
+    }
+
+    /// Checks the response against the request it answers, returning every issue found.
+    ///
+    /// Assets served through `assetsurl` are not checked. Response assets are matched to request
+    /// assets by id, so assets without an id are skipped and do not satisfy a required asset.
+    /// Sizes, types and lengths the response omits are assumed to conform.
+    pub fn check(&self, req: &Request) -> Vec<Issue> {
+        let mut out = Vec::new();
+        let assets = self.assets.as_deref().unwrap_or_default();
+
+        if self.assetsurl.is_none() {
+            for asset in req.assets.iter().filter(|v| v.required) {
+                if !assets.iter().any(|v| v.id == Some(asset.id)) {
+                    out.push(Issue::MissingAsset(asset.id));
+                }
+            }
+        }
+
+        for res in assets {
+            // Assets are matched by id only; one without an id cannot be checked.
+            let id = match res.id {
+                Some(id) => id,
+                None => continue,
+            };
+            let asset = match req.assets.iter().find(|v| v.id == id) {
+                Some(v) => v,
+                None => {
+                    out.push(Issue::UnknownAsset(id));
+                    continue;
+                }
+            };
+            match (asset, res) {
+                (
+                    AssetRequest {
+                        title: Some(req), ..
+                    },
+                    AssetResponse {
+                        title: Some(res), ..
+                    },
+                ) => {
+                    let len = res.text.chars().count();
+                    if len > req.len.max(0) as usize {
+                        out.push(Issue::TitleTooLong {
+                            id,
+                            len,
+                            max: req.len,
+                        });
+                    }
+                }
+                (AssetRequest { img: Some(req), .. }, AssetResponse { img: Some(res), .. }) => {
+                    let fits = |size: Option<i32>, exact: Option<i32>, min: Option<i32>| match (
+                        size, min, exact,
+                    ) {
+                        (Some(size), Some(min), _) => size >= min,
+                        (Some(size), None, Some(exact)) => size == exact,
+                        _ => true,
+                    };
+                    if !fits(res.w, req.w, req.wmin) || !fits(res.h, req.h, req.hmin) {
+                        out.push(Issue::ImageSize {
+                            id,
+                            w: res.w,
+                            h: res.h,
+                        });
+                    }
+                    if req.type_.is_some() && res.type_.is_some() && req.type_ != res.type_ {
+                        out.push(Issue::ImageType(id));
+                    }
+                }
+                (AssetRequest { video: Some(_), .. }, AssetResponse { video: Some(_), .. }) => {}
+                (
+                    AssetRequest {
+                        data: Some(req), ..
+                    },
+                    AssetResponse {
+                        data: Some(res), ..
+                    },
+                ) => {
+                    if res.type_.is_some_and(|v| v != req.type_) {
+                        out.push(Issue::DataType(id));
+                    }
+                    let len = res.value.chars().count();
+                    if let Some(max) = req.len.filter(|max| len > (*max).max(0) as usize) {
+                        out.push(Issue::DataTooLong { id, len, max });
+                    }
+                }
+                // A link-only asset is not bound to a kind.
+                (
+                    _,
+                    AssetResponse {
+                        title: None,
+                        img: None,
+                        video: None,
+                        data: None,
+                        ..
+                    },
+                ) => {}
+                _ => out.push(Issue::AssetKind(id)),
+            }
+        }
+
+        let supported = req.eventtrackers.as_deref().unwrap_or_default();
+        for tracker in self.eventtrackers.iter().flatten() {
+            let ok = supported
+                .iter()
+                .any(|v| v.event == tracker.event && v.methods.contains(&tracker.method));
+            if !ok {
+                out.push(Issue::UnsupportedTracker {
+                    event: tracker.event,
+                    method: tracker.method,
+                });
+            }
+        }
+
+        out
+    }
+}
+
+/// Decodes the native request of an impression and the native response in a bid, and checks one
+/// against the other. See [`Response::check`].
+pub fn check(native: &crate::Native, bid: &crate::Bid) -> Result<Vec<Issue>, Error> {
+    let req = Request::decode(&native.request).map_err(Error::Request)?;
+    let adm = bid.adm.as_deref().ok_or(Error::NoMarkup)?;
+    let res = Response::decode(adm).map_err(Error::Response)?;
+    Ok(res.check(&req))
+}
+
+fn decode<T: serde::de::DeserializeOwned>(s: &str) -> serde_json::Result<T> {
+    let mut value: serde_json::Value = serde_json::from_str(s)?;
+    if let Some(native) = value.get_mut("native").filter(|v| v.is_object()) {
+        value = native.take();
+    }
+    serde_json::from_value(value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const REQUEST: &str = r#"{
+        "native": {
+            "ver": "1.2",
+            "assets": [
+                {"id": 1, "required": 1, "title": {"len": 10}},
+                {"id": 2, "required": 1, "img": {"type": 3, "wmin": 300, "hmin": 250}},
+                {"id": 3, "img": {"type": 1, "w": 50, "h": 50}},
+                {"id": 4, "data": {"type": 1, "len": 5}},
+                {"id": 5, "required": 1, "data": {"type": 2}}
+            ],
+            "eventtrackers": [{"event": 1, "methods": [1]}, {"event": 2, "methods": [1, 2]}]
+        }
+    }"#;
+
+    #[test]
+    fn json() -> serde_json::Result<()> {
+        let req = Request::decode(REQUEST)?;
+        assert_eq!(req.ver.as_deref(), Some("1.2"));
+        assert_eq!(req.assets.len(), 5);
+        assert!(req.assets[0].required);
+        assert_eq!(
+            req.assets[3].data.as_ref().unwrap().type_,
+            DataAssetType::Sponsored
+        );
+        assert_eq!(
+            serde_json::to_string(&req.assets[1])?,
+            r#"{"id":2,"required":1,"img":{"type":3,"wmin":300,"hmin":250}}"#
+        );
+
+        let tracker: EventTrackerResponse =
+            serde_json::from_str(r#"{"event":555,"method":501,"url":"https://x.example/t"}"#)?;
+        assert_eq!(tracker.event, EventType::ExchangeSpecific(555));
+        assert_eq!(tracker.method, EventTrackingMethod::ExchangeSpecific(501));
+        assert_eq!(
+            serde_json::to_string(&tracker)?,
+            r#"{"event":555,"method":501,"url":"https://x.example/t"}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<Vec<DataAssetType>>("[12,500]")?,
+            vec![DataAssetType::CtaText, DataAssetType::ExchangeSpecific(500)]
+        );
+        assert_eq!(
+            serde_json::from_str::<ImageAssetType>("600")?,
+            ImageAssetType::ExchangeSpecific(600)
+        );
+        assert!(serde_json::from_str::<ImageAssetType>("4").is_err());
+        assert!(serde_json::from_str::<DataAssetType>("0").is_err());
+        assert!(serde_json::from_str::<EventType>("499").is_err());
+        assert!(serde_json::from_str::<EventTrackingMethod>("3").is_err());
+
+        let res = Response::decode(r#"{"link":{"url":"https://acme.example"}}"#)?;
+        assert_eq!(res.link.url, "https://acme.example");
+        assert!(Response::decode(r#"{"native":{}}"#).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn check() -> serde_json::Result<()> {
+        let req = Request::decode(REQUEST)?;
+
+        let res = Response::decode(
+            r#"{
+                "link": {"url": "https://acme.example"},
+                "assets": [
+                    {"id": 1, "title": {"text": "Acme Corp."}},
+                    {"id": 2, "img": {"url": "https://acme.example/1.png", "w": 600, "h": 500}},
+                    {"id": 5, "data": {"value": "Everything you need."}},
+                    {"link": {"url": "https://acme.example/cta"}}
+                ],
+                "eventtrackers": [{"event": 2, "method": 2, "url": "https://acme.example/v.js"}]
+            }"#,
+        )?;
+        assert!(res.check(&req).is_empty());
+
+        let res = Response::decode(
+            r#"{
+                "link": {"url": "https://acme.example"},
+                "assets": [
+                    {"id": 1, "title": {"text": "Acme Corporation"}},
+                    {"id": 3, "img": {"type": 3, "url": "https://acme.example/1.png", "w": 60}},
+                    {"id": 4, "data": {"type": 2, "value": "Acme Corp."}},
+                    {"id": 5, "img": {"url": "https://acme.example/2.png"}},
+                    {"id": 6, "title": {"text": "Acme"}}
+                ],
+                "eventtrackers": [{"event": 1, "method": 2, "url": "https://acme.example/i.js"}]
+            }"#,
+        )?;
+        assert_eq!(
+            res.check(&req),
+            vec![
+                Issue::MissingAsset(2),
+                Issue::TitleTooLong {
+                    id: 1,
+                    len: 16,
+                    max: 10
+                },
+                Issue::ImageSize {
+                    id: 3,
+                    w: Some(60),
+                    h: None
+                },
+                Issue::ImageType(3),
+                Issue::DataType(4),
+                Issue::DataTooLong {
+                    id: 4,
+                    len: 10,
+                    max: 5
+                },
+                Issue::AssetKind(5),
+                Issue::UnknownAsset(6),
+                Issue::UnsupportedTracker {
+                    event: EventType::Impression,
+                    method: EventTrackingMethod::Js
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_bid() {
+        let native = crate::Native {
+            request: REQUEST.into(),
+            ..Default::default()
+        };
+        let mut bid = crate::Bid::default();
+        assert!(matches!(super::check(&native, &bid), Err(Error::NoMarkup)));
+        bid.adm = Some("<html></html>".into());
+        assert!(matches!(
+            super::check(&native, &bid),
+            Err(Error::Response(_))
+        ));
+        let native = crate::Native::default();
+        assert!(matches!(
+            super::check(&native, &bid),
+            Err(Error::Request(_))
+        ));
+    }
+}
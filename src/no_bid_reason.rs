@@ -1,14 +1,11 @@
-use serde_repr::{Deserialize_repr, Serialize_repr};
-
 /// 5.24 No-Bid Reason Codes
 ///
 /// The following table lists the options for a bidder to signal the exchange as to why it did not
 /// offer a bid for the impression.
-#[derive(Serialize_repr, Deserialize_repr, Debug, PartialEq, Eq, Clone, Copy)]
-#[repr(i32)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum NoBidReason {
     /// Unknown Error
-    UnknownError = 0,
+    UnknownError,
     /// Technical Error
     TechnicalError,
     /// Invalid Request
@@ -29,6 +26,128 @@ pub enum NoBidReason {
     DailyReaderCap,
     /// Daily Domain Cap Met
     DailyDomainCap,
+    /// Bidder specific, 500 or greater (should be communicated to the exchange a priori)
+    BidderSpecific(i32),
+}
+
+impl NoBidReason {
+    /// All codes defined by the specification, in code order.
+    pub const KNOWN: [Self; 11] = [
+        Self::UnknownError,
+        Self::TechnicalError,
+        Self::InvalidRequest,
+        Self::KnownWebSpider,
+        Self::SuspectedNonHumanTraffic,
+        Self::CloudDataCenterProxyIp,
+        Self::UnsupportedDevice,
+        Self::BlockedPublisher,
+        Self::UnmatchedUser,
+        Self::DailyReaderCap,
+        Self::DailyDomainCap,
+    ];
+
+    /// Returns the code of the reason.
+    pub fn code(self) -> i32 {
+        match self {
+            Self::UnknownError => 0,
+            Self::TechnicalError => 1,
+            Self::InvalidRequest => 2,
+            Self::KnownWebSpider => 3,
+            Self::SuspectedNonHumanTraffic => 4,
+            Self::CloudDataCenterProxyIp => 5,
+            Self::UnsupportedDevice => 6,
+            Self::BlockedPublisher => 7,
+            Self::UnmatchedUser => 8,
+            Self::DailyReaderCap => 9,
+            Self::DailyDomainCap => 10,
+            Self::BidderSpecific(v) => v,
+        }
+    }
+
+    /// Returns the reason with the given code, or `None` if the code is neither defined by the
+    /// specification nor in the bidder-specific range.
+    pub fn from_code(code: i32) -> Option<Self> {
+        if code >= 500 {
+            return Some(Self::BidderSpecific(code));
+        }
+        Self::KNOWN.iter().copied().find(|v| v.code() == code)
+    }
+}
+
+impl std::fmt::Display for NoBidReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::UnknownError => "Unknown Error",
+            Self::TechnicalError => "Technical Error",
+            Self::InvalidRequest => "Invalid Request",
+            Self::KnownWebSpider => "Known Web Spider",
+            Self::SuspectedNonHumanTraffic => "Suspected Non-Human Traffic",
+            Self::CloudDataCenterProxyIp => "Cloud, Data center, or Proxy IP",
+            Self::UnsupportedDevice => "Unsupported Device",
+            Self::BlockedPublisher => "Blocked Publisher or Site",
+            Self::UnmatchedUser => "Unmatched User",
+            Self::DailyReaderCap => "Daily Reader Cap Met",
+            Self::DailyDomainCap => "Daily Domain Cap Met",
+            Self::BidderSpecific(v) => return write!(f, "Bidder-specific no-bid reason {}", v),
+        };
+        f.write_str(s)
+    }
+}
+
+impl serde::Serialize for NoBidReason {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_i32(self.code())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for NoBidReason {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let v = i32::deserialize(deserializer)?;
+        Self::from_code(v).ok_or_else(|| {
+            let s = format!("invalid value: {}, expected 0 to 10 or at least 500", v);
+            serde::de::Error::custom(s)
+        })
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for NoBidReason {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let v = match Self::KNOWN.get(u.int_in_range(0..=Self::KNOWN.len())?) {
+            Some(v) => *v,
+            None => Self::BidderSpecific(u.int_in_range(500..=i32::MAX)?),
+        };
+        Ok(v)
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for NoBidReason {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "NoBidReason".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        let mut variants: Vec<_> = Self::KNOWN
+            .iter()
+            .map(|v| serde_json::json!({ "const": v.code(), "description": v.to_string() }))
+            .collect();
+        variants.push(
+            serde_json::json!({ "minimum": 500, "description": "Bidder-specific no-bid reason" }),
+        );
+        schemars::json_schema!({
+            "description": "Options for a bidder to signal the exchange as to why it did not offer a bid for \
+                the impression. Codes of 500 and greater are bidder specific.",
+            "type": "integer",
+            "oneOf": variants
+        })
+    }
 }
 
 #[cfg(test)]
@@ -38,12 +157,18 @@ mod test {
     #[test]
     fn json() -> serde_json::Result<()> {
         assert!(serde_json::from_str::<NoBidReason>("-1").is_err());
+        assert!(serde_json::from_str::<NoBidReason>("499").is_err());
 
-        let json = "[0,1]";
+        let json = "[0,1,10,500]";
         let e1: Vec<NoBidReason> = serde_json::from_str(json)?;
         assert_eq!(
             e1,
-            vec![NoBidReason::UnknownError, NoBidReason::TechnicalError]
+            vec![
+                NoBidReason::UnknownError,
+                NoBidReason::TechnicalError,
+                NoBidReason::DailyDomainCap,
+                NoBidReason::BidderSpecific(500),
+            ]
         );
         assert_eq!(serde_json::to_string(&e1)?, json);
 
@@ -0,0 +1,328 @@
+/// An absolute `http` or `https` URL that may contain substitution macros (Section 4.4), such as
+/// the notice URLs of a [`Bid`], which are checked with [`Bid::nurl_checked`] and its siblings.
+///
+/// Macros like `${AUCTION_PRICE}` are not valid URL characters, so they are skipped when the URL
+/// is validated and kept as is.
+///
+/// ```
+/// # use openrtb2::{NoticeUrl, SubstitutionMacro};
+/// let url: NoticeUrl = "https://dsp.example/win?p=${AUCTION_PRICE}&c=${AUCTION_CURRENCY}".parse()?;
+/// assert!(url.is_secure());
+/// assert_eq!(url.macros().collect::<Vec<_>>(), vec!["AUCTION_PRICE", "AUCTION_CURRENCY"]);
+/// assert_eq!(
+///     url.substitution_macros().next(),
+///     Some(SubstitutionMacro::AuctionPrice)
+/// );
+///
+/// assert!("http: //adserver.example/win".parse::<NoticeUrl>().is_err());
+/// # Ok::<(), openrtb2::NoticeUrlError>(())
+/// ```
+///
+/// [`Bid`]: ./struct.Bid.html
+/// [`Bid::nurl_checked`]: ./struct.Bid.html#method.nurl_checked
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct NoticeUrl(String);
+
+/// An error returned when parsing a [`NoticeUrl`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum NoticeUrlError {
+    /// The URL does not start with `http://` or `https://`.
+    Scheme,
+    /// The URL has no host.
+    Host,
+    /// The URL contains a character that must be percent-encoded, at the given byte offset.
+    Char(char, usize),
+    /// A macro starting at the given byte offset is not closed by `}`.
+    UnterminatedMacro(usize),
+}
+
+impl NoticeUrl {
+    /// Parses and validates a URL.
+    pub fn parse(s: &str) -> Result<Self, NoticeUrlError> {
+        let scheme = ["https://", "http://"]
+            .iter()
+            .find(|v| s.get(..v.len()).is_some_and(|p| p.eq_ignore_ascii_case(v)))
+            .ok_or(NoticeUrlError::Scheme)?;
+
+        let start = scheme.len();
+        let end = s[start..]
+            .find(['/', '?', '#'])
+            .map_or(s.len(), |i| start + i);
+        // Userinfo is allowed but not validated any further than the path.
+        let host_start = s[start..end].rfind('@').map_or(start, |i| start + i + 1);
+        scan(s, start, host_start, is_path_char)?;
+        let host = &s[host_start..end];
+        if host.is_empty() || host.starts_with(':') {
+            return Err(NoticeUrlError::Host);
+        }
+        if host.starts_with('[') {
+            // An IP literal, optionally followed by a port.
+            let close = host.find(']').ok_or(NoticeUrlError::Host)?;
+            scan(s, host_start + 1, host_start + close, |c| {
+                c.is_ascii_hexdigit() || c == ':' || c == '.'
+            })?;
+            scan(s, host_start + close + 1, end, |c| {
+                c.is_ascii_digit() || c == ':'
+            })?;
+        } else {
+            scan(s, host_start, end, |c| {
+                c.is_ascii_alphanumeric() || "-._~:".contains(c)
+            })?;
+        }
+
+        scan(s, end, s.len(), is_path_char)?;
+        Ok(Self(s.to_owned()))
+    }
+
+    /// Returns the URL as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the URL as a string.
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
+    /// Returns `true` if the URL uses `https`, as required for secure impressions
+    /// (`Imp.secure`).
+    pub fn is_secure(&self) -> bool {
+        self.0[..6].eq_ignore_ascii_case("https:")
+    }
+
+    /// Iterates over the names of the macros in the URL, e.g. `AUCTION_PRICE` or
+    /// `AUCTION_PRICE:B64`.
+    pub fn macros(&self) -> impl Iterator<Item = &str> {
+        self.0
+            .split("${")
+            .skip(1)
+            .filter_map(|v| v.split('}').next())
+    }
+
+    /// Iterates over the substitution macros of Section 4.4 in the URL. Encoded macros such as
+    /// `${AUCTION_PRICE:B64}` are not included.
+    pub fn substitution_macros(&self) -> impl Iterator<Item = crate::SubstitutionMacro> + '_ {
+        self.macros()
+            .filter_map(crate::SubstitutionMacro::from_name)
+    }
+
+    /// Returns the URL with its macros replaced by `values`.
+    pub fn expand(&self, values: &crate::SubstitutionValues) -> String {
+        values.expand(&self.0)
+    }
+}
+
+/// Checks `s[from..to]`, skipping macros and percent-encoded octets.
+fn scan(
+    s: &str,
+    from: usize,
+    to: usize,
+    allowed: impl Fn(char) -> bool,
+) -> Result<(), NoticeUrlError> {
+    let mut chars = s[..to].char_indices().skip_while(|(i, _)| *i < from);
+    while let Some((i, c)) = chars.next() {
+        if s[i..to].starts_with("${") {
+            let len = s[i..to]
+                .find('}')
+                .ok_or(NoticeUrlError::UnterminatedMacro(i))?;
+            let name = &s[i + 2..i + len];
+            if let Some((j, c)) = name
+                .char_indices()
+                .find(|(_, c)| !c.is_ascii_alphanumeric() && *c != '_' && *c != ':')
+            {
+                return Err(NoticeUrlError::Char(c, i + 2 + j));
+            }
+            chars.nth(len - 1);
+        } else if c == '%' {
+            let hex = s.get(i + 1..i + 3).filter(|_| i + 3 <= to);
+            if !hex.is_some_and(|v| v.chars().all(|c| c.is_ascii_hexdigit())) {
+                return Err(NoticeUrlError::Char(c, i));
+            }
+            chars.nth(1);
+        } else if !allowed(c) {
+            return Err(NoticeUrlError::Char(c, i));
+        }
+    }
+    Ok(())
+}
+
+fn is_path_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "-._~!$&'()*+,;=:@/?#".contains(c)
+}
+
+impl std::str::FromStr for NoticeUrl {
+    type Err = NoticeUrlError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl std::convert::TryFrom<String> for NoticeUrl {
+    type Error = NoticeUrlError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::parse(&s)?;
+        Ok(Self(s))
+    }
+}
+
+impl From<NoticeUrl> for String {
+    fn from(v: NoticeUrl) -> Self {
+        v.0
+    }
+}
+
+impl std::ops::Deref for NoticeUrl {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for NoticeUrl {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for NoticeUrl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::fmt::Display for NoticeUrlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Scheme => f.write_str("URL must start with http:// or https://"),
+            Self::Host => f.write_str("URL has no host"),
+            Self::Char(c, i) => write!(f, "invalid character {:?} in URL at {}", c, i),
+            Self::UnterminatedMacro(i) => write!(f, "unterminated macro in URL at {}", i),
+        }
+    }
+}
+
+impl std::error::Error for NoticeUrlError {}
+
+impl serde::Serialize for NoticeUrl {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for NoticeUrl {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        std::convert::TryFrom::try_from(s).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for NoticeUrl {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let scheme = if u.arbitrary()? { "https" } else { "http" };
+        let mut host = String::new();
+        for _ in 0..u.int_in_range(1..=12)? {
+            host.push(*u.choose(b"abcdefghijklmnopqrstuvwxyz0123456789-.")? as char);
+        }
+        let query = match u.int_in_range(0..=2)? {
+            0 => "",
+            1 => "?p=${AUCTION_PRICE}",
+            _ => "?id=${AUCTION_ID}&l=${AUCTION_LOSS}",
+        };
+        Ok(Self(format!("{}://{}/{}", scheme, host, query)))
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for NoticeUrl {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "NoticeUrl".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "Absolute http or https URL that may contain substitution macros.",
+            "type": "string",
+            "pattern": "^[Hh][Tt][Tt][Pp][Ss]?://[^/?#]+"
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let ok = |s: &str| NoticeUrl::parse(s).map(|v| v.into_string());
+        for s in [
+            "http://adserver.example/winnotice?impid=102",
+            "HTTPS://a.example",
+            "https://user:pw@a.example:8443/p%20q?x=${AUCTION_PRICE:B64}#f",
+            "https://[::1]:443/",
+            "https://${AUCTION_SEAT_ID}.dsp.example/win",
+        ]
+        .iter()
+        {
+            assert_eq!(ok(s).as_deref(), Ok(*s));
+        }
+
+        let err = |s: &str| NoticeUrl::parse(s).unwrap_err();
+        assert_eq!(err("//a.example"), NoticeUrlError::Scheme);
+        assert_eq!(err("ftp://a.example"), NoticeUrlError::Scheme);
+        assert_eq!(err("https:/"), NoticeUrlError::Scheme);
+        assert_eq!(err("https:///path"), NoticeUrlError::Host);
+        assert_eq!(err("https://:80/"), NoticeUrlError::Host);
+        assert_eq!(err("http: //a.example"), NoticeUrlError::Scheme);
+        assert_eq!(err("http://a b.example"), NoticeUrlError::Char(' ', 8));
+        assert_eq!(err("http://a.example/<"), NoticeUrlError::Char('<', 17));
+        assert_eq!(err("http://a.example/%zz"), NoticeUrlError::Char('%', 17));
+        assert_eq!(err("http://a.example/%2"), NoticeUrlError::Char('%', 17));
+        assert_eq!(err("http://a.example/{x}"), NoticeUrlError::Char('{', 17));
+        assert_eq!(
+            err("http://a.example/${AUCTION_PRICE"),
+            NoticeUrlError::UnterminatedMacro(17)
+        );
+        assert_eq!(
+            err("http://a.example/${AUCTION PRICE}"),
+            NoticeUrlError::Char(' ', 26)
+        );
+    }
+
+    #[test]
+    fn macros() -> serde_json::Result<()> {
+        let url: NoticeUrl =
+            serde_json::from_str(r#""http://a.example/?p=${AUCTION_PRICE}&x=${OTHER}""#)?;
+        assert!(!url.is_secure());
+        assert_eq!(
+            url.macros().collect::<Vec<_>>(),
+            vec!["AUCTION_PRICE", "OTHER"]
+        );
+        assert_eq!(
+            url.substitution_macros().collect::<Vec<_>>(),
+            vec![crate::SubstitutionMacro::AuctionPrice]
+        );
+
+        let values = crate::SubstitutionValues {
+            price: Some(2.5),
+            ..Default::default()
+        };
+        assert_eq!(url.expand(&values), "http://a.example/?p=2.5&x=${OTHER}");
+        assert_eq!(
+            serde_json::to_string(&url)?,
+            r#""http://a.example/?p=${AUCTION_PRICE}&x=${OTHER}""#
+        );
+        assert!(serde_json::from_str::<NoticeUrl>(r#""a.example""#).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,340 @@
+use serde_json::Value;
+use serde_path_to_error::{Path, Segment};
+
+/// Deserializes a [`BidRequest`] from a string of JSON text, reporting failures as a
+/// [`ParseError`] that locates the offending field.
+///
+/// ```
+/// # use openrtb2::ParseError;
+/// let json = r#"{"id":"1","imp":[{"id":"1","banner":{"topframe":3}}]}"#;
+/// match openrtb2::parse_bid_request(json) {
+///     Err(ParseError::InvalidBoolFlag(cx)) => {
+///         assert_eq!(cx.pointer, "/imp/0/banner/topframe");
+///         assert_eq!(cx.value, Some(3.into()));
+///         assert_eq!(cx.section, "3.2.6");
+///     }
+///     _ => unreachable!(),
+/// }
+/// ```
+///
+/// [`BidRequest`]: ./struct.BidRequest.html
+pub fn parse_bid_request(s: &str) -> Result<crate::BidRequest, ParseError> {
+    parse(s, "3.2.1")
+}
+
+/// Deserializes a [`BidResponse`] from a string of JSON text, reporting failures as a
+/// [`ParseError`] that locates the offending field.
+///
+/// [`BidResponse`]: ./struct.BidResponse.html
+pub fn parse_bid_response(s: &str) -> Result<crate::BidResponse, ParseError> {
+    parse(s, "4.2.1")
+}
+
+/// An error returned by [`parse_bid_request`] and [`parse_bid_response`].
+#[derive(Debug)]
+pub enum ParseError {
+    /// The input is not valid JSON.
+    Syntax(serde_json::Error),
+    /// A code that is not defined by the referenced list (e.g., `"pos": 99`).
+    UnknownEnumCode(ErrorContext),
+    /// A 0/1 flag holding any other integer (e.g., `"topframe": 3`).
+    InvalidBoolFlag(ErrorContext),
+    /// A required field is absent.
+    MissingField {
+        /// Name of the missing field.
+        field: String,
+        context: ErrorContext,
+    },
+    /// A value of the wrong JSON type (e.g., a string where an integer is expected).
+    TypeMismatch(ErrorContext),
+    /// Any other invalid content.
+    Other(ErrorContext),
+}
+
+/// Where and why a [`ParseError`] occurred.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ErrorContext {
+    /// JSON pointer (RFC 6901) to the offending value, e.g. `/imp/2/banner/topframe`.
+    pub pointer: String,
+
+    /// The offending value as received; `None` if it is absent.
+    pub value: Option<Value>,
+
+    /// The specification section of the object holding the value, e.g. `3.2.6`.
+    pub section: &'static str,
+
+    /// The underlying deserializer message.
+    pub message: String,
+}
+
+impl ParseError {
+    /// Returns the location of the error, unless the input is not valid JSON.
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            Self::Syntax(_) => None,
+            Self::UnknownEnumCode(cx)
+            | Self::InvalidBoolFlag(cx)
+            | Self::MissingField { context: cx, .. }
+            | Self::TypeMismatch(cx)
+            | Self::Other(cx) => Some(cx),
+        }
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Syntax(e) => write!(f, "{}", e),
+            Self::UnknownEnumCode(cx)
+            | Self::InvalidBoolFlag(cx)
+            | Self::MissingField { context: cx, .. }
+            | Self::TypeMismatch(cx)
+            | Self::Other(cx) => {
+                write!(f, "{} (section {}): {}", cx.pointer, cx.section, cx.message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Syntax(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+fn parse<T>(s: &str, root: &'static str) -> Result<T, ParseError>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let mut de = serde_json::Deserializer::from_str(s);
+    let err = match serde_path_to_error::deserialize::<_, T>(&mut de) {
+        Ok(v) => return de.end().map(|_| v).map_err(ParseError::Syntax),
+        Err(err) => err,
+    };
+    if err.inner().is_syntax() || err.inner().is_eof() || err.inner().is_io() {
+        return Err(ParseError::Syntax(err.into_inner()));
+    }
+
+    let mut message = err.inner().to_string();
+    if let Some(i) = message.rfind(" at line ") {
+        message.truncate(i);
+    }
+
+    let path = err.path();
+    if let Some(field) = message
+        .strip_prefix("missing field `")
+        .and_then(|s| s.strip_suffix('`'))
+    {
+        let context = ErrorContext {
+            pointer: format!("{}/{}", pointer(path), escape(field)),
+            value: None,
+            section: section(root, path.iter()),
+            message: message.clone(),
+        };
+        return Err(ParseError::MissingField {
+            field: field.to_owned(),
+            context,
+        });
+    }
+
+    // The input is known to be valid JSON at this point, so the offending value can be recovered by
+    // parsing it again; this keeps the successful path free of an intermediate `Value`.
+    let segments: Vec<_> = path.iter().collect();
+    let value = serde_json::from_str::<Value>(s)
+        .ok()
+        .and_then(|v| lookup(v, &segments));
+    let cx = ErrorContext {
+        pointer: pointer(path),
+        value,
+        section: section(
+            root,
+            segments[..segments.len().saturating_sub(1)].iter().copied(),
+        ),
+        message,
+    };
+    Err(if cx.message.contains("expected 0 or 1") {
+        ParseError::InvalidBoolFlag(cx)
+    } else if is_unknown_code(&cx.message) {
+        ParseError::UnknownEnumCode(cx)
+    } else if cx.message.starts_with("invalid type") || cx.message.starts_with("invalid length") {
+        ParseError::TypeMismatch(cx)
+    } else {
+        ParseError::Other(cx)
+    })
+}
+
+/// Returns `true` if `message` reports a code rejected by an enum: these name the accepted codes
+/// after `expected`, while the deserializers of primitives name a type, as in
+/// ``invalid value: integer `4294967296`, expected i32``.
+fn is_unknown_code(message: &str) -> bool {
+    if message.starts_with("unknown variant") {
+        return true;
+    }
+    match message
+        .strip_prefix("invalid value: ")
+        .and_then(|s| s.split_once(", expected "))
+    {
+        Some((_, expected)) => {
+            expected.starts_with("one of: ")
+                || expected.starts_with(|c: char| c.is_ascii_digit() || c == '-')
+        }
+        None => false,
+    }
+}
+
+fn lookup(value: Value, segments: &[&Segment]) -> Option<Value> {
+    segments.iter().try_fold(value, |mut v, seg| match seg {
+        Segment::Seq { index } => v.get_mut(*index).map(Value::take),
+        Segment::Map { key } | Segment::Enum { variant: key } => {
+            v.get_mut(key.as_str()).map(Value::take)
+        }
+        Segment::Unknown => None,
+    })
+}
+
+fn pointer(path: &Path) -> String {
+    let mut s = String::new();
+    for seg in path {
+        match seg {
+            Segment::Seq { index } => s.push_str(&format!("/{}", index)),
+            Segment::Map { key } | Segment::Enum { variant: key } => {
+                s.push('/');
+                s.push_str(&escape(key));
+            }
+            Segment::Unknown => break,
+        }
+    }
+    s
+}
+
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Resolves the specification section of the object reached by following `segments`.
+fn section<'a>(root: &'static str, segments: impl Iterator<Item = &'a Segment>) -> &'static str {
+    let mut section = root;
+    for seg in segments {
+        let key = match seg {
+            Segment::Map { key } | Segment::Enum { variant: key } => key.as_str(),
+            Segment::Seq { .. } => continue,
+            Segment::Unknown => break,
+        };
+        section = match key {
+            // Contents of extension objects are not described by the specification.
+            "ext" => break,
+            "source" => "3.2.2",
+            "regs" => "3.2.3",
+            "imp" => "3.2.4",
+            "metric" => "3.2.5",
+            "banner" | "companionad" => "3.2.6",
+            "video" => "3.2.7",
+            "audio" => "3.2.8",
+            "native" => "3.2.9",
+            "format" => "3.2.10",
+            "pmp" => "3.2.11",
+            "deals" => "3.2.12",
+            "site" => "3.2.13",
+            "app" => "3.2.14",
+            "publisher" => "3.2.15",
+            "content" => "3.2.16",
+            "producer" => "3.2.17",
+            "device" => "3.2.18",
+            "geo" => "3.2.19",
+            "user" => "3.2.20",
+            "data" => "3.2.21",
+            "segment" => "3.2.22",
+            "seatbid" => "4.2.2",
+            "bid" => "4.2.3",
+            _ => section,
+        };
+    }
+    section
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bid_request() {
+        let json = r#"{"id":"1","imp":[{"id":"1"},{"id":"2","video":{"mimes":[],"pos":99}}]}"#;
+        match parse_bid_request(json) {
+            Err(ParseError::UnknownEnumCode(cx)) => {
+                assert_eq!(cx.pointer, "/imp/1/video/pos");
+                assert_eq!(cx.value, Some(99.into()));
+                assert_eq!(cx.section, "3.2.7");
+            }
+            r => panic!("{:?}", r),
+        }
+
+        let json = r#"{"id":"1","imp":[{"id":"1"}],"user":{"data":[{"segment":[{"id":1}]}]}}"#;
+        match parse_bid_request(json) {
+            Err(ParseError::TypeMismatch(cx)) => {
+                assert_eq!(cx.pointer, "/user/data/0/segment/0/id");
+                assert_eq!(cx.value, Some(1.into()));
+                assert_eq!(cx.section, "3.2.22");
+            }
+            r => panic!("{:?}", r),
+        }
+
+        match parse_bid_request(r#"{"id":"1","imp":[{}]}"#) {
+            Err(ParseError::MissingField { field, context }) => {
+                assert_eq!(field, "id");
+                assert_eq!(context.pointer, "/imp/0/id");
+                assert_eq!(context.value, None);
+                assert_eq!(context.section, "3.2.4");
+            }
+            r => panic!("{:?}", r),
+        }
+
+        let json = r#"{"id":"1","imp":[{"id":"1","ext":{"imp":{"secure":2}}}],"test":2}"#;
+        let err = parse_bid_request(json).unwrap_err();
+        assert!(matches!(err, ParseError::InvalidBoolFlag(_)));
+        assert_eq!(
+            err.to_string(),
+            "/test (section 3.2.1): invalid value: 2, expected 0 or 1"
+        );
+
+        // An integer out of range is not an enum code.
+        let json = r#"{"id":"1","imp":[{"id":"1","banner":{"w":-1,"h":4294967296}}]}"#;
+        match parse_bid_request(json) {
+            Err(ParseError::Other(cx)) => {
+                assert_eq!(cx.pointer, "/imp/0/banner/h");
+                assert_eq!(cx.section, "3.2.6");
+            }
+            r => panic!("{:?}", r),
+        }
+        let json = r#"{"id":"1","imp":[{"id":"1","banner":{"w":-1}}],"at":4}"#;
+        assert!(matches!(
+            parse_bid_request(json),
+            Err(ParseError::UnknownEnumCode(_))
+        ));
+
+        assert!(matches!(
+            parse_bid_request(r#"{"id":"1","imp":[]"#),
+            Err(ParseError::Syntax(_))
+        ));
+        assert!(matches!(
+            parse_bid_request(r#"{"id":"1","imp":[]} {}"#),
+            Err(ParseError::Syntax(_))
+        ));
+        assert!(parse_bid_request(r#"{"id":"1","imp":[]}"#).is_ok());
+    }
+
+    #[test]
+    fn bid_response() {
+        let json = r#"{"id":"1","seatbid":[{"bid":[{"id":"1","impid":"1","price":"1"}]}]}"#;
+        match parse_bid_response(json) {
+            Err(ParseError::TypeMismatch(cx)) => {
+                assert_eq!(cx.pointer, "/seatbid/0/bid/0/price");
+                assert_eq!(cx.value, Some("1".into()));
+                assert_eq!(cx.section, "4.2.3");
+            }
+            r => panic!("{:?}", r),
+        }
+    }
+}
@@ -4,6 +4,12 @@
 /// that may pertain to this impression. The actual deals are represented as a collection of Deal
 /// objects. Refer to Section 7.3 for more details.
 #[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::recommended)
+)]
 pub struct Pmp {
     /// integer; default 0
     /// Indicator of auction eligibility to seats named in the Direct Deals object, where 0 = all
@@ -13,6 +19,10 @@ pub struct Pmp {
         skip_serializing_if = "default_ext::DefaultExt::is_default",
         with = "crate::serde::i32_as_bool"
     )]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(schema_with = "crate::serde::i32_as_bool::schema")
+    )]
     pub private_auction: bool,
 
     /// object array
@@ -24,6 +34,7 @@ pub struct Pmp {
     /// object
     /// Placeholder for exchange-specific extensions to OpenRTB.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::ext))]
     pub ext: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
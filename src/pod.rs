@@ -0,0 +1,433 @@
+/// A commercial break (ad pod): the video impressions of a request that are played back to back,
+/// as returned by [`BidRequest::pods`].
+///
+/// The OpenRTB 2.6 attributes `podid`, `poddur`, `slotinpod` and `maxseq` are read from
+/// `Video.ext`, since OpenRTB 2.5 has no place for them.
+///
+/// [`BidRequest::pods`]: ./struct.BidRequest.html#method.pods
+#[derive(Debug, PartialEq, Clone)]
+pub struct Pod<'a> {
+    /// The `podid` shared by the impressions, if they carry one.
+    pub id: Option<&'a str>,
+
+    /// The `startdelay` shared by the impressions.
+    pub startdelay: Option<crate::StartDelay>,
+
+    /// Total duration of the break in seconds (`poddur`), if given.
+    pub duration: Option<i32>,
+
+    /// The slots of the break, in playback order.
+    pub slots: Vec<Slot<'a>>,
+}
+
+/// A video impression within a [`Pod`].
+#[derive(Debug, PartialEq, Clone)]
+pub struct Slot<'a> {
+    /// The impression.
+    pub imp: &'a crate::Imp,
+
+    /// Where in the break the impression plays (`slotinpod`).
+    pub position: SlotPosition,
+
+    /// How many ads the impression takes: `maxseq` if given, unbounded for a pod of a single
+    /// impression with a `poddur` (a dynamic pod), and 1 otherwise.
+    pub capacity: usize,
+}
+
+/// 2.6 Slot Position in Pod
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SlotPosition {
+    /// Any position in the pod
+    Any,
+    /// First position in the pod
+    First,
+    /// Last position in the pod
+    Last,
+    /// First or last position in the pod
+    FirstOrLast,
+}
+
+/// A bid offered for a [`Pod`], with the duration of its creative in seconds.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct PodBid<'b> {
+    /// The bid; `Bid.impid` names the slot it is for.
+    pub bid: &'b crate::Bid,
+
+    /// Duration of the creative in seconds.
+    pub duration: i32,
+}
+
+/// The bids chosen to fill a [`Pod`], as returned by [`Pod::fill`].
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct PodFill<'b> {
+    /// The chosen bids, in playback order.
+    pub bids: Vec<PodBid<'b>>,
+
+    /// Sum of the prices of the chosen bids.
+    pub revenue: f64,
+
+    /// Sum of the durations of the chosen bids in seconds, which may exceed an `i32` when neither
+    /// the pod nor its slots bound the duration.
+    pub duration: i64,
+}
+
+impl<'a> Pod<'a> {
+    /// The number of steps after which [`Pod::fill`] stops looking for a better fill.
+    ///
+    /// [`Pod::fill`]: ./struct.Pod.html#method.fill
+    pub const MAX_SEARCH_STEPS: usize = 100_000;
+
+    /// Groups the video impressions of `imps` into pods: by `podid` where given, otherwise by
+    /// `startdelay`. Slots are ordered by `slotinpod`, then `Video.sequence`, then request order.
+    pub(crate) fn group(imps: &'a [crate::Imp]) -> Vec<Self> {
+        let mut pods: Vec<Self> = Vec::new();
+        for imp in imps {
+            let video = match &imp.video {
+                Some(v) => v,
+                None => continue,
+            };
+            let id = ext(video)
+                .and_then(|v| v.get("podid"))
+                .and_then(|v| v.as_str());
+            let pod = match pods
+                .iter_mut()
+                .find(|p| p.id == id && (id.is_some() || p.startdelay == video.startdelay))
+            {
+                Some(pod) => pod,
+                None => {
+                    pods.push(Self {
+                        id,
+                        startdelay: video.startdelay,
+                        duration: None,
+                        slots: Vec::new(),
+                    });
+                    pods.last_mut().unwrap()
+                }
+            };
+            pod.duration = pod.duration.or_else(|| int(video, "poddur"));
+            pod.slots.push(Slot {
+                imp,
+                position: match int(video, "slotinpod") {
+                    Some(1) => SlotPosition::First,
+                    Some(-1) => SlotPosition::Last,
+                    Some(2) => SlotPosition::FirstOrLast,
+                    _ => SlotPosition::Any,
+                },
+                capacity: int(video, "maxseq").map_or(1, |v| v.max(0) as usize),
+            });
+        }
+
+        for pod in &mut pods {
+            if let ([slot], true) = (&mut pod.slots[..], pod.duration.is_some()) {
+                if int(slot.imp.video.as_ref().unwrap(), "maxseq").is_none() {
+                    slot.capacity = usize::MAX;
+                }
+            }
+            let rank = |s: &Slot<'_>| match s.position {
+                SlotPosition::First => 0,
+                SlotPosition::FirstOrLast | SlotPosition::Any => 1,
+                SlotPosition::Last => 2,
+            };
+            // A stable sort keeps request order among equals.
+            pod.slots
+                .sort_by_key(|s| (rank(s), s.imp.video.as_ref().unwrap().sequence));
+        }
+        pods
+    }
+
+    /// Chooses the bids that maximise revenue subject to:
+    ///
+    /// * each bid filling the slot its `impid` names, within the slot's capacity and its
+    ///   `minduration` and `maxduration` (extended by `maxextended`),
+    /// * the total duration not exceeding the pod duration,
+    /// * competitive separation: no two chosen bids sharing an `adomain` (ignoring ASCII case) or a
+    ///   `cat`.
+    ///
+    /// The search is exact, pruning with the revenue still available, for the tens of bids a
+    /// single break usually receives. It gives up after [`MAX_SEARCH_STEPS`] steps and returns
+    /// the best fill found so far, which is at least as good as filling the break greedily by
+    /// descending price.
+    ///
+    /// [`MAX_SEARCH_STEPS`]: ./struct.Pod.html#associatedconstant.MAX_SEARCH_STEPS
+    pub fn fill<'b>(&self, bids: &[PodBid<'b>]) -> PodFill<'b> {
+        let mut candidates: Vec<(usize, PodBid<'b>)> = bids
+            .iter()
+            .filter_map(|b| {
+                let slot = self.slots.iter().position(|s| s.imp.id == b.bid.impid)?;
+                let video = self.slots[slot].imp.video.as_ref()?;
+                let max = video.maxduration.and_then(|max| match video.maxextended {
+                    crate::MaxExtendedAdDuration::NoLimit => None,
+                    crate::MaxExtendedAdDuration::NotAllowed => Some(max),
+                    crate::MaxExtendedAdDuration::Specific(v) => Some(max.saturating_add(v)),
+                });
+                let fits = b.duration >= video.minduration.unwrap_or(0)
+                    && max.is_none_or(|max| b.duration <= max)
+                    && self.duration.is_none_or(|max| b.duration <= max)
+                    && b.bid.price > 0.0;
+                fits.then_some((slot, *b))
+            })
+            .collect();
+        candidates.sort_by(|a, b| b.1.bid.price.total_cmp(&a.1.bid.price));
+
+        let mut search = Search {
+            pod: self,
+            candidates: &candidates,
+            remaining: candidates
+                .iter()
+                .rev()
+                .scan(0.0, |sum, c| {
+                    *sum += c.1.bid.price;
+                    Some(*sum)
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+                .rev()
+                .collect(),
+            // The first path searched is the greedy fill, so it is always completed.
+            steps: Self::MAX_SEARCH_STEPS + candidates.len(),
+            used: vec![0; self.slots.len()],
+            chosen: Vec::new(),
+            duration: 0,
+            revenue: 0.0,
+            best: (0.0, Vec::new()),
+        };
+        search.run(0);
+
+        let mut chosen = search.best.1;
+        chosen.sort_by_key(|&i| candidates[i].0);
+        let bids: Vec<_> = chosen.into_iter().map(|i| candidates[i].1).collect();
+        PodFill {
+            revenue: bids.iter().map(|b| b.bid.price).sum(),
+            duration: bids.iter().map(|b| i64::from(b.duration)).sum(),
+            bids,
+        }
+    }
+}
+
+struct Search<'p, 'a, 'b> {
+    pod: &'p Pod<'a>,
+    /// Slot index and bid, by descending price.
+    candidates: &'p [(usize, PodBid<'b>)],
+    /// Sum of the prices of `candidates[i..]`.
+    remaining: Vec<f64>,
+    /// Number of steps left before the search gives up.
+    steps: usize,
+    used: Vec<usize>,
+    chosen: Vec<usize>,
+    duration: i64,
+    revenue: f64,
+    best: (f64, Vec<usize>),
+}
+
+impl Search<'_, '_, '_> {
+    fn run(&mut self, i: usize) {
+        if self.revenue > self.best.0 {
+            self.best = (self.revenue, self.chosen.clone());
+        }
+        if self.steps == 0
+            || i == self.candidates.len()
+            || self.revenue + self.remaining[i] <= self.best.0
+        {
+            return;
+        }
+        self.steps -= 1;
+
+        let (slot, b) = self.candidates[i];
+        if self.accepts(slot, &b) {
+            self.used[slot] += 1;
+            self.chosen.push(i);
+            self.duration += i64::from(b.duration);
+            self.revenue += b.bid.price;
+            self.run(i + 1);
+            self.revenue -= b.bid.price;
+            self.duration -= i64::from(b.duration);
+            self.chosen.pop();
+            self.used[slot] -= 1;
+        }
+        self.run(i + 1);
+    }
+
+    fn accepts(&self, slot: usize, b: &PodBid<'_>) -> bool {
+        if self.used[slot] >= self.pod.slots[slot].capacity
+            || self
+                .pod
+                .duration
+                .is_some_and(|max| self.duration + i64::from(b.duration) > i64::from(max))
+        {
+            return false;
+        }
+        let adomain = b.bid.adomain.as_deref().unwrap_or_default();
+        let cat = b.bid.cat.as_deref().unwrap_or_default();
+        self.chosen.iter().all(|&j| {
+            let other = self.candidates[j].1.bid;
+            let shares_adomain = other
+                .adomain
+                .iter()
+                .flatten()
+                .any(|o| adomain.iter().any(|a| a.eq_ignore_ascii_case(o)));
+            let shares_cat = other.cat.iter().flatten().any(|o| cat.contains(o));
+            !shares_adomain && !shares_cat
+        })
+    }
+}
+
+fn ext(video: &crate::Video) -> Option<&serde_json::Map<String, serde_json::Value>> {
+    video.ext.as_ref()
+}
+
+fn int(video: &crate::Video, key: &str) -> Option<i32> {
+    use std::convert::TryFrom;
+
+    ext(video)?
+        .get(key)?
+        .as_i64()
+        .and_then(|v| i32::try_from(v).ok())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn group() -> serde_json::Result<()> {
+        let req: crate::BidRequest = serde_json::from_str(
+            r#"{"id":"1","imp":[
+                {"id":"1","video":{"mimes":[],"startdelay":0,"sequence":2}},
+                {"id":"2","video":{"mimes":[],"startdelay":0,"sequence":1}},
+                {"id":"3","video":{"mimes":[],"startdelay":-1}},
+                {"id":"4","banner":{}},
+                {"id":"5","video":{"mimes":[],"ext":{"podid":"p","poddur":60,"slotinpod":-1}}},
+                {"id":"6","video":{"mimes":[],"ext":{"podid":"p","slotinpod":1,"maxseq":2}}},
+                {"id":"7","video":{"mimes":[],"startdelay":30,"ext":{"poddur":90}}}
+            ]}"#,
+        )?;
+        let pods = req.pods();
+        let ids = |p: &Pod<'_>| p.slots.iter().map(|s| s.imp.id.clone()).collect::<Vec<_>>();
+
+        assert_eq!(pods.len(), 4);
+        assert_eq!(pods[0].startdelay, Some(crate::StartDelay::PreRoll));
+        assert_eq!(ids(&pods[0]), vec!["2", "1"]);
+        assert_eq!(ids(&pods[1]), vec!["3"]);
+        assert_eq!(pods[1].slots[0].capacity, 1);
+
+        assert_eq!((pods[2].id, pods[2].duration), (Some("p"), Some(60)));
+        assert_eq!(ids(&pods[2]), vec!["6", "5"]);
+        assert_eq!(pods[2].slots[0].position, SlotPosition::First);
+        assert_eq!(pods[2].slots[0].capacity, 2);
+        assert_eq!(pods[2].slots[1].position, SlotPosition::Last);
+
+        assert_eq!(pods[3].slots[0].capacity, usize::MAX);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fill() -> serde_json::Result<()> {
+        let req: crate::BidRequest = serde_json::from_str(
+            r#"{"id":"1","imp":[
+                {"id":"1","video":{"mimes":[],"maxduration":30,"ext":{"podid":"p","poddur":60}}},
+                {"id":"2","video":{"mimes":[],"maxduration":30,"ext":{"podid":"p"}}},
+                {"id":"3","video":{"mimes":[],"minduration":15,"maxduration":15,"maxextended":15,"ext":{"podid":"p"}}}
+            ]}"#,
+        )?;
+        let pod = &req.pods()[0];
+        let bid = |id: &str, impid: &str, price: f64, adomain: &str, cat: &str| crate::Bid {
+            id: id.into(),
+            impid: impid.into(),
+            price,
+            adomain: Some(vec![adomain.into()]),
+            cat: serde_json::from_str(&format!(r#"["{}"]"#, cat)).unwrap(),
+            ..Default::default()
+        };
+        let bids = [
+            bid("a", "1", 10.0, "acme.com", "IAB2"),
+            bid("b", "2", 9.0, "ACME.com", "IAB3"),
+            bid("c", "2", 6.0, "globex.com", "IAB2"),
+            bid("d", "3", 5.0, "initech.com", "IAB4"),
+            bid("e", "2", 4.0, "hooli.com", "IAB5"),
+            bid("f", "3", 20.0, "umbrella.com", "IAB6"),
+            bid("g", "9", 50.0, "other.com", "IAB7"),
+        ];
+        let durations = [30, 30, 15, 30, 15, 40, 15];
+        let offers: Vec<_> = bids
+            .iter()
+            .zip(durations.iter())
+            .map(|(bid, &duration)| PodBid { bid, duration })
+            .collect();
+
+        // "f" is too long for its slot and "g" is for another impression; "a" and "b" share an
+        // advertiser, "a" and "c" a category, and "a", "e" and "d" together exceed the duration.
+        let fill = pod.fill(&offers);
+        let ids: Vec<_> = fill.bids.iter().map(|b| b.bid.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "d"]);
+        assert_eq!((fill.revenue, fill.duration), (15.0, 60));
+
+        let fill = pod.fill(&offers[2..3]);
+        assert_eq!(fill.revenue, 6.0);
+        assert_eq!(pod.fill(&[]), PodFill::default());
+
+        Ok(())
+    }
+
+    #[test]
+    fn fill_many() -> serde_json::Result<()> {
+        // Equal bids defeat pruning: an exact search would try every way of choosing 20 of them.
+        let req: crate::BidRequest = serde_json::from_str(
+            r#"{"id":"1","imp":[
+                {"id":"1","video":{"mimes":[],"ext":{"podid":"p","poddur":99999999999,"maxseq":100}}},
+                {"id":"2","video":{"mimes":[],"ext":{"podid":"p","poddur":300}}}
+            ]}"#,
+        )?;
+        let pod = &req.pods()[0];
+        // A duration out of range is ignored.
+        assert_eq!(pod.duration, Some(300));
+
+        let bids: Vec<_> = (0..60)
+            .map(|i| crate::Bid {
+                id: i.to_string(),
+                impid: "1".into(),
+                price: 1.0,
+                adomain: Some(vec![format!("{}.example", i)]),
+                ..Default::default()
+            })
+            .collect();
+        let offers: Vec<_> = bids
+            .iter()
+            .map(|bid| PodBid { bid, duration: 15 })
+            .collect();
+        let fill = pod.fill(&offers);
+        assert_eq!((fill.revenue, fill.duration), (20.0, 300));
+
+        Ok(())
+    }
+
+    #[test]
+    fn fill_unbounded() -> serde_json::Result<()> {
+        let req: crate::BidRequest = serde_json::from_str(
+            r#"{"id":"1","imp":[
+                {"id":"1","video":{"mimes":[],"ext":{"podid":"p"}}},
+                {"id":"2","video":{"mimes":[],"ext":{"podid":"p"}}}
+            ]}"#,
+        )?;
+        let pod = &req.pods()[0];
+        let bids: Vec<_> = ["1", "2"]
+            .iter()
+            .map(|&impid| crate::Bid {
+                impid: impid.into(),
+                price: 1.0,
+                ..Default::default()
+            })
+            .collect();
+        let offers: Vec<_> = bids
+            .iter()
+            .map(|bid| PodBid {
+                bid,
+                duration: 2_000_000_000,
+            })
+            .collect();
+        let fill = pod.fill(&offers);
+        assert_eq!((fill.revenue, fill.duration), (2.0, 4_000_000_000));
+
+        Ok(())
+    }
+}
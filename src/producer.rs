@@ -4,6 +4,12 @@
 /// particularly useful when the content is syndicated and may be distributed through different
 /// publishers and thus when the producer and publisher are not necessarily the same entity.
 #[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::recommended)
+)]
 pub struct Producer {
     /// string
     /// Content producer or originator ID. Useful if content is syndicated and may be posted on a
@@ -29,6 +35,7 @@ pub struct Producer {
     /// object
     /// Placeholder for exchange-specific extensions to OpenRTB.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::ext))]
     pub ext: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
@@ -5,6 +5,12 @@ use serde_repr::{Deserialize_repr, Serialize_repr};
 /// The following table lists the options for content quality. These values are defined by the IAB;
 /// refer to www.iab.com/wp-content/uploads/2015/03/long-form-video-final.pdf for more information.
 #[derive(Serialize_repr, Deserialize_repr, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::repr::<Self>)
+)]
 #[repr(i32)]
 pub enum ProductionQuality {
     /// Unknown
@@ -0,0 +1,51 @@
+//! Public suffixes from the ICANN section of the Public Suffix List, see
+//! <https://publicsuffix.org/>. Suffixes of the private section, such as `github.io`, are not
+//! included.
+
+include!("public_suffix_list.rs");
+
+/// Returns the public suffix of `domain`, lower case without a trailing dot: its longest suffix
+/// named by a rule, or its last label by the default rule `*`.
+pub(crate) fn public_suffix(domain: &str) -> &str {
+    let mut suffix = domain;
+    loop {
+        let parent = suffix.split_once('.').map(|v| v.1);
+        if EXCEPTIONS.binary_search(&suffix).is_ok() {
+            // An exception makes the parent of the domain it names the public suffix.
+            return parent.unwrap_or(suffix);
+        }
+        if RULES.binary_search(&suffix).is_ok() {
+            return suffix;
+        }
+        match parent {
+            Some(parent) if WILDCARDS.binary_search(&parent).is_ok() => return suffix,
+            Some(parent) => suffix = parent,
+            None => return suffix,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rules() {
+        for list in [RULES, WILDCARDS, EXCEPTIONS].iter() {
+            assert!(list.windows(2).all(|w| w[0] < w[1]));
+        }
+        assert_eq!(public_suffix("com"), "com");
+        assert_eq!(public_suffix("example"), "example");
+        assert_eq!(public_suffix("www.ford.co.uk"), "co.uk");
+        assert_eq!(public_suffix("ford.com"), "com");
+        assert_eq!(public_suffix("example.github.io"), "io");
+
+        // `*.kawasaki.jp` and `!city.kawasaki.jp`
+        assert_eq!(public_suffix("a.shop.kawasaki.jp"), "shop.kawasaki.jp");
+        assert_eq!(public_suffix("a.city.kawasaki.jp"), "kawasaki.jp");
+        assert_eq!(public_suffix("kawasaki.jp"), "jp");
+
+        assert_eq!(public_suffix("a.ålesund.no"), "ålesund.no");
+        assert_eq!(public_suffix("a.xn--lesund-hua.no"), "xn--lesund-hua.no");
+    }
+}
@@ -0,0 +1,88 @@
+use schemars::Schema;
+use serde_json::Value;
+
+/// Lists the properties documented as recommended under the `x-recommended` keyword, next to the
+/// standard `required` keyword.
+pub(crate) fn recommended(schema: &mut Schema) {
+    let names: Vec<Value> = match schema.get("properties").and_then(Value::as_object) {
+        Some(props) => props
+            .iter()
+            .filter(|(_, prop)| {
+                prop.get("description")
+                    .and_then(Value::as_str)
+                    .and_then(|s| s.lines().next())
+                    .is_some_and(|s| s.contains("recommended"))
+            })
+            .map(|(name, _)| name.as_str().into())
+            .collect(),
+        None => return,
+    };
+    if !names.is_empty() {
+        schema.insert("x-recommended".into(), names.into());
+    }
+}
+
+/// Rewrites the schema derived for a `serde_repr` enum, which describes the variant names, to
+/// describe the integer codes that are actually (de)serialized.
+pub(crate) fn repr<T>(schema: &mut Schema)
+where
+    T: serde::de::DeserializeOwned + std::fmt::Debug,
+{
+    // The `Debug` name of each variant is the name used by the derived schema. Codes of all lists
+    // of the specification lie within this range; a variant outside it would be left as a name in
+    // an integer schema, so it is rejected.
+    let codes: std::collections::HashMap<_, _> = (-1..=1000)
+        .filter_map(|v| {
+            let e = serde_json::from_value::<T>(v.into()).ok()?;
+            Some((format!("{:?}", e), v))
+        })
+        .collect();
+    let code = |v: &mut Value| {
+        if let Some(s) = v.as_str() {
+            match codes.get(s) {
+                Some(c) => *v = (*c).into(),
+                None => panic!(
+                    "variant `{}` of `{}` has no code in -1..=1000",
+                    s,
+                    std::any::type_name::<T>()
+                ),
+            }
+        }
+    };
+    let rewrite = |variant: &mut serde_json::Map<String, Value>| {
+        variant.insert("type".into(), "integer".into());
+        if let Some(v) = variant.get_mut("const") {
+            code(v);
+        }
+        if let Some(Value::Array(values)) = variant.get_mut("enum") {
+            values.iter_mut().for_each(code);
+        }
+    };
+
+    // Variants are listed under `oneOf` when documented, and as a plain `enum` otherwise.
+    match schema.get_mut("oneOf") {
+        Some(Value::Array(variants)) => variants
+            .iter_mut()
+            .filter_map(Value::as_object_mut)
+            .for_each(rewrite),
+        _ => rewrite(schema.ensure_object()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, serde_repr::Deserialize_repr, schemars::JsonSchema)]
+    #[repr(i32)]
+    enum Codes {
+        Listed = 1,
+        Unlisted = 5000,
+    }
+
+    #[test]
+    #[should_panic(expected = "variant `Unlisted`")]
+    fn repr_unmapped() {
+        repr::<Codes>(&mut schemars::schema_for!(Codes));
+    }
+}
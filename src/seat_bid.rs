@@ -6,10 +6,17 @@
 /// impressions that it can win (default) or if it is only interested in winning any if it can win
 /// them all as a group.
 #[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::recommended)
+)]
 pub struct SeatBid {
     /// object array; required
     /// Array of 1+ Bid objects (Section 4.2.3) each related to an impression. Multiple bids can
     /// relate to the same impression.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::non_empty))]
     pub bid: Vec<crate::Bid>,
 
     /// string
@@ -24,11 +31,16 @@ pub struct SeatBid {
         skip_serializing_if = "default_ext::DefaultExt::is_default",
         with = "crate::serde::i32_as_bool"
     )]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(schema_with = "crate::serde::i32_as_bool::schema")
+    )]
     pub group: bool,
 
     /// object
     /// Placeholder for bidder-specific extensions to OpenRTB.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::ext))]
     pub ext: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
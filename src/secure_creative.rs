@@ -0,0 +1,346 @@
+/// The outcome of checking a bid for a secure impression, as returned by [`Imp::check_secure`].
+///
+/// [`Imp::check_secure`]: ./struct.Imp.html#method.check_secure
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct SecureCheck {
+    /// The non-secure URLs of the bid; empty if the impression does not require secure
+    /// creatives.
+    pub insecure: Vec<InsecureUrl>,
+}
+
+/// A non-secure URL referenced by a bid.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct InsecureUrl {
+    /// Where the URL was found.
+    pub location: UrlLocation,
+
+    /// The URL.
+    pub url: String,
+}
+
+/// Where an [`InsecureUrl`] was found.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum UrlLocation {
+    /// A field of the bid: `nurl`, `burl`, `lurl` or `iurl`.
+    Bid(&'static str),
+    /// HTML or VAST markup in `adm`.
+    Markup {
+        /// Byte offset of the URL in `adm`.
+        offset: usize,
+        /// Name of the element containing the URL, e.g. `img` or `MediaFile`.
+        element: Option<String>,
+        /// Name of the attribute whose value is the URL, e.g. `src`.
+        attribute: Option<String>,
+    },
+    /// A native response in `adm`, as the path of the JSON string containing the URL, e.g.
+    /// `assets[0].img.url`.
+    Native(String),
+}
+
+impl SecureCheck {
+    /// Returns `true` if the bid references no non-secure URL.
+    pub fn is_secure(&self) -> bool {
+        self.insecure.is_empty()
+    }
+
+    /// Returns the loss reason of a bid that is not secure.
+    pub fn loss_reason(&self) -> Option<crate::LossReason> {
+        if self.is_secure() {
+            None
+        } else {
+            Some(crate::LossReason::CreativeFiltered_NotSecure)
+        }
+    }
+}
+
+/// Returns the `http` URLs of the notice URLs of `bid` and of its markup.
+pub(crate) fn insecure_urls(bid: &crate::Bid) -> Vec<InsecureUrl> {
+    let fields = [
+        ("nurl", &bid.nurl),
+        ("burl", &bid.burl),
+        ("lurl", &bid.lurl),
+        ("iurl", &bid.iurl),
+    ];
+    let mut found: Vec<_> = fields
+        .iter()
+        .filter_map(|(name, url)| {
+            let url: &str = url.as_deref()?;
+            is_insecure(url).then(|| InsecureUrl {
+                location: UrlLocation::Bid(name),
+                url: url.to_owned(),
+            })
+        })
+        .collect();
+
+    let adm = match &bid.adm {
+        Some(adm) => adm,
+        None => return found,
+    };
+    if crate::media_type::markup_types(adm).contains(crate::MediaType::Native) {
+        if let Ok(value) = serde_json::from_str(adm) {
+            native(&value, &mut String::new(), &mut found);
+        }
+    } else {
+        markup(adm, &mut found);
+    }
+    found
+}
+
+fn is_insecure(url: &str) -> bool {
+    url.get(..7)
+        .is_some_and(|v| v.eq_ignore_ascii_case("http://"))
+}
+
+/// Iterates over the byte offsets and text of the `http` URLs in `s` that start an attribute
+/// value, element text or `s` itself. URLs elsewhere, e.g. in a query string or in prose, are not
+/// fetched by the creative and are skipped.
+fn find_urls(s: &str) -> impl Iterator<Item = (usize, &str)> {
+    let lower = s.to_ascii_lowercase();
+    let starts: Vec<_> = lower
+        .match_indices("http://")
+        .map(|(i, _)| i)
+        .filter(|&i| starts_value(&s[..i]))
+        .collect();
+    starts.into_iter().map(move |i| {
+        let len = s[i..]
+            .find(|c: char| c.is_whitespace() || "\"'<>()[]".contains(c))
+            .unwrap_or(s.len() - i);
+        (i, &s[i..i + len])
+    })
+}
+
+/// Returns `true` if text following `before` starts an attribute value, element text or the
+/// whole string.
+fn starts_value(before: &str) -> bool {
+    let s = before.trim_end();
+    s.is_empty() || s.ends_with('>') || s.ends_with("<![CDATA[") || attribute(before).is_some()
+}
+
+fn markup(adm: &str, found: &mut Vec<InsecureUrl>) {
+    for (offset, url) in find_urls(adm) {
+        let before = &adm[..offset];
+        let attribute = attribute(before);
+        if attribute
+            .as_deref()
+            .is_some_and(|v| v.starts_with("xmlns") || v.ends_with("schemaLocation"))
+        {
+            // Namespace names are identifiers, not fetched.
+            continue;
+        }
+        found.push(InsecureUrl {
+            location: UrlLocation::Markup {
+                offset,
+                element: element(before),
+                attribute,
+            },
+            url: url.to_owned(),
+        });
+    }
+}
+
+/// Returns the name of the attribute whose value starts right after `before`, if any.
+fn attribute(before: &str) -> Option<String> {
+    let tag = &before[before.rfind('<')?..];
+    if tag.contains('>') {
+        return None;
+    }
+    // Skip the complete values of the preceding attributes.
+    let mut quote = None;
+    for (i, c) in tag.char_indices() {
+        match quote {
+            Some((q, _)) if c == q => quote = None,
+            None if c == '"' || c == '\'' => quote = Some((c, i)),
+            _ => {}
+        }
+    }
+    let s = match quote {
+        Some((_, i)) if tag[i + 1..].trim().is_empty() => &tag[..i],
+        Some(_) => return None,
+        None => tag,
+    };
+    let s = s.trim_end().strip_suffix('=')?.trim_end();
+    let start = s
+        .rfind(|c: char| c.is_whitespace() || c == '<')
+        .map_or(0, |i| i + 1);
+    Some(s[start..].to_owned()).filter(|v| !v.is_empty())
+}
+
+/// Returns the name of the innermost element started in `before`, skipping CDATA sections,
+/// comments and end tags.
+fn element(before: &str) -> Option<String> {
+    let mut s = before;
+    while let Some(i) = s.rfind('<') {
+        let tag = &s[i + 1..];
+        if tag.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            let end = tag
+                .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+                .unwrap_or(tag.len());
+            return Some(tag[..end].to_owned());
+        }
+        s = &s[..i];
+    }
+    None
+}
+
+fn native(value: &serde_json::Value, path: &mut String, found: &mut Vec<InsecureUrl>) {
+    let len = path.len();
+    match value {
+        serde_json::Value::String(s) => {
+            for (_, url) in find_urls(s) {
+                found.push(InsecureUrl {
+                    location: UrlLocation::Native(path.clone()),
+                    url: url.to_owned(),
+                });
+            }
+        }
+        serde_json::Value::Array(values) => {
+            for (i, v) in values.iter().enumerate() {
+                path.push_str(&format!("[{}]", i));
+                native(v, path, found);
+                path.truncate(len);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for (k, v) in map {
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                path.push_str(k);
+                native(v, path, found);
+                path.truncate(len);
+            }
+        }
+        _ => {}
+    }
+}
+
+impl std::fmt::Display for UrlLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bid(name) => f.write_str(name),
+            Self::Markup {
+                offset,
+                element,
+                attribute,
+            } => {
+                f.write_str("adm")?;
+                if let Some(v) = element {
+                    write!(f, " <{}>", v)?;
+                }
+                if let Some(v) = attribute {
+                    write!(f, " {}", v)?;
+                }
+                write!(f, " at {}", offset)
+            }
+            Self::Native(path) => write!(f, "adm {}", path),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn location(offset: usize, element: Option<&str>, attribute: Option<&str>) -> UrlLocation {
+        UrlLocation::Markup {
+            offset,
+            element: element.map(Into::into),
+            attribute: attribute.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn html() {
+        let adm = r#"<a href="HTTP://click.example/c"><img src='https://cdn.example/a.png'></a><svg xmlns="http://www.w3.org/2000/svg"><image href=http://cdn.example/b.png /></svg><script>fetch("http://t.example/p")</script><p>Visit http://t.example/</p>"#;
+        let mut found = Vec::new();
+        markup(adm, &mut found);
+        assert_eq!(
+            found,
+            vec![
+                InsecureUrl {
+                    location: location(9, Some("a"), Some("href")),
+                    url: "HTTP://click.example/c".into()
+                },
+                InsecureUrl {
+                    location: location(126, Some("image"), Some("href")),
+                    url: "http://cdn.example/b.png".into()
+                },
+            ]
+        );
+        assert_eq!(found[0].location.to_string(), "adm <a> href at 9");
+
+        let adm = r#"<a href="https://clk.example/?u=http://landing" title='x' data-u=http://t.example/><b>http://t.example/b</b></a>"#;
+        let mut found = Vec::new();
+        markup(adm, &mut found);
+        assert_eq!(
+            found,
+            vec![
+                InsecureUrl {
+                    location: location(65, Some("a"), Some("data-u")),
+                    url: "http://t.example/".into()
+                },
+                InsecureUrl {
+                    location: location(86, Some("b"), None),
+                    url: "http://t.example/b".into()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn vast() {
+        let adm = r#"<VAST version="3.0"><Ad><InLine><Impression><![CDATA[http://t.example/i]]></Impression><MediaFile type="video/mp4"> https://cdn.example/v.mp4 </MediaFile></InLine></Ad></VAST>"#;
+        let mut found = Vec::new();
+        markup(adm, &mut found);
+        assert_eq!(
+            found,
+            vec![InsecureUrl {
+                location: location(53, Some("Impression"), None),
+                url: "http://t.example/i".into()
+            }]
+        );
+    }
+
+    #[test]
+    fn check() -> serde_json::Result<()> {
+        let imp: crate::Imp = serde_json::from_str(r#"{"id":"1","secure":1}"#)?;
+        let bid: crate::Bid = serde_json::from_str(
+            r#"{
+                "id": "1",
+                "impid": "1",
+                "price": 1,
+                "nurl": "https://dsp.example/win",
+                "lurl": "http://dsp.example/loss",
+                "adm": "{\"native\":{\"assets\":[{\"id\":1,\"img\":{\"url\":\"http://cdn.example/a.png\"}}],\"link\":{\"url\":\"https://a.example\"}}}"
+            }"#,
+        )?;
+
+        let check = imp.check_secure(&bid);
+        assert_eq!(
+            check.insecure,
+            vec![
+                InsecureUrl {
+                    location: UrlLocation::Bid("lurl"),
+                    url: "http://dsp.example/loss".into()
+                },
+                InsecureUrl {
+                    location: UrlLocation::Native("native.assets[0].img.url".into()),
+                    url: "http://cdn.example/a.png".into()
+                },
+            ]
+        );
+        assert_eq!(
+            check.loss_reason(),
+            Some(crate::LossReason::CreativeFiltered_NotSecure)
+        );
+
+        let imp = crate::Imp {
+            secure: Some(false),
+            ..imp
+        };
+        assert!(imp.check_secure(&bid).is_secure());
+        assert_eq!(bid.insecure_urls().len(), 2);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,257 @@
+use std::collections::{HashMap, HashSet};
+
+/// Identifies the data providers of [`Data`] objects.
+///
+/// [`Data`]: ./struct.Data.html
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DataProvider<'a> {
+    /// Matches `Data.id`.
+    Id(&'a str),
+    /// Matches `Data.name`, ignoring ASCII case.
+    Name(&'a str),
+    /// Matches the Seller-Defined Audiences taxonomy `Data.ext.segtax`.
+    Segtax(i32),
+    /// A Seller-Defined Audiences provider: matches both `Data.name`, ignoring ASCII case, and
+    /// `Data.ext.segtax`.
+    Sda {
+        /// Domain of the party that defined the segments.
+        name: &'a str,
+        /// The taxonomy of the segments.
+        segtax: i32,
+    },
+}
+
+/// A lookup of the segments of a list of [`Data`] objects by provider and by segment id, as
+/// returned by [`User::segments`] and [`Content::segments`].
+///
+/// ```
+/// # use openrtb2::DataProvider;
+/// let user: openrtb2::User = serde_json::from_str(
+///     r#"{"data":[{"id":"dmp","segment":[{"id":"1"}]},{"name":"pub.example","segment":[{"id":"7"}],"ext":{"segtax":4}}]}"#,
+/// )?;
+/// let segments = user.segments();
+/// assert!(segments.has_segment("7"));
+/// assert!(segments.contains(DataProvider::Segtax(4), "7"));
+/// assert_eq!(segments.segment_ids(DataProvider::Id("dmp")).collect::<Vec<_>>(), vec!["1"]);
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+///
+/// [`Data`]: ./struct.Data.html
+/// [`User::segments`]: ./struct.User.html#method.segments
+/// [`Content::segments`]: ./struct.Content.html#method.segments
+#[derive(Debug, Clone)]
+pub struct SegmentIndex<'a> {
+    data: Vec<&'a crate::Data>,
+    ids: HashMap<&'a str, Vec<usize>>,
+    names: HashMap<String, Vec<usize>>,
+    segtaxes: HashMap<i32, Vec<usize>>,
+    segments: HashSet<&'a str>,
+}
+
+impl DataProvider<'_> {
+    /// Returns `true` if `data` is from this provider.
+    pub fn matches(self, data: &crate::Data) -> bool {
+        let name = |name: &str| {
+            data.name
+                .as_deref()
+                .is_some_and(|v| v.eq_ignore_ascii_case(name))
+        };
+        match self {
+            Self::Id(id) => data.id.as_deref() == Some(id),
+            Self::Name(v) => name(v),
+            Self::Segtax(segtax) => data.segtax() == Some(segtax),
+            Self::Sda { name: v, segtax } => name(v) && data.segtax() == Some(segtax),
+        }
+    }
+}
+
+impl<'a> SegmentIndex<'a> {
+    /// Indexes the segments of `data`.
+    pub fn new(data: &'a [crate::Data]) -> Self {
+        let mut index = Self {
+            data: data.iter().collect(),
+            ids: HashMap::new(),
+            names: HashMap::new(),
+            segtaxes: HashMap::new(),
+            segments: HashSet::new(),
+        };
+        for (i, data) in data.iter().enumerate() {
+            if let Some(id) = &data.id {
+                index.ids.entry(id).or_default().push(i);
+            }
+            if let Some(name) = &data.name {
+                index
+                    .names
+                    .entry(name.to_ascii_lowercase())
+                    .or_default()
+                    .push(i);
+            }
+            if let Some(segtax) = data.segtax() {
+                index.segtaxes.entry(segtax).or_default().push(i);
+            }
+            index.segments.extend(data.segment_ids());
+        }
+        index
+    }
+
+    /// Iterates over the [`Data`] objects of `provider`.
+    ///
+    /// [`Data`]: ./struct.Data.html
+    pub fn providers<'s>(
+        &'s self,
+        provider: DataProvider<'s>,
+    ) -> impl Iterator<Item = &'a crate::Data> + 's {
+        let found = match provider {
+            DataProvider::Id(id) => self.ids.get(id),
+            DataProvider::Name(name) | DataProvider::Sda { name, .. } => {
+                self.names.get(&name.to_ascii_lowercase())
+            }
+            DataProvider::Segtax(segtax) => self.segtaxes.get(&segtax),
+        };
+        found
+            .into_iter()
+            .flatten()
+            .map(move |&i| self.data[i])
+            .filter(move |data| provider.matches(data))
+    }
+
+    /// Iterates over the segments of `provider`.
+    pub fn segments<'s>(
+        &'s self,
+        provider: DataProvider<'s>,
+    ) -> impl Iterator<Item = &'a crate::Segment> + 's {
+        self.providers(provider)
+            .flat_map(|data| data.segment.iter().flatten())
+    }
+
+    /// Iterates over the segment ids of `provider`.
+    pub fn segment_ids<'s>(
+        &'s self,
+        provider: DataProvider<'s>,
+    ) -> impl Iterator<Item = &'a str> + 's {
+        self.providers(provider).flat_map(|data| data.segment_ids())
+    }
+
+    /// Returns `true` if `provider` has a segment with the given id.
+    pub fn contains(&self, provider: DataProvider<'_>, id: &str) -> bool {
+        self.providers(provider).any(|data| data.has_segment(id))
+    }
+
+    /// Returns `true` if any provider has a segment with the given id.
+    pub fn has_segment(&self, id: &str) -> bool {
+        self.segments.contains(id)
+    }
+}
+
+/// Returns the first [`Data`] of `provider` in `data`, adding one if there is none.
+///
+/// [`Data`]: ./struct.Data.html
+pub(crate) fn data_mut<'d>(
+    data: &'d mut Option<Vec<crate::Data>>,
+    provider: DataProvider<'_>,
+) -> &'d mut crate::Data {
+    let data = data.get_or_insert_with(Vec::new);
+    let i = match data.iter().position(|v| provider.matches(v)) {
+        Some(i) => i,
+        None => {
+            let mut v = crate::Data::default();
+            match provider {
+                DataProvider::Id(id) => v.id = Some(id.to_owned()),
+                DataProvider::Name(name) => v.name = Some(name.to_owned()),
+                DataProvider::Segtax(segtax) => v.set_segtax(segtax),
+                DataProvider::Sda { name, segtax } => {
+                    v.name = Some(name.to_owned());
+                    v.set_segtax(segtax);
+                }
+            }
+            data.push(v);
+            data.len() - 1
+        }
+    };
+    &mut data[i]
+}
+
+/// Removes the segment with the given id from every [`Data`] of `provider` in `data`. Returns
+/// `true` if any was removed.
+///
+/// [`Data`]: ./struct.Data.html
+pub(crate) fn remove_segment(
+    data: &mut Option<Vec<crate::Data>>,
+    provider: DataProvider<'_>,
+    id: &str,
+) -> bool {
+    let mut removed = false;
+    for data in data.iter_mut().flatten() {
+        if provider.matches(data) {
+            removed |= data.remove_segment(id).is_some();
+        }
+    }
+    removed
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn index() -> serde_json::Result<()> {
+        let data: Vec<crate::Data> = serde_json::from_str(
+            r#"[
+                {"id": "dmp", "segment": [{"id": "1"}, {"id": "2"}]},
+                {"id": "dmp", "segment": [{"id": "3"}]},
+                {"name": "Pub.example", "segment": [{"id": "7"}], "ext": {"segtax": 4}},
+                {"name": "pub.example", "segment": [{"id": "8"}], "ext": {"segtax": 6}}
+            ]"#,
+        )?;
+        let index = SegmentIndex::new(&data);
+        let ids = |p| index.segment_ids(p).collect::<Vec<_>>();
+
+        assert_eq!(ids(DataProvider::Id("dmp")), vec!["1", "2", "3"]);
+        assert_eq!(ids(DataProvider::Name("pub.EXAMPLE")), vec!["7", "8"]);
+        assert_eq!(ids(DataProvider::Segtax(6)), vec!["8"]);
+        assert_eq!(
+            ids(DataProvider::Sda {
+                name: "pub.example",
+                segtax: 4
+            }),
+            vec!["7"]
+        );
+        assert!(ids(DataProvider::Id("other")).is_empty());
+
+        assert!(index.contains(DataProvider::Id("dmp"), "3"));
+        assert!(!index.contains(DataProvider::Id("dmp"), "7"));
+        assert!(index.has_segment("8"));
+        assert!(!index.has_segment("9"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn enrich() -> serde_json::Result<()> {
+        let mut user = crate::User::default();
+        let sda = DataProvider::Sda {
+            name: "pub.example",
+            segtax: 4,
+        };
+        user.data_mut(sda).add_segment(crate::Segment {
+            id: Some("7".into()),
+            ..Default::default()
+        });
+        user.data_mut(sda).add_segment(crate::Segment {
+            id: Some("9".into()),
+            ..Default::default()
+        });
+        user.data_mut(DataProvider::Id("dmp"));
+        assert_eq!(
+            serde_json::to_string(&user)?,
+            r#"{"data":[{"name":"pub.example","segment":[{"id":"7"},{"id":"9"}],"ext":{"segtax":4}},{"id":"dmp"}]}"#
+        );
+
+        assert!(user.remove_segment(DataProvider::Segtax(4), "7"));
+        assert!(!user.remove_segment(DataProvider::Id("dmp"), "9"));
+        assert!(!user.segments().has_segment("7"));
+        assert!(user.segments().has_segment("9"));
+
+        Ok(())
+    }
+}
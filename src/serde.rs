@@ -12,13 +12,65 @@ macro_rules! i32_to_bool {
             0 => Ok(false),
             1 => Ok(true),
             v => {
-                let s = format!("invalid value: {}, expected 0 or 1", v);
+                let s = format!("invalid value: {}, expected {}", v, FLAG);
                 Err(serde::de::Error::custom(s))
             }
         }
     };
 }
 
+/// What the visitor of a 0/1 flag expects, by which lenient deserialization recognises flags.
+pub(crate) const FLAG: &str = "0 or 1";
+
+struct Flag;
+
+impl<'de> serde::de::Visitor<'de> for Flag {
+    type Value = bool;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(FLAG)
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<bool, E>
+    where
+        E: serde::de::Error,
+    {
+        i32_to_bool!(v)
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<bool, E>
+    where
+        E: serde::de::Error,
+    {
+        i32_to_bool!(v)
+    }
+}
+
+struct OptFlag;
+
+impl<'de> serde::de::Visitor<'de> for OptFlag {
+    type Value = Option<bool>;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} or null", FLAG)
+    }
+
+    fn visit_none<E>(self) -> Result<Option<bool>, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E>(self) -> Result<Option<bool>, E> {
+        Ok(None)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Option<bool>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_i32(Flag).map(Some)
+    }
+}
+
 pub mod i32_as_bool {
     use super::*;
 
@@ -33,7 +85,12 @@ pub mod i32_as_bool {
     where
         D: Deserializer<'de>,
     {
-        i32_to_bool!(i32::deserialize(deserializer)?)
+        deserializer.deserialize_i32(Flag)
+    }
+
+    #[cfg(feature = "schema")]
+    pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({ "type": "integer", "enum": [0, 1] })
     }
 }
 
@@ -54,11 +111,162 @@ pub mod i32_as_opt_bool {
     where
         D: Deserializer<'de>,
     {
-        match Option::<i32>::deserialize(deserializer)? {
-            Some(v) => i32_to_bool!(v).map(Some),
-            None => Ok(None),
+        deserializer.deserialize_option(OptFlag)
+    }
+
+    #[cfg(feature = "schema")]
+    pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({ "type": ["integer", "null"], "enum": [0, 1, null] })
+    }
+}
+
+/// Deserializes an array that older data may hold as a single value, such as `Audio.battr`.
+///
+/// Errors of the element type, such as an unknown code, are reported as is.
+pub mod one_or_many {
+    use super::*;
+    use serde::de::{self, IntoDeserializer};
+
+    struct Visitor<T>(std::marker::PhantomData<T>);
+
+    impl<T> Visitor<T> {
+        fn one<'de, D>(v: D) -> Result<Option<Vec<T>>, D::Error>
+        where
+            D: Deserializer<'de>,
+            T: Deserialize<'de>,
+        {
+            T::deserialize(v).map(|v| Some(vec![v]))
+        }
+    }
+
+    impl<'de, T> de::Visitor<'de> for Visitor<T>
+    where
+        T: Deserialize<'de>,
+    {
+        type Value = Option<Vec<T>>;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("a value or an array of values")
+        }
+
+        fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_any(self)
+        }
+
+        fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: de::SeqAccess<'de>,
+        {
+            Vec::deserialize(de::value::SeqAccessDeserializer::new(seq)).map(Some)
+        }
+
+        fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+        where
+            A: de::MapAccess<'de>,
+        {
+            Self::one(de::value::MapAccessDeserializer::new(map))
+        }
+
+        fn visit_bool<E: de::Error>(self, v: bool) -> Result<Self::Value, E> {
+            Self::one(v.into_deserializer())
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+            Self::one(v.into_deserializer())
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            Self::one(v.into_deserializer())
+        }
+
+        fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+            Self::one(v.into_deserializer())
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            Self::one(v.into_deserializer())
+        }
+
+        fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+            Self::one(v.into_deserializer())
+        }
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de>,
+    {
+        deserializer.deserialize_option(Visitor(std::marker::PhantomData))
+    }
+}
+
+/// Deserializes a string that older data may hold as an integer, such as `Content.language`.
+///
+/// An integer does not name a value of the string field (earlier versions of this crate wrote the
+/// index of a language), so it is read as `None`.
+pub mod string_or_i32 {
+    use super::*;
+    use serde::de;
+
+    struct Visitor;
+
+    impl<'de> de::Visitor<'de> for Visitor {
+        type Value = Option<String>;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("a string or an integer")
+        }
+
+        fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_any(self)
+        }
+
+        fn visit_i64<E: de::Error>(self, _: i64) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_u64<E: de::Error>(self, _: u64) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            Ok(Some(v.to_owned()))
+        }
+
+        fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+            Ok(Some(v))
         }
     }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_option(Visitor)
+    }
 }
 
 #[cfg(test)]
@@ -113,4 +321,57 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn one_or_many() -> serde_json::Result<()> {
+        #[derive(serde::Deserialize, Debug, PartialEq)]
+        struct O {
+            #[serde(default, deserialize_with = "crate::serde::one_or_many::deserialize")]
+            v: Option<Vec<i32>>,
+        }
+
+        assert_eq!(serde_json::from_str::<O>(r#"{}"#)?, O { v: None });
+        assert_eq!(serde_json::from_str::<O>(r#"{"v":null}"#)?, O { v: None });
+        assert_eq!(
+            serde_json::from_str::<O>(r#"{"v":3}"#)?,
+            O { v: Some(vec![3]) }
+        );
+        assert_eq!(
+            serde_json::from_str::<O>(r#"{"v":[1,2]}"#)?,
+            O {
+                v: Some(vec![1, 2])
+            }
+        );
+        let err = serde_json::from_str::<O>(r#"{"v":"1"}"#).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("invalid type: string \"1\", expected i32"));
+        let err = serde_json::from_str::<O>(r#"{"v":[1,4294967296]}"#).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("invalid value: integer `4294967296`"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn string_or_i32() -> serde_json::Result<()> {
+        #[derive(serde::Deserialize, Debug, PartialEq)]
+        struct O {
+            #[serde(default, deserialize_with = "crate::serde::string_or_i32::deserialize")]
+            v: Option<String>,
+        }
+
+        assert_eq!(serde_json::from_str::<O>(r#"{}"#)?, O { v: None });
+        assert_eq!(
+            serde_json::from_str::<O>(r#"{"v":"en"}"#)?,
+            O {
+                v: Some("en".into())
+            }
+        );
+        assert_eq!(serde_json::from_str::<O>(r#"{"v":7}"#)?, O { v: None });
+        assert!(serde_json::from_str::<O>(r#"{"v":[]}"#).is_err());
+
+        Ok(())
+    }
 }
@@ -7,6 +7,12 @@
 /// another RTB exchange, a mediation platform, or an ad server combines direct campaigns with 3rd
 /// party demand in decisioning.
 #[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::recommended)
+)]
 pub struct Source {
     /// integer; recommended
     /// Entity responsible for the final impression sale decision, where 0 = exchange, 1 = upstream
@@ -16,6 +22,10 @@ pub struct Source {
         skip_serializing_if = "Option::is_none",
         with = "crate::serde::i32_as_opt_bool"
     )]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(schema_with = "crate::serde::i32_as_opt_bool::schema")
+    )]
     pub fd: Option<bool>,
 
     /// string; recommended
@@ -33,6 +43,7 @@ pub struct Source {
     /// object
     /// Placeholder for exchange-specific extensions to OpenRTB.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::ext))]
     pub ext: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
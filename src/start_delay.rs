@@ -54,6 +54,41 @@ impl<'de> Deserialize<'de> for StartDelay {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for StartDelay {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let v = match u.int_in_range(0..=3)? {
+            0 => StartDelay::MidRoll(u.int_in_range(1..=i32::MAX)?),
+            1 => StartDelay::PreRoll,
+            2 => StartDelay::GenericMidRoll,
+            _ => StartDelay::GenericPostRoll,
+        };
+        Ok(v)
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for StartDelay {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "StartDelay".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "5.12 Start Delay\n\nThe following table lists the various options for \
+                the video or audio start delay. If the start delay value is greater than 0, then \
+                the position is mid-roll and the value indicates the start delay.",
+            "type": "integer",
+            "oneOf": [
+                { "minimum": 1, "description": "Mid-Roll (value indicates start delay in second)" },
+                { "const": 0, "description": "Pre-Roll" },
+                { "const": -1, "description": "Generic Mid-Roll" },
+                { "const": -2, "description": "Generic Post-Roll" }
+            ]
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
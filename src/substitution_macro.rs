@@ -0,0 +1,209 @@
+/// 4.4 Substitution Macros
+///
+/// The win notice URL and its format are defined by the bidder. In order for the exchange to
+/// convey certain information to the winning bidder (e.g., the clearing price), a number of
+/// substitution macros can be inserted into the win notice URL definition.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum SubstitutionMacro {
+    /// ID of the bid request; from BidRequest.id attribute.
+    AuctionId,
+    /// ID of the bid; from BidResponse.bidid attribute.
+    AuctionBidId,
+    /// ID of the impression just won; from imp.id attribute.
+    AuctionImpId,
+    /// ID of the bidder seat for whom the bid was made.
+    AuctionSeatId,
+    /// ID of the ad markup the bidder wishes to serve; from bid.adid attribute.
+    AuctionAdId,
+    /// Clearing price using the same currency and units as the bid.
+    AuctionPrice,
+    /// The currency used in the bid (explicit or implied); for confirmation only.
+    AuctionCurrency,
+    /// Market Bid Ratio defined as: clearance price / bid price.
+    AuctionMbr,
+    /// Loss reason codes. Refer to List 5.25.
+    AuctionLoss,
+}
+
+impl SubstitutionMacro {
+    /// All macros, in the order of the specification.
+    pub const ALL: [Self; 9] = [
+        Self::AuctionId,
+        Self::AuctionBidId,
+        Self::AuctionImpId,
+        Self::AuctionSeatId,
+        Self::AuctionAdId,
+        Self::AuctionPrice,
+        Self::AuctionCurrency,
+        Self::AuctionMbr,
+        Self::AuctionLoss,
+    ];
+
+    /// Returns the name of the macro as written between `${` and `}`, e.g. `AUCTION_PRICE`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::AuctionId => "AUCTION_ID",
+            Self::AuctionBidId => "AUCTION_BID_ID",
+            Self::AuctionImpId => "AUCTION_IMP_ID",
+            Self::AuctionSeatId => "AUCTION_SEAT_ID",
+            Self::AuctionAdId => "AUCTION_AD_ID",
+            Self::AuctionPrice => "AUCTION_PRICE",
+            Self::AuctionCurrency => "AUCTION_CURRENCY",
+            Self::AuctionMbr => "AUCTION_MBR",
+            Self::AuctionLoss => "AUCTION_LOSS",
+        }
+    }
+
+    /// Returns the macro with the given name, e.g. `AUCTION_PRICE`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|v| v.name() == name)
+    }
+}
+
+impl std::fmt::Display for SubstitutionMacro {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "${{{}}}", self.name())
+    }
+}
+
+/// The values an exchange substitutes for each [`SubstitutionMacro`]; `None` leaves the macro in
+/// place.
+///
+/// ```
+/// # use openrtb2::SubstitutionValues;
+/// let values = SubstitutionValues {
+///     auction_id: Some("1".into()),
+///     price: Some(1.5),
+///     ..Default::default()
+/// };
+/// assert_eq!(
+///     values.expand("https://adx.example/win?id=${AUCTION_ID}&p=${AUCTION_PRICE}&l=${AUCTION_LOSS}"),
+///     "https://adx.example/win?id=1&p=1.5&l=${AUCTION_LOSS}",
+/// );
+/// ```
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct SubstitutionValues {
+    /// Value of `${AUCTION_ID}`.
+    pub auction_id: Option<String>,
+
+    /// Value of `${AUCTION_BID_ID}`.
+    pub bid_id: Option<String>,
+
+    /// Value of `${AUCTION_IMP_ID}`.
+    pub imp_id: Option<String>,
+
+    /// Value of `${AUCTION_SEAT_ID}`.
+    pub seat_id: Option<String>,
+
+    /// Value of `${AUCTION_AD_ID}`.
+    pub ad_id: Option<String>,
+
+    /// Value of `${AUCTION_PRICE}`.
+    pub price: Option<f64>,
+
+    /// Value of `${AUCTION_CURRENCY}`.
+    pub currency: Option<String>,
+
+    /// Value of `${AUCTION_MBR}`.
+    pub mbr: Option<f64>,
+
+    /// Value of `${AUCTION_LOSS}`.
+    pub loss: Option<crate::LossReason>,
+}
+
+impl SubstitutionValues {
+    /// Takes the values known from a bid response: everything except the clearing price, market
+    /// bid ratio and loss reason, which are outcomes of the auction.
+    pub fn new(res: &crate::BidResponse, seatbid: &crate::SeatBid, bid: &crate::Bid) -> Self {
+        Self {
+            auction_id: Some(res.id.clone()),
+            bid_id: res.bidid.clone(),
+            imp_id: Some(bid.impid.clone()),
+            seat_id: seatbid.seat.clone(),
+            ad_id: bid.adid.clone(),
+            currency: Some(res.cur.clone()),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the value substituted for a macro.
+    pub fn get(&self, m: SubstitutionMacro) -> Option<String> {
+        match m {
+            SubstitutionMacro::AuctionId => self.auction_id.clone(),
+            SubstitutionMacro::AuctionBidId => self.bid_id.clone(),
+            SubstitutionMacro::AuctionImpId => self.imp_id.clone(),
+            SubstitutionMacro::AuctionSeatId => self.seat_id.clone(),
+            SubstitutionMacro::AuctionAdId => self.ad_id.clone(),
+            SubstitutionMacro::AuctionPrice => self.price.map(|v| v.to_string()),
+            SubstitutionMacro::AuctionCurrency => self.currency.clone(),
+            SubstitutionMacro::AuctionMbr => self.mbr.map(|v| v.to_string()),
+            SubstitutionMacro::AuctionLoss => self.loss.map(|v| v.code().to_string()),
+        }
+    }
+
+    /// Replaces every macro in `s` that has a value. Unknown and encoded (e.g.
+    /// `${AUCTION_PRICE:B64}`) macros are kept as is.
+    pub fn expand(&self, s: &str) -> String {
+        let mut out = String::with_capacity(s.len());
+        let mut rest = s;
+        while let Some(start) = rest.find("${") {
+            out.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let value = rest.find('}').and_then(|end| {
+                let value = SubstitutionMacro::from_name(&rest[2..end]).and_then(|m| self.get(m));
+                value.map(|v| (v, end))
+            });
+            match value {
+                Some((v, end)) => {
+                    out.push_str(&v);
+                    rest = &rest[end + 1..];
+                }
+                None => {
+                    out.push_str("${");
+                    rest = &rest[2..];
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn expand() -> serde_json::Result<()> {
+        let res: crate::BidResponse = serde_json::from_str(
+            r#"{"id":"r","bidid":"b","cur":"EUR","seatbid":[{"seat":"s","bid":[{"id":"1","impid":"i","price":2,"adid":"a"}]}]}"#,
+        )?;
+        let seatbid = &res.seatbid.as_ref().unwrap()[0];
+        let mut values = SubstitutionValues::new(&res, seatbid, &seatbid.bid[0]);
+        assert_eq!(
+            values.expand("${AUCTION_ID}/${AUCTION_BID_ID}/${AUCTION_IMP_ID}/${AUCTION_SEAT_ID}/${AUCTION_AD_ID}/${AUCTION_CURRENCY}"),
+            "r/b/i/s/a/EUR"
+        );
+
+        let s = "p=${AUCTION_PRICE}&m=${AUCTION_MBR}&l=${AUCTION_LOSS}&x=${OTHER}&b=${AUCTION_PRICE:B64}&${";
+        assert_eq!(values.expand(s), s);
+        values.price = Some(1.25);
+        values.mbr = Some(0.625);
+        values.loss = Some(crate::LossReason::LostHigherBid);
+        assert_eq!(
+            values.expand(s),
+            "p=1.25&m=0.625&l=102&x=${OTHER}&b=${AUCTION_PRICE:B64}&${"
+        );
+
+        assert_eq!(
+            SubstitutionMacro::from_name("AUCTION_MBR"),
+            Some(SubstitutionMacro::AuctionMbr)
+        );
+        assert_eq!(
+            SubstitutionMacro::AuctionLoss.to_string(),
+            "${AUCTION_LOSS}"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,334 @@
+/// A targeting expression evaluated against an impression of a bid request.
+///
+/// ```
+/// # use openrtb2::{Predicate, Targeting};
+/// let targeting = Targeting::All(vec![
+///     Targeting::Predicate(Predicate::Country(vec!["USA".into(), "CAN".into()])),
+///     Targeting::Not(Box::new(Targeting::Predicate(Predicate::Bundle(vec![
+///         "com.example.blocked".into(),
+///     ])))),
+/// ]);
+///
+/// let req: openrtb2::BidRequest = serde_json::from_str(
+///     r#"{"id":"1","imp":[{"id":"1"}],"app":{"bundle":"com.example"},"device":{"geo":{"country":"FRA"}}}"#,
+/// )?;
+/// let failure = targeting.evaluate(&req, &req.imp[0]).unwrap_err();
+/// assert_eq!(failure.to_string(), r#"Country(["USA", "CAN"]) does not match"#);
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub enum Targeting {
+    /// Matches if every expression matches; an empty list always matches.
+    All(Vec<Targeting>),
+    /// Matches if any expression matches; an empty list never matches.
+    Any(Vec<Targeting>),
+    /// Matches if the expression does not match.
+    Not(Box<Targeting>),
+    /// Matches if the predicate holds.
+    Predicate(Predicate),
+}
+
+/// A single targeting condition of a [`Targeting`] expression.
+///
+/// Predicates listing values hold if the request has any of them; strings compare ignoring ASCII
+/// case. A predicate on an attribute the request does not carry does not hold.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Predicate {
+    /// `Geo.country` of the device.
+    Country(Vec<String>),
+    /// `Geo.region` of the device.
+    Region(Vec<String>),
+    /// `Geo.metro` of the device.
+    Metro(Vec<String>),
+    /// `Device.devicetype`.
+    DeviceType(Vec<crate::DeviceType>),
+    /// `Device.os`.
+    Os(Vec<String>),
+    /// `Device.connectiontype`.
+    ConnectionType(Vec<crate::ConnectionType>),
+    /// `Site.domain`.
+    Domain(Vec<String>),
+    /// `App.bundle`.
+    Bundle(Vec<String>),
+    /// `Publisher.id` of the site or app.
+    Publisher(Vec<String>),
+    /// Any of `cat`, `sectioncat` and `pagecat` of the site or app, or `Content.cat`.
+    Category(Vec<crate::ContentCategory>),
+    /// `Device.language`, the language of the user's browser or operating system.
+    DeviceLanguage(Vec<String>),
+    /// `Content.language` of the site or app, the language of the content being displayed.
+    ContentLanguage(Vec<String>),
+    /// Any `Segment.id` of `User.data`, restricted to the data provider with the given `Data.id`
+    /// if set.
+    Segment {
+        /// `Data.id` of the provider.
+        data: Option<String>,
+        /// `Segment.id`s.
+        ids: Vec<String>,
+    },
+    /// The banner of the impression accepts a creative of this width and height.
+    Size(i32, i32),
+    /// The video or audio of the impression accepts a creative of this duration in seconds.
+    Duration(i32),
+    /// The video or audio of the impression supports the protocol.
+    Protocol(Vec<crate::Protocol>),
+}
+
+/// Why a [`Targeting`] expression does not match, as returned by [`Targeting::evaluate`].
+#[derive(Debug, PartialEq, Clone)]
+pub enum Failure<'t> {
+    /// The predicate does not hold.
+    Predicate(&'t Predicate),
+    /// The negated expression matches.
+    Not(&'t Targeting),
+    /// None of the alternatives matches; the failure of each.
+    Any(Vec<Failure<'t>>),
+}
+
+impl Targeting {
+    /// Evaluates the expression against `imp` of `req`, returning the first failure of an `All`.
+    pub fn evaluate<'t>(
+        &'t self,
+        req: &crate::BidRequest,
+        imp: &crate::Imp,
+    ) -> Result<(), Failure<'t>> {
+        match self {
+            Self::All(v) => v.iter().try_for_each(|t| t.evaluate(req, imp)),
+            Self::Any(v) => {
+                let mut failures = Vec::with_capacity(v.len());
+                for t in v {
+                    match t.evaluate(req, imp) {
+                        Ok(()) => return Ok(()),
+                        Err(e) => failures.push(e),
+                    }
+                }
+                Err(Failure::Any(failures))
+            }
+            Self::Not(t) => match t.evaluate(req, imp) {
+                Ok(()) => Err(Failure::Not(t)),
+                Err(_) => Ok(()),
+            },
+            Self::Predicate(p) => {
+                if p.holds(req, imp) {
+                    Ok(())
+                } else {
+                    Err(Failure::Predicate(p))
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if the expression matches `imp` of `req`.
+    pub fn matches(&self, req: &crate::BidRequest, imp: &crate::Imp) -> bool {
+        self.evaluate(req, imp).is_ok()
+    }
+}
+
+impl Predicate {
+    /// Returns `true` if the predicate holds for `imp` of `req`.
+    pub fn holds(&self, req: &crate::BidRequest, imp: &crate::Imp) -> bool {
+        let device = req.device.as_ref();
+        let geo = device.and_then(|v| v.geo.as_ref());
+        let site = req.channel.as_ref().and_then(|v| v.as_site());
+        let app = req.channel.as_ref().and_then(|v| v.as_app());
+
+        match self {
+            Self::Country(v) => any_str(v, geo.and_then(|v| v.country.as_deref())),
+            Self::Region(v) => any_str(v, geo.and_then(|v| v.region.as_deref())),
+            Self::Metro(v) => any_str(v, geo.and_then(|v| v.metro.as_deref())),
+            Self::DeviceType(v) => device
+                .and_then(|d| d.devicetype)
+                .is_some_and(|d| v.contains(&d)),
+            Self::Os(v) => any_str(v, device.and_then(|v| v.os.as_deref())),
+            Self::ConnectionType(v) => device
+                .and_then(|d| d.connectiontype)
+                .is_some_and(|d| v.contains(&d)),
+            Self::Domain(v) => any_str(v, site.and_then(|v| v.domain.as_deref())),
+            Self::Bundle(v) => any_str(v, app.and_then(|v| v.bundle.as_deref())),
+            Self::Publisher(v) => {
+                let publisher = site
+                    .and_then(|v| v.publisher.as_ref())
+                    .or_else(|| app.and_then(|v| v.publisher.as_ref()));
+                any_str(v, publisher.and_then(|v| v.id.as_deref()))
+            }
+            Self::Category(v) => {
+                let (cats, content) = match (site, app) {
+                    (Some(s), _) => ([&s.cat, &s.sectioncat, &s.pagecat], s.content.as_ref()),
+                    (_, Some(a)) => ([&a.cat, &a.sectioncat, &a.pagecat], a.content.as_ref()),
+                    _ => return false,
+                };
+                cats.iter()
+                    .copied()
+                    .chain(content.map(|c| &c.cat))
+                    .flatten()
+                    .flatten()
+                    .any(|c| v.contains(c))
+            }
+            Self::DeviceLanguage(v) => any_str(v, device.and_then(|v| v.language.as_deref())),
+            Self::ContentLanguage(v) => {
+                let content = site
+                    .and_then(|v| v.content.as_ref())
+                    .or_else(|| app.and_then(|v| v.content.as_ref()));
+                any_str(v, content.and_then(|v| v.language.as_deref()))
+            }
+            Self::Segment { data, ids } => req
+                .user
+                .iter()
+                .flat_map(|u| u.data.iter().flatten())
+                .filter(|d| data.is_none() || d.id == *data)
+                .flat_map(|d| d.segment.iter().flatten())
+                .any(|s| s.id.as_ref().is_some_and(|id| ids.contains(id))),
+            Self::Size(w, h) => imp.banner.as_ref().is_some_and(|b| b.accepts_size(*w, *h)),
+            Self::Duration(d) => {
+                let video = imp.video.as_ref().map(|v| (v.minduration, v.maxduration));
+                let audio = imp.audio.as_ref().map(|v| (v.minduration, v.maxduration));
+                video.into_iter().chain(audio).any(|(min, max)| {
+                    min.is_none_or(|min| *d >= min) && max.is_none_or(|max| *d <= max)
+                })
+            }
+            Self::Protocol(v) => {
+                let video = imp.video.as_ref().and_then(|v| v.protocols.as_ref());
+                let audio = imp.audio.as_ref().and_then(|v| v.protocols.as_ref());
+                video
+                    .into_iter()
+                    .chain(audio)
+                    .flatten()
+                    .any(|p| v.contains(p))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Failure<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Predicate(p) => write!(f, "{:?} does not match", p),
+            Self::Not(t) => write!(f, "excluded by {:?}", t),
+            Self::Any(v) => {
+                f.write_str("none of: ")?;
+                for (i, e) in v.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str("; ")?;
+                    }
+                    write!(f, "{}", e)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn any_str(values: &[String], value: Option<&str>) -> bool {
+    value.is_some_and(|value| values.iter().any(|v| v.eq_ignore_ascii_case(value)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn evaluate() -> serde_json::Result<()> {
+        let req: crate::BidRequest = serde_json::from_str(
+            r#"{
+                "id": "1",
+                "imp": [
+                    {"id": "1", "banner": {"format": [{"w": 300, "h": 250}]}},
+                    {"id": "2", "video": {"mimes": [], "maxduration": 30, "protocols": [2, 3]}}
+                ],
+                "site": {
+                    "domain": "news.example",
+                    "cat": ["IAB12"],
+                    "publisher": {"id": "p1"},
+                    "content": {"cat": ["IAB17"], "language": "de"}
+                },
+                "device": {
+                    "devicetype": 2,
+                    "os": "Linux",
+                    "connectiontype": 2,
+                    "language": "en",
+                    "geo": {"country": "USA", "region": "CA", "metro": "807"}
+                },
+                "user": {
+                    "data": [
+                        {"id": "dmp1", "segment": [{"id": "s1"}, {"id": "s2"}]},
+                        {"id": "dmp2", "segment": [{"id": "s3"}]}
+                    ]
+                }
+            }"#,
+        )?;
+        let (banner, video) = (&req.imp[0], &req.imp[1]);
+        let holds = |p: Predicate, imp| p.holds(&req, imp);
+        let strs = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let cat = |s: &str| serde_json::from_str::<crate::ContentCategory>(&format!("{:?}", s));
+
+        assert!(holds(Predicate::Country(strs(&["usa"])), banner));
+        assert!(holds(Predicate::Region(strs(&["CA"])), banner));
+        assert!(holds(Predicate::Metro(strs(&["807"])), banner));
+        assert!(holds(
+            Predicate::DeviceType(vec![crate::DeviceType::PersonalComputer]),
+            banner
+        ));
+        assert!(holds(Predicate::Os(strs(&["linux"])), banner));
+        assert!(holds(
+            Predicate::ConnectionType(vec![crate::ConnectionType::WiFi]),
+            banner
+        ));
+        assert!(holds(Predicate::Domain(strs(&["news.example"])), banner));
+        assert!(!holds(Predicate::Bundle(strs(&["news.example"])), banner));
+        assert!(holds(Predicate::Publisher(strs(&["p1"])), banner));
+        assert!(holds(Predicate::Category(vec![cat("IAB17")?]), banner));
+        assert!(!holds(Predicate::Category(vec![cat("IAB1")?]), banner));
+        assert!(holds(Predicate::DeviceLanguage(strs(&["EN"])), banner));
+        assert!(!holds(Predicate::DeviceLanguage(strs(&["de"])), banner));
+        assert!(holds(Predicate::ContentLanguage(strs(&["de"])), banner));
+        assert!(!holds(Predicate::ContentLanguage(strs(&["en"])), banner));
+        assert!(holds(
+            Predicate::Segment {
+                data: None,
+                ids: strs(&["s3"])
+            },
+            banner
+        ));
+        assert!(!holds(
+            Predicate::Segment {
+                data: Some("dmp1".into()),
+                ids: strs(&["s3"])
+            },
+            banner
+        ));
+        assert!(holds(Predicate::Size(300, 250), banner));
+        assert!(!holds(Predicate::Size(300, 250), video));
+        assert!(holds(Predicate::Duration(15), video));
+        assert!(!holds(Predicate::Duration(60), video));
+        assert!(holds(
+            Predicate::Protocol(vec![crate::Protocol::Vast3]),
+            video
+        ));
+
+        let country = Predicate::Country(strs(&["CAN"]));
+        let size = Targeting::Predicate(Predicate::Size(728, 90));
+        let targeting = Targeting::All(vec![
+            Targeting::Predicate(Predicate::Os(strs(&["Linux"]))),
+            Targeting::Any(vec![Targeting::Predicate(country.clone()), size.clone()]),
+        ]);
+        assert_eq!(
+            targeting.evaluate(&req, banner),
+            Err(Failure::Any(vec![
+                Failure::Predicate(&country),
+                Failure::Predicate(&Predicate::Size(728, 90))
+            ]))
+        );
+        assert_eq!(
+            targeting.evaluate(&req, banner).unwrap_err().to_string(),
+            r#"none of: Country(["CAN"]) does not match; Size(728, 90) does not match"#
+        );
+
+        let not = Targeting::Not(Box::new(Targeting::Predicate(Predicate::Size(300, 250))));
+        assert!(not.matches(&req, video));
+        assert!(matches!(not.evaluate(&req, banner), Err(Failure::Not(_))));
+        assert!(Targeting::All(vec![]).matches(&req, banner));
+        assert!(!Targeting::Any(vec![]).matches(&req, banner));
+
+        Ok(())
+    }
+}
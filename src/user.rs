@@ -5,6 +5,12 @@
 /// other privacy policies. However, this user ID must be stable long enough to serve reasonably as
 /// the basis for frequency capping and retargeting.
 #[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::recommended)
+)]
 pub struct User {
     /// string; recommended
     /// Exchange-specific ID for the user. At least one of id or buyeruid is recommended.
@@ -53,8 +59,28 @@ pub struct User {
     /// object
     /// Placeholder for exchange-specific extensions to OpenRTB.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::ext))]
     pub ext: Option<serde_json::Map<String, serde_json::Value>>,
 }
+
+impl User {
+    /// Indexes the segments of `data`.
+    pub fn segments(&self) -> crate::SegmentIndex<'_> {
+        crate::SegmentIndex::new(self.data.as_deref().unwrap_or_default())
+    }
+
+    /// Returns the first `Data` of `provider`, adding one if there is none.
+    pub fn data_mut(&mut self, provider: crate::DataProvider<'_>) -> &mut crate::Data {
+        crate::segment_index::data_mut(&mut self.data, provider)
+    }
+
+    /// Removes the segment with the given id from every `Data` of `provider`. Returns `true` if
+    /// any was removed.
+    pub fn remove_segment(&mut self, provider: crate::DataProvider<'_>, id: &str) -> bool {
+        crate::segment_index::remove_segment(&mut self.data, provider, id)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
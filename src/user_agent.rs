@@ -0,0 +1,474 @@
+use crate::DeviceType;
+
+/// The device properties derived from a User-Agent string by a bundled rule set, as used by
+/// [`Device::enrich_from_ua`].
+///
+/// Rules recognise common phones, tablets, connected TVs, game consoles and desktop operating
+/// systems by tokens in the string; anything else yields no properties. `osv` is the version as
+/// written in the string, e.g. the Windows NT version `10.0`.
+///
+/// ```
+/// # use openrtb2::{DeviceType, UserAgent};
+/// let ua = UserAgent::parse(
+///     "Mozilla/5.0 (Linux; Android 13; SM-S911B Build/TP1A.220624.014) AppleWebKit/537.36 \
+///      (KHTML, like Gecko) Chrome/116.0.0.0 Mobile Safari/537.36",
+/// );
+/// assert_eq!(ua.devicetype, Some(DeviceType::Phone));
+/// assert_eq!(ua.make.as_deref(), Some("Samsung"));
+/// assert_eq!(ua.model.as_deref(), Some("SM-S911B"));
+/// assert_eq!((ua.os.as_deref(), ua.osv.as_deref()), (Some("Android"), Some("13")));
+/// assert_eq!(ua.js, Some(true));
+/// ```
+///
+/// [`Device::enrich_from_ua`]: ./struct.Device.html#method.enrich_from_ua
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct UserAgent {
+    /// The general type of device.
+    pub devicetype: Option<DeviceType>,
+    /// Device make, e.g. `Apple`.
+    pub make: Option<String>,
+    /// Device model, e.g. `iPhone`.
+    pub model: Option<String>,
+    /// Operating system, e.g. `iOS`.
+    pub os: Option<String>,
+    /// Operating system version, e.g. `17.1`.
+    pub osv: Option<String>,
+    /// `true` for browsers, which support JavaScript; unknown otherwise.
+    pub js: Option<bool>,
+}
+
+/// A device recognised by a token in the User-Agent string; empty strings are unknown.
+struct Rule {
+    token: &'static str,
+    devicetype: Option<DeviceType>,
+    make: &'static str,
+    model: &'static str,
+    os: &'static str,
+    /// The text preceding the OS version.
+    osv: &'static str,
+}
+
+const fn rule(
+    token: &'static str,
+    devicetype: Option<DeviceType>,
+    make: &'static str,
+    model: &'static str,
+    os: &'static str,
+    osv: &'static str,
+) -> Rule {
+    Rule {
+        token,
+        devicetype,
+        make,
+        model,
+        os,
+        osv,
+    }
+}
+
+/// The device rules, tried in order; the first whose token occurs in the string applies.
+const RULES: &[Rule] = {
+    use DeviceType::*;
+    &[
+        rule(
+            "Windows Phone",
+            Some(Phone),
+            "",
+            "",
+            "Windows Phone",
+            "Windows Phone ",
+        ),
+        rule("iPad", Some(Tablet), "Apple", "iPad", "iOS", "OS "),
+        rule("iPhone", Some(Phone), "Apple", "iPhone", "iOS", "OS "),
+        rule(
+            "iPod",
+            Some(ConnectedDevice),
+            "Apple",
+            "iPod touch",
+            "iOS",
+            "OS ",
+        ),
+        rule(
+            "AppleTV",
+            Some(ConnectedTv),
+            "Apple",
+            "Apple TV",
+            "tvOS",
+            "OS ",
+        ),
+        rule(
+            "Apple TV",
+            Some(ConnectedTv),
+            "Apple",
+            "Apple TV",
+            "tvOS",
+            "OS ",
+        ),
+        rule("Xbox", Some(ConnectedTv), "Microsoft", "Xbox", "", ""),
+        rule(
+            "PlayStation",
+            Some(ConnectedTv),
+            "Sony",
+            "PlayStation",
+            "",
+            "",
+        ),
+        rule("Roku", Some(ConnectedTv), "Roku", "", "Roku", "Roku/DVP-"),
+        rule(
+            "SMART-TV",
+            Some(ConnectedTv),
+            "Samsung",
+            "",
+            "Tizen",
+            "Tizen ",
+        ),
+        rule("Web0S", Some(ConnectedTv), "LG", "", "webOS", ""),
+        rule("; AFT", Some(ConnectedTv), "Amazon", "", "Fire OS", ""),
+        rule(
+            "Android TV",
+            Some(ConnectedTv),
+            "",
+            "",
+            "Android",
+            "Android ",
+        ),
+        rule("GoogleTV", Some(ConnectedTv), "", "", "Android", "Android "),
+        // TVs and streaming devices whose strings otherwise read as Android or Linux.
+        rule("HbbTV", Some(ConnectedTv), "", "", "", ""),
+        rule("CrKey", Some(ConnectedTv), "Google", "Chromecast", "", ""),
+        rule(
+            "BRAVIA",
+            Some(ConnectedTv),
+            "Sony",
+            "",
+            "Android",
+            "Android ",
+        ),
+        rule("SmartTV", Some(ConnectedTv), "", "", "", ""),
+        // Phone or tablet, see `android`.
+        rule("Android", None, "", "", "Android", "Android "),
+        rule("CrOS", Some(PersonalComputer), "", "", "Chrome OS", ""),
+        rule(
+            "Macintosh",
+            Some(PersonalComputer),
+            "Apple",
+            "Mac",
+            "macOS",
+            "Mac OS X ",
+        ),
+        rule(
+            "Windows NT",
+            Some(PersonalComputer),
+            "",
+            "",
+            "Windows",
+            "Windows NT ",
+        ),
+        rule("Linux", Some(PersonalComputer), "", "", "Linux", ""),
+    ]
+};
+
+/// Makes of Android devices by model prefix, compared ignoring ASCII case.
+const ANDROID_MAKES: &[(&str, &str)] = &[
+    ("CPH", "OPPO"),
+    ("GT-", "Samsung"),
+    ("HUAWEI", "Huawei"),
+    ("KF", "Amazon"),
+    ("LG-", "LG"),
+    ("LM-", "LG"),
+    ("Mi ", "Xiaomi"),
+    ("moto", "Motorola"),
+    ("Nexus", "Google"),
+    ("Nokia", "Nokia"),
+    ("ONEPLUS", "OnePlus"),
+    ("Pixel", "Google"),
+    ("POCO", "Xiaomi"),
+    ("Redmi", "Xiaomi"),
+    ("SAMSUNG", "Samsung"),
+    ("SM-", "Samsung"),
+    ("vivo", "vivo"),
+];
+
+impl UserAgent {
+    /// Parses a User-Agent string.
+    pub fn parse(ua: &str) -> Self {
+        let mut v = Self::default();
+        if let Some(rule) = RULES.iter().find(|r| ua.contains(r.token)) {
+            v.devicetype = rule.devicetype;
+            let known = |s: &str| (!s.is_empty()).then(|| s.to_owned());
+            v.make = known(rule.make);
+            v.model = known(rule.model);
+            v.os = known(rule.os);
+            v.osv = known(rule.osv).and_then(|prefix| version(ua, &prefix));
+            if rule.token == "Android" {
+                android(ua, &mut v);
+            }
+        }
+        if ua.starts_with("Mozilla/") {
+            v.js = Some(true);
+        }
+        v
+    }
+}
+
+/// Returns the version following `prefix` in `ua`, with `_` read as `.`, e.g. `17.1` for
+/// `OS 17_1 like Mac OS X` and the prefix `OS `.
+fn version(ua: &str, prefix: &str) -> Option<String> {
+    let start = ua.find(prefix)? + prefix.len();
+    let rest = &ua[start..];
+    let end = rest
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '_'))
+        .unwrap_or(rest.len());
+    let v = rest[..end].replace('_', ".");
+    let v = v.trim_end_matches('.');
+    (!v.is_empty()).then(|| v.to_owned())
+}
+
+/// Sets the type, model and make of an Android device. Android browsers mark phones with
+/// `Mobile`; the model is the comment entry before `Build/`, or after the Android version.
+fn android(ua: &str, v: &mut UserAgent) {
+    v.devicetype = Some(if ua.contains("Mobile") {
+        DeviceType::Phone
+    } else {
+        DeviceType::Tablet
+    });
+
+    let comment = ua
+        .find('(')
+        .and_then(|i| ua[i + 1..].find(')').map(|j| &ua[i + 1..i + 1 + j]));
+    let entries: Vec<_> = comment
+        .into_iter()
+        .flat_map(|c| c.split(';'))
+        .map(str::trim)
+        .collect();
+    let model = entries
+        .iter()
+        .find_map(|e| e.find(" Build/").map(|i| &e[..i]))
+        .or_else(|| {
+            let i = entries.iter().position(|e| e.starts_with("Android"))?;
+            entries.get(i + 1).copied()
+        })
+        .map(str::trim)
+        // Browsers with a reduced User-Agent send `K` in place of the model.
+        .filter(|m| !m.is_empty() && *m != "K" && !m.starts_with("wv"));
+    if let Some(model) = model {
+        v.make = ANDROID_MAKES
+            .iter()
+            .find(|(prefix, _)| {
+                model
+                    .get(..prefix.len())
+                    .is_some_and(|p| p.eq_ignore_ascii_case(prefix))
+            })
+            .map(|(_, make)| (*make).to_owned());
+        v.model = Some(model.to_owned());
+    }
+}
+
+/// Fills the missing fields of `device` from its `ua` and records the names of the filled fields
+/// in `ext.ua_inferred`. Returns the names of the filled fields.
+pub(crate) fn enrich(device: &mut crate::Device) -> Vec<&'static str> {
+    let ua = match device.ua.as_deref() {
+        Some(ua) => UserAgent::parse(ua),
+        None => return Vec::new(),
+    };
+
+    fn fill<T>(field: &mut Option<T>, value: Option<T>) -> bool {
+        let filled = field.is_none() && value.is_some();
+        if filled {
+            *field = value;
+        }
+        filled
+    }
+
+    let mut filled = Vec::new();
+    let fields = [
+        ("devicetype", fill(&mut device.devicetype, ua.devicetype)),
+        ("make", fill(&mut device.make, ua.make)),
+        ("model", fill(&mut device.model, ua.model)),
+        ("os", fill(&mut device.os, ua.os)),
+        ("osv", fill(&mut device.osv, ua.osv)),
+        ("js", fill(&mut device.js, ua.js)),
+    ];
+    for (name, _) in fields.iter().filter(|(_, filled)| *filled) {
+        filled.push(*name);
+    }
+
+    if !filled.is_empty() {
+        let ext = device.ext.get_or_insert_with(Default::default);
+        let inferred = ext
+            .entry("ua_inferred")
+            .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+        if !inferred.is_array() {
+            *inferred = serde_json::Value::Array(Vec::new());
+        }
+        if let serde_json::Value::Array(names) = inferred {
+            for name in &filled {
+                if !names.iter().any(|v| v == name) {
+                    names.push((*name).into());
+                }
+            }
+        }
+    }
+    filled
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type Parsed = (
+        Option<DeviceType>,
+        Option<String>,
+        Option<String>,
+        Option<String>,
+        Option<String>,
+    );
+
+    fn parse(ua: &str) -> Parsed {
+        let v = UserAgent::parse(ua);
+        (v.devicetype, v.make, v.model, v.os, v.osv)
+    }
+
+    fn some(v: &str) -> Option<String> {
+        Some(v.to_owned())
+    }
+
+    #[test]
+    fn rules() {
+        use DeviceType::*;
+
+        assert_eq!(
+            parse("Mozilla/5.0 (iPhone; CPU iPhone OS 17_1_2 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.1 Mobile/15E148 Safari/604.1"),
+            (Some(Phone), some("Apple"), some("iPhone"), some("iOS"), some("17.1.2"))
+        );
+        assert_eq!(
+            parse("Mozilla/5.0 (iPad; CPU OS 16_6 like Mac OS X) AppleWebKit/605.1.15"),
+            (
+                Some(Tablet),
+                some("Apple"),
+                some("iPad"),
+                some("iOS"),
+                some("16.6")
+            )
+        );
+        assert_eq!(
+            parse("Mozilla/5.0 (Linux; Android 12; Pixel 6 Build/SD1A.210817.036; wv) AppleWebKit/537.36 (KHTML, like Gecko) Version/4.0 Chrome/119.0.6045.163 Mobile Safari/537.36"),
+            (Some(Phone), some("Google"), some("Pixel 6"), some("Android"), some("12"))
+        );
+        assert_eq!(
+            parse("Mozilla/5.0 (Linux; Android 10; K) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36"),
+            (Some(Tablet), None, None, some("Android"), some("10"))
+        );
+        assert_eq!(
+            parse("Mozilla/5.0 (Linux; Android 9; AFTMM Build/PS7233) AppleWebKit/537.36"),
+            (
+                Some(ConnectedTv),
+                some("Amazon"),
+                None,
+                some("Fire OS"),
+                None
+            )
+        );
+        assert_eq!(
+            parse("Roku/DVP-12.0 (12.0.0.4182-88)"),
+            (
+                Some(ConnectedTv),
+                some("Roku"),
+                None,
+                some("Roku"),
+                some("12.0")
+            )
+        );
+        assert_eq!(
+            parse("Mozilla/5.0 (SMART-TV; LINUX; Tizen 6.0) AppleWebKit/537.36 (KHTML, like Gecko) 76.0.3809.146/6.0 TV Safari/537.36"),
+            (Some(ConnectedTv), some("Samsung"), None, some("Tizen"), some("6.0"))
+        );
+        assert_eq!(
+            parse("Mozilla/5.0 (Windows NT 10.0; Win64; x64; Xbox; Xbox One) AppleWebKit/537.36"),
+            (
+                Some(ConnectedTv),
+                some("Microsoft"),
+                some("Xbox"),
+                None,
+                None
+            )
+        );
+        assert_eq!(
+            parse("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15"),
+            (
+                Some(PersonalComputer),
+                some("Apple"),
+                some("Mac"),
+                some("macOS"),
+                some("10.15.7")
+            )
+        );
+        assert_eq!(
+            parse("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36"),
+            (
+                Some(PersonalComputer),
+                None,
+                None,
+                some("Windows"),
+                some("10.0")
+            )
+        );
+        assert_eq!(
+            parse("Mozilla/5.0 (Linux armv7l) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/79.0.3945.79 Safari/537.36 OPR/66.0.3472.0 HbbTV/1.5.1 (+DRM; Philips; 55OLED806; TPM201E_R.107.001.233.001; _TV_NT72690_2021; 14.120.0.0.0)"),
+            (Some(ConnectedTv), None, None, None, None)
+        );
+        assert_eq!(
+            parse("Mozilla/5.0 (X11; Linux armv7l) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.225 Safari/537.36 CrKey/1.56.500000 DeviceType/Chromecast"),
+            (Some(ConnectedTv), some("Google"), some("Chromecast"), None, None)
+        );
+        assert_eq!(
+            parse("Mozilla/5.0 (Linux; Android 9; BRAVIA 4K GB Build/PTT1.190515.001.S52) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.120 Safari/537.36"),
+            (Some(ConnectedTv), some("Sony"), None, some("Android"), some("9"))
+        );
+        assert_eq!(
+            parse("Mozilla/5.0 (Linux; U; Android 4.2.2; SmartTV Build/JDQ39) AppleWebKit/534.30"),
+            (Some(ConnectedTv), None, None, None, None)
+        );
+        assert_eq!(
+            parse("Mozilla/5.0 (X11; Linux x86_64; rv:109.0) Gecko/20100101 Firefox/119.0"),
+            (Some(PersonalComputer), None, None, some("Linux"), None)
+        );
+        assert_eq!(UserAgent::parse("curl/8.4.0"), UserAgent::default());
+        assert_eq!(UserAgent::parse("Roku/DVP-12.0").js, None);
+    }
+
+    #[test]
+    fn enrich() -> serde_json::Result<()> {
+        let mut device: crate::Device = serde_json::from_str(
+            r#"{
+                "ua": "Mozilla/5.0 (iPhone; CPU iPhone OS 17_1 like Mac OS X)",
+                "ip": "192.0.2.1",
+                "os": "iPhone OS",
+                "js": 0,
+                "ext": {"ua_inferred": ["osv"]}
+            }"#,
+        )?;
+        assert_eq!(
+            device.enrich_from_ua(),
+            vec!["devicetype", "make", "model", "osv"]
+        );
+        assert_eq!(
+            serde_json::to_value(&device)?,
+            serde_json::json!({
+                "ua": "Mozilla/5.0 (iPhone; CPU iPhone OS 17_1 like Mac OS X)",
+                "ip": "192.0.2.1",
+                "devicetype": 4,
+                "make": "Apple",
+                "model": "iPhone",
+                "os": "iPhone OS",
+                "osv": "17.1",
+                "js": 0,
+                "ext": {"ua_inferred": ["osv", "devicetype", "make", "model"]}
+            })
+        );
+        assert!(device.enrich_from_ua().is_empty());
+        assert!(crate::Device::default().enrich_from_ua().is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,1404 @@
+//! Typed VAST 2.0 – 4.2 documents and checks against the [`Video`] and [`Audio`] objects of a bid
+//! request.
+//!
+//! ```
+//! use openrtb2::vast::{Mismatch, Vast};
+//!
+//! let xml = r#"<VAST version="3.0"><Ad><InLine>
+//!     <AdSystem>Acme</AdSystem><AdTitle>Sample</AdTitle>
+//!     <Impression><![CDATA[https://acme.example/imp]]></Impression>
+//!     <Creatives><Creative><Linear>
+//!         <Duration>00:00:30</Duration>
+//!         <MediaFiles>
+//!             <MediaFile delivery="progressive" type="video/mp4" width="640" height="480">
+//!                 <![CDATA[https://acme.example/video.mp4]]>
+//!             </MediaFile>
+//!         </MediaFiles>
+//!     </Linear></Creative></Creatives>
+//! </InLine></Ad></VAST>"#;
+//! let vast: Vast = xml.parse()?;
+//!
+//! let video: openrtb2::Video = serde_json::from_str(
+//!     r#"{"mimes":["video/mp4"],"maxduration":15,"protocols":[2,3]}"#,
+//! )?;
+//! assert_eq!(
+//!     vast.check_video(&video),
+//!     vec![Mismatch::Duration(std::time::Duration::from_secs(30))],
+//! );
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [`Video`]: ../struct.Video.html
+//! [`Audio`]: ../struct.Audio.html
+
+use std::time::Duration;
+
+use roxmltree::Node;
+
+/// The root `<VAST>` element.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Vast {
+    /// The `version` attribute.
+    pub version: Version,
+
+    /// The `<Ad>` elements; more than one forms an ad pod.
+    pub ads: Vec<Ad>,
+
+    /// Error URIs of a document without ads ("no ad" response).
+    pub errors: Vec<String>,
+}
+
+/// The VAST versions this module understands.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Version {
+    /// VAST 2.0
+    V2_0,
+    /// VAST 3.0
+    V3_0,
+    /// VAST 4.0
+    V4_0,
+    /// VAST 4.1
+    V4_1,
+    /// VAST 4.2
+    V4_2,
+}
+
+/// An `<Ad>` element.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Ad {
+    /// The `id` attribute.
+    pub id: Option<String>,
+
+    /// The `sequence` attribute; the position of the ad within a pod.
+    pub sequence: Option<i32>,
+
+    /// The content of the ad.
+    pub kind: AdKind,
+}
+
+/// The content of an [`Ad`].
+#[derive(Debug, PartialEq, Clone)]
+pub enum AdKind {
+    /// An `<InLine>` ad carrying everything needed to play it.
+    InLine(InLine),
+    /// A `<Wrapper>` ad pointing to another VAST document.
+    Wrapper(Wrapper),
+}
+
+/// An `<InLine>` element.
+#[derive(Debug, PartialEq, Clone)]
+pub struct InLine {
+    /// The `<AdSystem>` element.
+    pub ad_system: String,
+
+    /// The `<AdTitle>` element.
+    pub ad_title: String,
+
+    /// The `<Impression>` URIs.
+    pub impressions: Vec<String>,
+
+    /// The `<Error>` URIs.
+    pub errors: Vec<String>,
+
+    /// The `<Creatives>`.
+    pub creatives: Vec<Creative>,
+}
+
+/// A `<Wrapper>` element.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Wrapper {
+    /// The `<AdSystem>` element.
+    pub ad_system: String,
+
+    /// The `<VASTAdTagURI>` of the wrapped document.
+    pub vast_ad_tag_uri: String,
+
+    /// The `<Impression>` URIs.
+    pub impressions: Vec<String>,
+
+    /// The `<Error>` URIs.
+    pub errors: Vec<String>,
+
+    /// The `<Creatives>`; in a wrapper they only carry tracking.
+    pub creatives: Vec<Creative>,
+}
+
+/// A `<Creative>` element.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Creative {
+    /// The `id` attribute.
+    pub id: Option<String>,
+
+    /// The `sequence` attribute.
+    pub sequence: Option<i32>,
+
+    /// The `adId` attribute.
+    pub ad_id: Option<String>,
+
+    /// The content of the creative.
+    pub kind: CreativeKind,
+}
+
+/// The content of a [`Creative`].
+#[derive(Debug, PartialEq, Clone)]
+pub enum CreativeKind {
+    /// A `<Linear>` creative.
+    Linear(Linear),
+    /// A `<NonLinearAds>` creative.
+    NonLinear(NonLinearAds),
+    /// A `<CompanionAds>` creative.
+    Companion(Vec<Companion>),
+}
+
+/// A `<Linear>` element.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Linear {
+    /// The `<Duration>`; absent in wrappers.
+    pub duration: Option<Duration>,
+
+    /// The `skipoffset` attribute (VAST 3.0+), either `HH:MM:SS[.mmm]` or a percentage.
+    pub skipoffset: Option<String>,
+
+    /// The `<MediaFiles>`.
+    pub media_files: Vec<MediaFile>,
+
+    /// The `<InteractiveCreativeFile>` elements (VAST 4.0+).
+    pub interactive_creative_files: Vec<InteractiveCreativeFile>,
+
+    /// The `<TrackingEvents>`.
+    pub tracking_events: Vec<Tracking>,
+
+    /// The `<ClickThrough>` URI.
+    pub click_through: Option<String>,
+
+    /// The `<ClickTracking>` URIs.
+    pub click_tracking: Vec<String>,
+}
+
+/// A `<MediaFile>` element.
+#[derive(Debug, PartialEq, Clone)]
+pub struct MediaFile {
+    /// The URI of the file.
+    pub url: String,
+
+    /// The `type` attribute, a MIME type.
+    pub mime_type: String,
+
+    /// The `delivery` attribute, `progressive` or `streaming`.
+    pub delivery: Option<String>,
+
+    /// The `width` attribute in pixels.
+    pub width: Option<u32>,
+
+    /// The `height` attribute in pixels.
+    pub height: Option<u32>,
+
+    /// The `bitrate` attribute in Kbps.
+    pub bitrate: Option<u32>,
+
+    /// The `apiFramework` attribute, e.g. `VPAID`.
+    pub api_framework: Option<String>,
+}
+
+/// An `<InteractiveCreativeFile>` element.
+#[derive(Debug, PartialEq, Clone)]
+pub struct InteractiveCreativeFile {
+    /// The URI of the file.
+    pub url: String,
+
+    /// The `type` attribute, a MIME type.
+    pub mime_type: Option<String>,
+
+    /// The `apiFramework` attribute, e.g. `SIMID`.
+    pub api_framework: Option<String>,
+}
+
+/// A `<Tracking>` element.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Tracking {
+    /// The `event` attribute, e.g. `start`.
+    pub event: String,
+
+    /// The `offset` attribute of a `progress` event.
+    pub offset: Option<String>,
+
+    /// The tracking URI.
+    pub url: String,
+}
+
+/// A `<NonLinearAds>` element.
+#[derive(Debug, PartialEq, Clone)]
+pub struct NonLinearAds {
+    /// The `<NonLinear>` elements.
+    pub non_linears: Vec<NonLinear>,
+
+    /// The `<TrackingEvents>`.
+    pub tracking_events: Vec<Tracking>,
+}
+
+/// A `<NonLinear>` element.
+#[derive(Debug, PartialEq, Clone)]
+pub struct NonLinear {
+    /// The `id` attribute.
+    pub id: Option<String>,
+
+    /// The `width` attribute in pixels.
+    pub width: Option<u32>,
+
+    /// The `height` attribute in pixels.
+    pub height: Option<u32>,
+
+    /// The `minSuggestedDuration` attribute.
+    pub min_suggested_duration: Option<Duration>,
+
+    /// The `apiFramework` attribute.
+    pub api_framework: Option<String>,
+}
+
+/// A `<Companion>` element.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Companion {
+    /// The `id` attribute.
+    pub id: Option<String>,
+
+    /// The `width` attribute in pixels.
+    pub width: Option<u32>,
+
+    /// The `height` attribute in pixels.
+    pub height: Option<u32>,
+
+    /// The `apiFramework` attribute.
+    pub api_framework: Option<String>,
+}
+
+/// A reason why a VAST document cannot be served to a [`Video`] or [`Audio`] impression.
+///
+/// [`Video`]: ../struct.Video.html
+/// [`Audio`]: ../struct.Audio.html
+#[derive(Debug, PartialEq, Clone)]
+pub enum Mismatch {
+    /// The version and ad type (inline or wrapper) are not among the supported protocols.
+    Protocol(crate::Protocol),
+    /// None of the media files of a linear creative has a supported MIME type; holds the MIME
+    /// types offered.
+    MimeType(Vec<String>),
+    /// The duration of a linear creative is outside `minduration` and `maxduration` (extended by
+    /// `maxextended`).
+    Duration(Duration),
+    /// A creative requires an API framework of List 5.6 that is not supported; frameworks the
+    /// list does not know, e.g. `SIMID`, are not checked.
+    ApiFramework(String),
+    /// An inline ad has no creative of the required linearity.
+    Linearity(crate::VideoLinearity),
+}
+
+/// An error returned when parsing a [`Vast`] document.
+#[derive(Debug)]
+pub enum Error {
+    /// The input is not well-formed XML.
+    Xml(roxmltree::Error),
+    /// The root element is not `<VAST>`.
+    NotVast,
+    /// The `version` attribute is missing or outside 2.0 – 4.2.
+    UnsupportedVersion(Option<String>),
+    /// A required element or attribute is absent, e.g. `Wrapper/VASTAdTagURI`.
+    Missing(&'static str),
+    /// An element or attribute holds an invalid value.
+    Invalid {
+        /// Name of the element or attribute, e.g. `Linear/Duration`.
+        name: &'static str,
+        /// The value as received.
+        value: String,
+    },
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Xml(e) => write!(f, "{}", e),
+            Self::NotVast => f.write_str("root element is not VAST"),
+            Self::UnsupportedVersion(Some(v)) => write!(f, "unsupported VAST version: {}", v),
+            Self::UnsupportedVersion(None) => f.write_str("missing VAST version"),
+            Self::Missing(name) => write!(f, "missing {}", name),
+            Self::Invalid { name, value } => write!(f, "invalid {}: {}", name, value),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Xml(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// The exchange's additions to a wrapper built by [`wrap_bid`].
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct Trackers {
+    /// The `<AdSystem>` of the wrapper, naming the exchange.
+    pub ad_system: String,
+
+    /// `<Impression>` URIs.
+    pub impressions: Vec<String>,
+
+    /// `<Error>` URIs.
+    pub errors: Vec<String>,
+
+    /// Linear `<Tracking>` events.
+    pub tracking_events: Vec<Tracking>,
+}
+
+/// An error returned by [`wrap_bid`].
+#[derive(Debug)]
+pub enum WrapError {
+    /// The bid has neither `adm` nor `nurl`.
+    NoMarkup,
+    /// The `adm` is not a VAST document.
+    Vast(Error),
+}
+
+impl std::fmt::Display for WrapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoMarkup => f.write_str("bid has neither adm nor nurl"),
+            Self::Vast(e) => write!(f, "adm is not a VAST document: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for WrapError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::NoMarkup => None,
+            Self::Vast(e) => Some(e),
+        }
+    }
+}
+
+impl std::str::FromStr for Vast {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Version {
+    /// Returns the value of the `version` attribute, e.g. `4.2`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::V2_0 => "2.0",
+            Self::V3_0 => "3.0",
+            Self::V4_0 => "4.0",
+            Self::V4_1 => "4.1",
+            Self::V4_2 => "4.2",
+        }
+    }
+
+    /// Returns the version of a List 5.8 VAST protocol; VAST 1.0 and DAAST are not supported.
+    pub fn from_protocol(protocol: crate::Protocol) -> Option<Self> {
+        use crate::Protocol::*;
+        match protocol {
+            Vast2 | Vast2Wrapper => Some(Self::V2_0),
+            Vast3 | Vast3Wrapper => Some(Self::V3_0),
+            Vast4 | Vast4Wrapper => Some(Self::V4_0),
+            _ => None,
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        let mut parts = s.trim().split('.');
+        let major = parts.next()?.parse::<u32>().ok()?;
+        let minor = parts.next().map_or(Some(0), |s| s.parse::<u32>().ok())?;
+        Some(match (major, minor) {
+            (2, 0) => Self::V2_0,
+            (3, 0) => Self::V3_0,
+            (4, 0) => Self::V4_0,
+            (4, 1) => Self::V4_1,
+            (4, 2) => Self::V4_2,
+            _ => return None,
+        })
+    }
+
+    /// Returns the List 5.8 protocol of an inline or wrapper ad of this version. VAST 4.1 and 4.2
+    /// have no code of their own and map to VAST 4.0.
+    pub fn protocol(self, wrapper: bool) -> crate::Protocol {
+        use crate::Protocol::*;
+        match (self, wrapper) {
+            (Self::V2_0, false) => Vast2,
+            (Self::V2_0, true) => Vast2Wrapper,
+            (Self::V3_0, false) => Vast3,
+            (Self::V3_0, true) => Vast3Wrapper,
+            (_, false) => Vast4,
+            (_, true) => Vast4Wrapper,
+        }
+    }
+}
+
+impl Ad {
+    /// Returns `true` if this is a wrapper ad.
+    pub fn is_wrapper(&self) -> bool {
+        matches!(self.kind, AdKind::Wrapper(_))
+    }
+
+    /// Returns the creatives of the ad.
+    pub fn creatives(&self) -> &[Creative] {
+        match &self.kind {
+            AdKind::InLine(v) => &v.creatives,
+            AdKind::Wrapper(v) => &v.creatives,
+        }
+    }
+}
+
+/// The constraints shared by [`Video`] and [`Audio`].
+///
+/// [`Video`]: ../struct.Video.html
+/// [`Audio`]: ../struct.Audio.html
+struct Constraints<'a> {
+    mimes: &'a [String],
+    minduration: Option<i32>,
+    maxduration: Option<i32>,
+    maxextended: crate::MaxExtendedAdDuration,
+    protocols: Option<&'a [crate::Protocol]>,
+    api: &'a [crate::ApiFramework],
+    linearity: Option<crate::VideoLinearity>,
+}
+
+impl Vast {
+    /// Parses a VAST document.
+    pub fn parse(xml: &str) -> Result<Self, Error> {
+        let doc = roxmltree::Document::parse(xml).map_err(Error::Xml)?;
+        let root = doc.root_element();
+        if root.tag_name().name() != "VAST" {
+            return Err(Error::NotVast);
+        }
+        let version = root.attribute("version");
+        let version = version
+            .and_then(Version::parse)
+            .ok_or_else(|| Error::UnsupportedVersion(version.map(Into::into)))?;
+
+        Ok(Self {
+            version,
+            ads: elements(root, "Ad")
+                .map(parse_ad)
+                .collect::<Result<_, _>>()?,
+            errors: texts(root, "Error"),
+        })
+    }
+
+    /// Checks the document against a video impression, returning every way in which it does not
+    /// conform.
+    ///
+    /// Empty `mimes` and absent `protocols` are not checked. An absent `api` means no API framework
+    /// is supported; frameworks without a code in List 5.6, e.g. `SIMID`, are not checked. Media
+    /// files, durations and linearity of wrapper ads are only known once the wrapped document is
+    /// fetched, so wrappers are checked for their protocol only.
+    pub fn check_video(&self, video: &crate::Video) -> Vec<Mismatch> {
+        self.check(Constraints {
+            mimes: &video.mimes,
+            minduration: video.minduration,
+            maxduration: video.maxduration,
+            maxextended: video.maxextended,
+            protocols: video.protocols.as_deref(),
+            api: video.api.as_deref().unwrap_or_default(),
+            linearity: video.linearity,
+        })
+    }
+
+    /// Checks the document against an audio impression, returning every way in which it does not
+    /// conform. See [`check_video`](#method.check_video).
+    pub fn check_audio(&self, audio: &crate::Audio) -> Vec<Mismatch> {
+        self.check(Constraints {
+            mimes: &audio.mimes,
+            minduration: audio.minduration,
+            maxduration: audio.maxduration,
+            maxextended: audio.maxextended.unwrap_or_default(),
+            protocols: audio.protocols.as_deref(),
+            api: audio.api.as_deref().unwrap_or_default(),
+            linearity: Some(crate::VideoLinearity::Linear),
+        })
+    }
+
+    fn check(&self, c: Constraints<'_>) -> Vec<Mismatch> {
+        let mut out = Vec::new();
+        let mut push = |m: Mismatch| {
+            if !out.contains(&m) {
+                out.push(m);
+            }
+        };
+
+        for ad in &self.ads {
+            let protocol = self.version.protocol(ad.is_wrapper());
+            if c.protocols.is_some_and(|v| !v.contains(&protocol)) {
+                push(Mismatch::Protocol(protocol));
+            }
+            if ad.is_wrapper() {
+                continue;
+            }
+
+            if let Some(linearity) = c.linearity {
+                let found = ad.creatives().iter().any(|cr| {
+                    matches!(
+                        (&cr.kind, linearity),
+                        (CreativeKind::Linear(_), crate::VideoLinearity::Linear)
+                            | (CreativeKind::NonLinear(_), crate::VideoLinearity::NonLinear)
+                    )
+                });
+                if !found {
+                    push(Mismatch::Linearity(linearity));
+                }
+            }
+
+            for creative in ad.creatives() {
+                let frameworks: Vec<&str> = match &creative.kind {
+                    CreativeKind::Linear(linear) => {
+                        check_linear(linear, &c).into_iter().for_each(&mut push);
+                        linear
+                            .media_files
+                            .iter()
+                            .filter_map(|v| v.api_framework.as_deref())
+                            .chain(
+                                linear
+                                    .interactive_creative_files
+                                    .iter()
+                                    .filter_map(|v| v.api_framework.as_deref()),
+                            )
+                            .collect()
+                    }
+                    CreativeKind::NonLinear(v) => v
+                        .non_linears
+                        .iter()
+                        .filter_map(|v| v.api_framework.as_deref())
+                        .collect(),
+                    CreativeKind::Companion(v) => v
+                        .iter()
+                        .filter_map(|v| v.api_framework.as_deref())
+                        .collect(),
+                };
+                for framework in frameworks {
+                    if supports_framework(c.api, framework) == Some(false) {
+                        push(Mismatch::ApiFramework(framework.to_owned()));
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Wraps the VAST of a bid in an exchange wrapper carrying `trackers`, and sets the wrapper as the
+/// new `adm`. Returns the wrapper document.
+///
+/// When the bid carries its VAST in `adm`, the markup is handed to `host`, which must make it
+/// available at the URI it returns (e.g., in an ad cache). Otherwise the wrapper references `nurl`,
+/// which serves the markup on win; the exchange must then not call `nurl` separately. Macros in
+/// `nurl`, the hosted markup and the tracker URIs are expanded with `values`.
+///
+/// The wrapper is written in the version of the wrapped document. When only `nurl` is available,
+/// the version is taken from `Bid.protocol`, falling back to VAST 2.0; `Bid.protocol`, if present,
+/// is updated to the matching wrapper protocol.
+///
+/// ```
+/// # use openrtb2::{vast::{self, Trackers}, Bid, SubstitutionValues};
+/// let mut bid = Bid {
+///     nurl: Some("https://dsp.example/win?p=${AUCTION_PRICE}".into()),
+///     ..Default::default()
+/// };
+/// let trackers = Trackers {
+///     ad_system: "Exchange".into(),
+///     impressions: vec!["https://adx.example/imp?p=${AUCTION_PRICE}".into()],
+///     ..Default::default()
+/// };
+/// let values = SubstitutionValues {
+///     price: Some(1.5),
+///     ..Default::default()
+/// };
+/// let wrapper = vast::wrap_bid(&mut bid, &trackers, &values, |_| unreachable!())?;
+///
+/// match &wrapper.ads[0].kind {
+///     vast::AdKind::Wrapper(v) => {
+///         assert_eq!(v.vast_ad_tag_uri, "https://dsp.example/win?p=1.5");
+///         assert_eq!(v.impressions, vec!["https://adx.example/imp?p=1.5"]);
+///     }
+///     _ => unreachable!(),
+/// }
+/// assert!(bid.adm.unwrap().contains("<VASTAdTagURI><![CDATA[https://dsp.example/win?p=1.5]]>"));
+/// # Ok::<(), vast::WrapError>(())
+/// ```
+pub fn wrap_bid(
+    bid: &mut crate::Bid,
+    trackers: &Trackers,
+    values: &crate::SubstitutionValues,
+    host: impl FnOnce(&str) -> String,
+) -> Result<Vast, WrapError> {
+    let (version, uri) = match (&bid.adm, &bid.nurl) {
+        (Some(adm), _) => {
+            let adm = values.expand(adm);
+            let version = Vast::parse(&adm).map_err(WrapError::Vast)?.version;
+            (version, host(&adm))
+        }
+        (None, Some(nurl)) => {
+            let version = bid.protocol.and_then(Version::from_protocol);
+            (version.unwrap_or(Version::V2_0), values.expand(nurl))
+        }
+        (None, None) => return Err(WrapError::NoMarkup),
+    };
+
+    let expand = |v: &[String]| -> Vec<String> { v.iter().map(|v| values.expand(v)).collect() };
+    let tracking_events: Vec<_> = trackers
+        .tracking_events
+        .iter()
+        .map(|v| Tracking {
+            url: values.expand(&v.url),
+            ..v.clone()
+        })
+        .collect();
+    let creatives = if tracking_events.is_empty() {
+        Vec::new()
+    } else {
+        vec![Creative {
+            id: None,
+            sequence: None,
+            ad_id: None,
+            kind: CreativeKind::Linear(Linear {
+                duration: None,
+                skipoffset: None,
+                media_files: Vec::new(),
+                interactive_creative_files: Vec::new(),
+                tracking_events,
+                click_through: None,
+                click_tracking: Vec::new(),
+            }),
+        }]
+    };
+    let wrapper = Vast {
+        version,
+        ads: vec![Ad {
+            id: Some(bid.id.clone()),
+            sequence: None,
+            kind: AdKind::Wrapper(Wrapper {
+                ad_system: trackers.ad_system.clone(),
+                vast_ad_tag_uri: uri,
+                impressions: expand(&trackers.impressions),
+                errors: expand(&trackers.errors),
+                creatives,
+            }),
+        }],
+        errors: Vec::new(),
+    };
+
+    bid.adm = Some(write_wrapper(&wrapper));
+    if bid.protocol.is_some() {
+        bid.protocol = Some(version.protocol(true));
+    }
+    Ok(wrapper)
+}
+
+fn check_linear(linear: &Linear, c: &Constraints<'_>) -> Vec<Mismatch> {
+    let mut out = Vec::new();
+
+    // A player picks one media file, so a single supported MIME type is enough.
+    let essence = |s: &str| {
+        s.split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase()
+    };
+    if !c.mimes.is_empty()
+        && !linear.media_files.is_empty()
+        && !linear
+            .media_files
+            .iter()
+            .any(|f| c.mimes.iter().any(|m| essence(m) == essence(&f.mime_type)))
+    {
+        out.push(Mismatch::MimeType(
+            linear
+                .media_files
+                .iter()
+                .map(|f| f.mime_type.clone())
+                .collect(),
+        ));
+    }
+
+    if let Some(duration) = linear.duration {
+        let secs = duration.as_secs_f64();
+        let max = c.maxduration.and_then(|max| match c.maxextended {
+            crate::MaxExtendedAdDuration::NoLimit => None,
+            crate::MaxExtendedAdDuration::NotAllowed => Some(max),
+            crate::MaxExtendedAdDuration::Specific(ext) => Some(max.saturating_add(ext)),
+        });
+        if c.minduration.is_some_and(|min| secs < f64::from(min))
+            || max.is_some_and(|max| secs > f64::from(max))
+        {
+            out.push(Mismatch::Duration(duration));
+        }
+    }
+
+    out
+}
+
+/// Maps a VAST `apiFramework` value onto List 5.6, which has no code for the version-less names
+/// VAST uses (e.g. `VPAID`), so any version of the framework is accepted. Returns `None` for
+/// frameworks List 5.6 does not know, e.g. `SIMID` or `OMID`, which cannot be checked.
+fn supports_framework(api: &[crate::ApiFramework], framework: &str) -> Option<bool> {
+    use crate::ApiFramework::*;
+    let accepted: &[crate::ApiFramework] = match framework.trim().to_ascii_uppercase().as_str() {
+        "VPAID" => &[Vpaid1, Vpaid2],
+        "MRAID" => &[Mraid1, Mraid2, Mraid3],
+        "ORMMA" => &[Ormma],
+        _ => return None,
+    };
+    Some(accepted.iter().any(|v| api.contains(v)))
+}
+
+/// Writes a document built by [`wrap_bid`].
+fn write_wrapper(vast: &Vast) -> String {
+    let mut out = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    out.push_str(&format!(r#"<VAST version="{}">"#, vast.version.as_str()));
+    for ad in &vast.ads {
+        let wrapper = match &ad.kind {
+            AdKind::Wrapper(v) => v,
+            AdKind::InLine(_) => continue,
+        };
+        match &ad.id {
+            Some(id) => out.push_str(&format!(r#"<Ad id="{}">"#, escape(id))),
+            None => out.push_str("<Ad>"),
+        }
+        out.push_str("<Wrapper>");
+        out.push_str(&format!(
+            "<AdSystem>{}</AdSystem>",
+            escape(&wrapper.ad_system)
+        ));
+        let uri = format!(
+            "<VASTAdTagURI>{}</VASTAdTagURI>",
+            cdata(&wrapper.vast_ad_tag_uri)
+        );
+        // VAST 4 moved the tag URI after the other elements, `<Creatives>` included.
+        if vast.version < Version::V4_0 {
+            out.push_str(&uri);
+        }
+        for v in &wrapper.errors {
+            out.push_str(&format!("<Error>{}</Error>", cdata(v)));
+        }
+        for v in &wrapper.impressions {
+            out.push_str(&format!("<Impression>{}</Impression>", cdata(v)));
+        }
+        let tracking: Vec<_> = wrapper
+            .creatives
+            .iter()
+            .filter_map(|v| match &v.kind {
+                CreativeKind::Linear(v) => Some(&v.tracking_events),
+                _ => None,
+            })
+            .collect();
+        if !tracking.is_empty() {
+            out.push_str("<Creatives>");
+        }
+        for events in &tracking {
+            out.push_str("<Creative><Linear><TrackingEvents>");
+            for v in *events {
+                out.push_str(&format!(r#"<Tracking event="{}""#, escape(&v.event)));
+                if let Some(offset) = &v.offset {
+                    out.push_str(&format!(r#" offset="{}""#, escape(offset)));
+                }
+                out.push_str(&format!(">{}</Tracking>", cdata(&v.url)));
+            }
+            out.push_str("</TrackingEvents></Linear></Creative>");
+        }
+        if !tracking.is_empty() {
+            out.push_str("</Creatives>");
+        }
+        if vast.version >= Version::V4_0 {
+            out.push_str(&uri);
+        }
+        out.push_str("</Wrapper></Ad>");
+    }
+    out.push_str("</VAST>");
+    out
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Wraps a URI in a CDATA section, splitting any `]]>` it contains.
+fn cdata(s: &str) -> String {
+    format!("<![CDATA[{}]]>", s.replace("]]>", "]]]]><![CDATA[>"))
+}
+
+fn parse_ad(node: Node<'_, '_>) -> Result<Ad, Error> {
+    let kind = if let Some(v) = element(node, "InLine") {
+        AdKind::InLine(InLine {
+            ad_system: required_text(v, "AdSystem", "InLine/AdSystem")?,
+            ad_title: required_text(v, "AdTitle", "InLine/AdTitle")?,
+            impressions: texts(v, "Impression"),
+            errors: texts(v, "Error"),
+            creatives: parse_creatives(v)?,
+        })
+    } else if let Some(v) = element(node, "Wrapper") {
+        AdKind::Wrapper(Wrapper {
+            ad_system: required_text(v, "AdSystem", "Wrapper/AdSystem")?,
+            vast_ad_tag_uri: required_text(v, "VASTAdTagURI", "Wrapper/VASTAdTagURI")?,
+            impressions: texts(v, "Impression"),
+            errors: texts(v, "Error"),
+            creatives: parse_creatives(v)?,
+        })
+    } else {
+        return Err(Error::Missing("Ad/InLine"));
+    };
+
+    Ok(Ad {
+        id: node.attribute("id").map(Into::into),
+        sequence: number(node, "sequence", "Ad@sequence")?,
+        kind,
+    })
+}
+
+fn parse_creatives(node: Node<'_, '_>) -> Result<Vec<Creative>, Error> {
+    let creatives = match element(node, "Creatives") {
+        Some(v) => v,
+        None => return Ok(Vec::new()),
+    };
+    let mut out = Vec::new();
+    for node in elements(creatives, "Creative") {
+        let kind = if let Some(v) = element(node, "Linear") {
+            CreativeKind::Linear(parse_linear(v)?)
+        } else if let Some(v) = element(node, "NonLinearAds") {
+            CreativeKind::NonLinear(NonLinearAds {
+                non_linears: elements(v, "NonLinear")
+                    .map(|v| {
+                        Ok(NonLinear {
+                            id: v.attribute("id").map(Into::into),
+                            width: number(v, "width", "NonLinear@width")?,
+                            height: number(v, "height", "NonLinear@height")?,
+                            min_suggested_duration: v
+                                .attribute("minSuggestedDuration")
+                                .map(|s| duration(s, "NonLinear@minSuggestedDuration"))
+                                .transpose()?,
+                            api_framework: v.attribute("apiFramework").map(Into::into),
+                        })
+                    })
+                    .collect::<Result<_, _>>()?,
+                tracking_events: parse_tracking(v)?,
+            })
+        } else if let Some(v) = element(node, "CompanionAds") {
+            CreativeKind::Companion(
+                elements(v, "Companion")
+                    .map(|v| {
+                        Ok(Companion {
+                            id: v.attribute("id").map(Into::into),
+                            width: number(v, "width", "Companion@width")?,
+                            height: number(v, "height", "Companion@height")?,
+                            api_framework: v.attribute("apiFramework").map(Into::into),
+                        })
+                    })
+                    .collect::<Result<_, _>>()?,
+            )
+        } else {
+            // Creatives without media (e.g. only `<UniversalAdId>` or `<CreativeExtensions>`).
+            continue;
+        };
+        out.push(Creative {
+            id: node.attribute("id").map(Into::into),
+            sequence: number(node, "sequence", "Creative@sequence")?,
+            ad_id: node
+                .attribute("adId")
+                .or_else(|| node.attribute("AdID"))
+                .map(Into::into),
+            kind,
+        });
+    }
+    Ok(out)
+}
+
+fn parse_linear(node: Node<'_, '_>) -> Result<Linear, Error> {
+    let media_files = element(node, "MediaFiles");
+    let clicks = element(node, "VideoClicks");
+    Ok(Linear {
+        duration: element(node, "Duration")
+            .map(|v| duration(&text(v), "Linear/Duration"))
+            .transpose()?,
+        skipoffset: node.attribute("skipoffset").map(Into::into),
+        media_files: media_files
+            .into_iter()
+            .flat_map(|v| elements(v, "MediaFile"))
+            .map(|v| {
+                Ok(MediaFile {
+                    url: text(v),
+                    mime_type: v
+                        .attribute("type")
+                        .ok_or(Error::Missing("MediaFile@type"))?
+                        .into(),
+                    delivery: v.attribute("delivery").map(Into::into),
+                    width: number(v, "width", "MediaFile@width")?,
+                    height: number(v, "height", "MediaFile@height")?,
+                    bitrate: number(v, "bitrate", "MediaFile@bitrate")?,
+                    api_framework: v.attribute("apiFramework").map(Into::into),
+                })
+            })
+            .collect::<Result<_, _>>()?,
+        interactive_creative_files: media_files
+            .into_iter()
+            .flat_map(|v| elements(v, "InteractiveCreativeFile"))
+            .map(|v| InteractiveCreativeFile {
+                url: text(v),
+                mime_type: v.attribute("type").map(Into::into),
+                api_framework: v.attribute("apiFramework").map(Into::into),
+            })
+            .collect(),
+        tracking_events: parse_tracking(node)?,
+        click_through: clicks.and_then(|v| element(v, "ClickThrough")).map(text),
+        click_tracking: clicks
+            .map(|v| texts(v, "ClickTracking"))
+            .unwrap_or_default(),
+    })
+}
+
+fn parse_tracking(node: Node<'_, '_>) -> Result<Vec<Tracking>, Error> {
+    element(node, "TrackingEvents")
+        .into_iter()
+        .flat_map(|v| elements(v, "Tracking"))
+        .map(|v| {
+            Ok(Tracking {
+                event: v
+                    .attribute("event")
+                    .ok_or(Error::Missing("Tracking@event"))?
+                    .into(),
+                offset: v.attribute("offset").map(Into::into),
+                url: text(v),
+            })
+        })
+        .collect()
+}
+
+fn elements<'a, 'input: 'a>(
+    node: Node<'a, 'input>,
+    name: &'static str,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children()
+        .filter(move |v| v.is_element() && v.tag_name().name() == name)
+}
+
+fn element<'a, 'input>(node: Node<'a, 'input>, name: &'static str) -> Option<Node<'a, 'input>> {
+    elements(node, name).next()
+}
+
+/// Returns the trimmed text content, joining text and CDATA sections.
+fn text(node: Node<'_, '_>) -> String {
+    let s: String = node
+        .children()
+        .filter(Node::is_text)
+        .filter_map(|v| v.text())
+        .collect();
+    s.trim().to_owned()
+}
+
+fn texts(node: Node<'_, '_>, name: &'static str) -> Vec<String> {
+    elements(node, name)
+        .map(text)
+        .filter(|v| !v.is_empty())
+        .collect()
+}
+
+fn required_text(
+    node: Node<'_, '_>,
+    name: &'static str,
+    path: &'static str,
+) -> Result<String, Error> {
+    element(node, name).map(text).ok_or(Error::Missing(path))
+}
+
+fn number<T: std::str::FromStr>(
+    node: Node<'_, '_>,
+    attr: &str,
+    name: &'static str,
+) -> Result<Option<T>, Error> {
+    node.attribute(attr)
+        .map(|s| {
+            s.trim().parse().map_err(|_| Error::Invalid {
+                name,
+                value: s.into(),
+            })
+        })
+        .transpose()
+}
+
+/// Parses a `HH:MM:SS` or `HH:MM:SS.mmm` time.
+fn duration(s: &str, name: &'static str) -> Result<Duration, Error> {
+    let invalid = || Error::Invalid {
+        name,
+        value: s.into(),
+    };
+    let mut parts = s.trim().splitn(3, ':');
+    let mut next = || parts.next().ok_or_else(invalid);
+    let (h, m, sec) = (next()?, next()?, next()?);
+    let h: u64 = h.parse().map_err(|_| invalid())?;
+    let m: u64 = m.parse().map_err(|_| invalid())?;
+    let sec: f64 = sec.parse().map_err(|_| invalid())?;
+    if m >= 60 || !(0.0..60.0).contains(&sec) {
+        return Err(invalid());
+    }
+    h.checked_mul(3600)
+        .and_then(|v| v.checked_add(m * 60))
+        .and_then(|v| Duration::from_secs(v).checked_add(Duration::from_secs_f64(sec)))
+        .ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INLINE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<VAST version="4.2">
+  <Ad id="1" sequence="1">
+    <InLine>
+      <AdSystem version="1.0">Acme</AdSystem>
+      <AdTitle><![CDATA[Sample]]></AdTitle>
+      <Impression id="i"><![CDATA[https://acme.example/imp]]></Impression>
+      <Error><![CDATA[https://acme.example/error?code=[ERRORCODE]]]></Error>
+      <Creatives>
+        <Creative id="c1" sequence="1" adId="a1">
+          <Linear skipoffset="00:00:05">
+            <Duration>00:00:15.500</Duration>
+            <TrackingEvents>
+              <Tracking event="start"><![CDATA[https://acme.example/start]]></Tracking>
+              <Tracking event="progress" offset="00:00:10">https://acme.example/10s</Tracking>
+            </TrackingEvents>
+            <VideoClicks>
+              <ClickThrough><![CDATA[https://acme.example/landing]]></ClickThrough>
+              <ClickTracking><![CDATA[https://acme.example/click]]></ClickTracking>
+            </VideoClicks>
+            <MediaFiles>
+              <MediaFile delivery="progressive" type="video/webm" width="640" height="360" bitrate="500">
+                <![CDATA[https://acme.example/video.webm]]>
+              </MediaFile>
+              <InteractiveCreativeFile type="text/html" apiFramework="SIMID">
+                <![CDATA[https://acme.example/simid.html]]>
+              </InteractiveCreativeFile>
+            </MediaFiles>
+          </Linear>
+        </Creative>
+        <Creative>
+          <CompanionAds>
+            <Companion id="cp" width="300" height="250" />
+          </CompanionAds>
+        </Creative>
+      </Creatives>
+    </InLine>
+  </Ad>
+</VAST>"#;
+
+    const WRAPPER: &str = r#"<VAST version="2.0">
+  <Ad id="w">
+    <Wrapper>
+      <AdSystem>Exchange</AdSystem>
+      <VASTAdTagURI><![CDATA[https://dsp.example/vast.xml]]></VASTAdTagURI>
+      <Impression>https://exchange.example/imp</Impression>
+      <Creatives>
+        <Creative>
+          <NonLinearAds>
+            <TrackingEvents>
+              <Tracking event="creativeView">https://exchange.example/view</Tracking>
+            </TrackingEvents>
+          </NonLinearAds>
+        </Creative>
+      </Creatives>
+    </Wrapper>
+  </Ad>
+</VAST>"#;
+
+    #[test]
+    fn parse_inline() -> Result<(), Error> {
+        let vast = Vast::parse(INLINE)?;
+        assert_eq!(vast.version, Version::V4_2);
+        assert_eq!(vast.ads.len(), 1);
+
+        let ad = &vast.ads[0];
+        assert_eq!((ad.id.as_deref(), ad.sequence), (Some("1"), Some(1)));
+        let inline = match &ad.kind {
+            AdKind::InLine(v) => v,
+            _ => unreachable!(),
+        };
+        assert_eq!(inline.ad_system, "Acme");
+        assert_eq!(inline.ad_title, "Sample");
+        assert_eq!(inline.impressions, vec!["https://acme.example/imp"]);
+        assert_eq!(
+            inline.errors,
+            vec!["https://acme.example/error?code=[ERRORCODE]"]
+        );
+        assert_eq!(inline.creatives.len(), 2);
+
+        let linear = match &inline.creatives[0].kind {
+            CreativeKind::Linear(v) => v,
+            _ => unreachable!(),
+        };
+        assert_eq!(linear.duration, Some(Duration::from_millis(15_500)));
+        assert_eq!(linear.skipoffset.as_deref(), Some("00:00:05"));
+        assert_eq!(
+            linear.media_files,
+            vec![MediaFile {
+                url: "https://acme.example/video.webm".into(),
+                mime_type: "video/webm".into(),
+                delivery: Some("progressive".into()),
+                width: Some(640),
+                height: Some(360),
+                bitrate: Some(500),
+                api_framework: None,
+            }]
+        );
+        assert_eq!(
+            linear.interactive_creative_files[0]
+                .api_framework
+                .as_deref(),
+            Some("SIMID")
+        );
+        assert_eq!(
+            linear.tracking_events[1].offset.as_deref(),
+            Some("00:00:10")
+        );
+        assert_eq!(linear.tracking_events[1].url, "https://acme.example/10s");
+        assert_eq!(
+            linear.click_through.as_deref(),
+            Some("https://acme.example/landing")
+        );
+        assert_eq!(linear.click_tracking, vec!["https://acme.example/click"]);
+
+        assert_eq!(
+            inline.creatives[1].kind,
+            CreativeKind::Companion(vec![Companion {
+                id: Some("cp".into()),
+                width: Some(300),
+                height: Some(250),
+                api_framework: None,
+            }])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_wrapper() -> Result<(), Error> {
+        let vast = Vast::parse(WRAPPER)?;
+        assert_eq!(vast.version, Version::V2_0);
+        match &vast.ads[0].kind {
+            AdKind::Wrapper(v) => {
+                assert_eq!(v.vast_ad_tag_uri, "https://dsp.example/vast.xml");
+                assert_eq!(v.impressions, vec!["https://exchange.example/imp"]);
+                assert_eq!(v.creatives.len(), 1);
+            }
+            _ => unreachable!(),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_error() {
+        assert!(matches!(Vast::parse("<VAST"), Err(Error::Xml(_))));
+        assert!(matches!(Vast::parse("<VMAP/>"), Err(Error::NotVast)));
+        assert!(matches!(
+            Vast::parse(r#"<VAST version="1.0"/>"#),
+            Err(Error::UnsupportedVersion(Some(_)))
+        ));
+        assert!(matches!(
+            Vast::parse(r#"<VAST version="3.0"><Ad><Wrapper><AdSystem/></Wrapper></Ad></VAST>"#),
+            Err(Error::Missing("Wrapper/VASTAdTagURI"))
+        ));
+        assert!(matches!(
+            Vast::parse(
+                r#"<VAST version="3.0"><Ad><InLine><AdSystem/><AdTitle/><Creatives><Creative>
+                <Linear><Duration>0:99:00</Duration></Linear></Creative></Creatives></InLine></Ad></VAST>"#
+            ),
+            Err(Error::Invalid {
+                name: "Linear/Duration",
+                ..
+            })
+        ));
+
+        assert!(matches!(
+            duration("99999999999999999:00:00", "Linear/Duration"),
+            Err(Error::Invalid {
+                name: "Linear/Duration",
+                ..
+            })
+        ));
+
+        let vast = Vast::parse(r#"<VAST version="4.1"><Error>https://e.example</Error></VAST>"#);
+        assert_eq!(vast.unwrap().errors, vec!["https://e.example"]);
+    }
+
+    #[test]
+    fn check_video() -> Result<(), Box<dyn std::error::Error>> {
+        let vast = Vast::parse(INLINE)?;
+
+        let video: crate::Video = serde_json::from_str(
+            r#"{"mimes":["video/webm"],"minduration":5,"maxduration":30,"protocols":[7],"api":[1]}"#,
+        )?;
+        assert!(vast.check_video(&video).is_empty());
+
+        let video: crate::Video = serde_json::from_str(
+            r#"{"mimes":["video/mp4"],"maxduration":15,"protocols":[2,3],"linearity":2}"#,
+        )?;
+        assert_eq!(
+            vast.check_video(&video),
+            vec![
+                Mismatch::Protocol(crate::Protocol::Vast4),
+                Mismatch::Linearity(crate::VideoLinearity::NonLinear),
+                Mismatch::MimeType(vec!["video/webm".into()]),
+                Mismatch::Duration(Duration::from_millis(15_500)),
+            ]
+        );
+
+        // extended play beyond `maxduration`
+        let video: crate::Video =
+            serde_json::from_str(r#"{"mimes":["VIDEO/WEBM"],"maxduration":15,"maxextended":1}"#)?;
+        assert!(vast.check_video(&video).is_empty());
+
+        // a known framework is checked, an unknown one is not
+        let vpaid = INLINE.replace(r#"apiFramework="SIMID""#, r#"apiFramework="VPAID""#);
+        let video: crate::Video = serde_json::from_str(r#"{"mimes":["video/webm"],"api":[3]}"#)?;
+        assert_eq!(
+            Vast::parse(&vpaid)?.check_video(&video),
+            vec![Mismatch::ApiFramework("VPAID".into())]
+        );
+        assert!(vast.check_video(&video).is_empty());
+        let video: crate::Video = serde_json::from_str(r#"{"mimes":["video/webm"],"api":[2]}"#)?;
+        assert!(Vast::parse(&vpaid)?.check_video(&video).is_empty());
+
+        // wrappers are checked for their protocol only
+        let vast = Vast::parse(WRAPPER)?;
+        let video: crate::Video =
+            serde_json::from_str(r#"{"mimes":["video/mp4"],"protocols":[2,3],"linearity":1}"#)?;
+        assert_eq!(
+            vast.check_video(&video),
+            vec![Mismatch::Protocol(crate::Protocol::Vast2Wrapper)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_audio() -> Result<(), Box<dyn std::error::Error>> {
+        let vast = Vast::parse(
+            r#"<VAST version="4.1"><Ad><InLine><AdSystem>Acme</AdSystem><AdTitle>Audio</AdTitle>
+            <Creatives><Creative><Linear><Duration>00:00:30</Duration><MediaFiles>
+            <MediaFile delivery="progressive" type="audio/mpeg">https://acme.example/a.mp3</MediaFile>
+            </MediaFiles></Linear></Creative></Creatives></InLine></Ad></VAST>"#,
+        )?;
+
+        let audio: crate::Audio =
+            serde_json::from_str(r#"{"mimes":["audio/mpeg"],"maxduration":30,"protocols":[7]}"#)?;
+        assert!(vast.check_audio(&audio).is_empty());
+
+        let audio: crate::Audio =
+            serde_json::from_str(r#"{"mimes":["audio/mp4"],"minduration":31}"#)?;
+        assert_eq!(
+            vast.check_audio(&audio),
+            vec![
+                Mismatch::MimeType(vec!["audio/mpeg".into()]),
+                Mismatch::Duration(Duration::from_secs(30)),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn wrap() -> Result<(), Box<dyn std::error::Error>> {
+        let trackers = Trackers {
+            ad_system: "Exchange & Co".into(),
+            impressions: vec!["https://adx.example/imp?id=${AUCTION_ID}".into()],
+            errors: vec!["https://adx.example/err?code=[ERRORCODE]".into()],
+            tracking_events: vec![Tracking {
+                event: "firstQuartile".into(),
+                offset: None,
+                url: "https://adx.example/q1?imp=${AUCTION_IMP_ID}&p=${AUCTION_PRICE}".into(),
+            }],
+        };
+        let values = crate::SubstitutionValues {
+            auction_id: Some("a".into()),
+            imp_id: Some("i".into()),
+            ..Default::default()
+        };
+
+        // markup in adm is hosted by the exchange
+        let mut bid = crate::Bid {
+            id: "b".into(),
+            adm: Some(INLINE.replace("https://acme.example/imp", "${AUCTION_ID}")),
+            protocol: Some(crate::Protocol::Vast4),
+            ..Default::default()
+        };
+        let mut hosted = String::new();
+        let wrapper = wrap_bid(&mut bid, &trackers, &values, |adm| {
+            hosted = adm.to_owned();
+            "https://cache.example/1".into()
+        })?;
+        assert!(hosted.contains("<![CDATA[a]]>"));
+        assert_eq!(bid.protocol, Some(crate::Protocol::Vast4Wrapper));
+
+        let adm = bid.adm.as_deref().unwrap();
+        assert_eq!(Vast::parse(adm)?, wrapper);
+        assert!(adm.contains(
+            "<Impression><![CDATA[https://adx.example/imp?id=a]]></Impression><Creatives>"
+        ));
+        assert!(adm.contains("</Creatives><VASTAdTagURI>"));
+        assert_eq!(wrapper.version, Version::V4_2);
+        assert_eq!(wrapper.ads[0].id.as_deref(), Some("b"));
+        match &wrapper.ads[0].kind {
+            AdKind::Wrapper(v) => {
+                assert_eq!(v.ad_system, "Exchange & Co");
+                assert_eq!(v.vast_ad_tag_uri, "https://cache.example/1");
+                assert_eq!(v.errors, trackers.errors);
+                match &v.creatives[0].kind {
+                    CreativeKind::Linear(v) => assert_eq!(
+                        v.tracking_events[0].url,
+                        "https://adx.example/q1?imp=i&p=${AUCTION_PRICE}"
+                    ),
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        }
+
+        // markup served by nurl
+        let mut bid = crate::Bid {
+            nurl: Some("https://dsp.example/win?imp=${AUCTION_IMP_ID}".into()),
+            protocol: Some(crate::Protocol::Vast3),
+            ..Default::default()
+        };
+        let wrapper = wrap_bid(&mut bid, &Trackers::default(), &values, |_| unreachable!())?;
+        assert_eq!(wrapper.version, Version::V3_0);
+        assert_eq!(bid.protocol, Some(crate::Protocol::Vast3Wrapper));
+        assert_eq!(
+            bid.adm.as_deref(),
+            Some(concat!(
+                r#"<?xml version="1.0" encoding="UTF-8"?><VAST version="3.0"><Ad id=""><Wrapper>"#,
+                "<AdSystem></AdSystem>",
+                "<VASTAdTagURI><![CDATA[https://dsp.example/win?imp=i]]></VASTAdTagURI>",
+                "</Wrapper></Ad></VAST>"
+            ))
+        );
+
+        let mut bid = crate::Bid::default();
+        assert!(matches!(
+            wrap_bid(&mut bid, &trackers, &values, |_| unreachable!()),
+            Err(WrapError::NoMarkup)
+        ));
+        bid.adm = Some("<html></html>".into());
+        assert!(matches!(
+            wrap_bid(&mut bid, &trackers, &values, |_| unreachable!()),
+            Err(WrapError::Vast(Error::NotVast))
+        ));
+        assert_eq!(bid.adm.as_deref(), Some("<html></html>"));
+
+        assert_eq!(cdata("a]]>b"), "<![CDATA[a]]]]><![CDATA[>b]]>");
+
+        Ok(())
+    }
+}
@@ -11,6 +11,12 @@
 /// be offered as banner, audio, and/or native by also including as Imp subordinates objects of
 /// those types. However, any given bid for the impression must conform to one of the offered types.
 #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::schema::recommended)
+)]
 pub struct Video {
     /// string array; required
     /// Content MIME types supported (e.g., “video/x-ms-wmv”, “video/mp4”).
@@ -68,6 +74,10 @@ pub struct Video {
         skip_serializing_if = "Option::is_none",
         with = "crate::serde::i32_as_opt_bool"
     )]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(schema_with = "crate::serde::i32_as_opt_bool::schema")
+    )]
     pub skip: Option<bool>,
 
     /// integer; default 0
@@ -119,6 +129,10 @@ pub struct Video {
         skip_serializing_if = "is_default_boxingallowed",
         with = "crate::serde::i32_as_bool"
     )]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(schema_with = "crate::serde::i32_as_bool::schema")
+    )]
     pub boxingallowed: bool,
 
     /// integer array
@@ -167,6 +181,7 @@ pub struct Video {
     /// object
     /// Placeholder for exchange-specific extensions to OpenRTB.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzzing::ext))]
     pub ext: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
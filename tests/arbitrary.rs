@@ -0,0 +1,49 @@
+#![cfg(feature = "proptest")]
+
+use openrtb2::{Auction, AuctionType, BidRequest, BidResponse, MaxExtendedAdDuration, StartDelay};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn round_trip(auction in any::<Auction>()) {
+        let Auction { request, response } = auction;
+
+        let json = serde_json::to_string(&request).unwrap();
+        prop_assert_eq!(serde_json::from_str::<BidRequest>(&json).unwrap(), request);
+
+        let json = serde_json::to_string(&response).unwrap();
+        prop_assert_eq!(serde_json::from_str::<BidResponse>(&json).unwrap(), response);
+    }
+
+    #[test]
+    fn cross_references(auction in any::<Auction>()) {
+        let Auction { request, response } = auction;
+        prop_assert!(!request.imp.is_empty());
+        prop_assert_eq!(&response.id, &request.id);
+        for bid in response.seatbid.iter().flatten().flat_map(|s| &s.bid) {
+            let imp = request.imp.iter().find(|imp| imp.id == bid.impid);
+            prop_assert!(imp.is_some());
+            if let Some(dealid) = &bid.dealid {
+                let deals = imp.and_then(|imp| imp.pmp.as_ref()).and_then(|pmp| pmp.deals.as_ref());
+                prop_assert!(deals.unwrap().iter().any(|deal| &deal.id == dealid));
+            }
+        }
+    }
+
+    #[test]
+    fn code_ranges(
+        at in any::<AuctionType>(),
+        sd in any::<StartDelay>(),
+        me in any::<MaxExtendedAdDuration>(),
+    ) {
+        if let AuctionType::ExchangeSpecific(v) = at {
+            prop_assert!(v > 500);
+        }
+        if let StartDelay::MidRoll(v) = sd {
+            prop_assert!(v > 0);
+        }
+        if let MaxExtendedAdDuration::Specific(v) = me {
+            prop_assert!(v > 0);
+        }
+    }
+}
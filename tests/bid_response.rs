@@ -26,3 +26,22 @@ test_json!(
     native_markup_returned_inline,
     "json/6.4.4_native_markup_returned_inline.json"
 );
+
+// The example in the specification has malformed URLs (`http: //`), which `NoticeUrl` rejects.
+#[cfg(feature = "notice-url")]
+#[test]
+fn direct_deal_notice_urls() -> serde_json::Result<()> {
+    let json = include_str!("json/6.4.3_direct_deal_ad_served_on_win_notice.json");
+    let res = serde_json::from_str::<openrtb2::BidResponse>(json)?;
+    let bid = &res.seatbid.as_ref().unwrap()[0].bid[0];
+    assert_eq!(
+        bid.nurl_checked(),
+        Some(Err(openrtb2::NoticeUrlError::Scheme))
+    );
+
+    let res = serde_json::from_str::<openrtb2::BidResponse>(&json.replace("http: //", "http://"))?;
+    let bid = &res.seatbid.as_ref().unwrap()[0].bid[0];
+    assert!(!bid.nurl_checked().unwrap().unwrap().is_secure());
+
+    Ok(())
+}
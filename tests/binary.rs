@@ -0,0 +1,120 @@
+#![cfg(any(feature = "msgpack", feature = "cbor"))]
+
+macro_rules! test_format {
+    ($name:ident, $feature:literal, $to:ident, $from:ident) => {
+        #[cfg(feature = $feature)]
+        mod $name {
+            use openrtb2::{$from, $to};
+
+            fn round_trip<T>(json: &str) -> Result<(), Box<dyn std::error::Error>>
+            where
+                T: serde::Serialize + serde::de::DeserializeOwned + PartialEq + std::fmt::Debug,
+            {
+                let v = serde_json::from_str::<T>(json)?;
+                assert_eq!($from::<T>(&$to(&v)?)?, v);
+                Ok(())
+            }
+
+            #[test]
+            fn simple_banner() -> Result<(), Box<dyn std::error::Error>> {
+                round_trip::<openrtb2::BidRequest>(include_str!("json/6.3.1_simple_banner.json"))
+            }
+
+            #[test]
+            fn expandable_creative() -> Result<(), Box<dyn std::error::Error>> {
+                round_trip::<openrtb2::BidRequest>(include_str!(
+                    "json/6.3.2_expandable_creative.json"
+                ))
+            }
+
+            #[test]
+            fn mobile() -> Result<(), Box<dyn std::error::Error>> {
+                round_trip::<openrtb2::BidRequest>(include_str!("json/6.3.3_mobile.json"))
+            }
+
+            #[test]
+            fn video() -> Result<(), Box<dyn std::error::Error>> {
+                round_trip::<openrtb2::BidRequest>(include_str!("json/6.3.4_video.json"))
+            }
+
+            #[test]
+            fn pmp_with_direct_deal() -> Result<(), Box<dyn std::error::Error>> {
+                round_trip::<openrtb2::BidRequest>(include_str!(
+                    "json/6.3.5_pmp_with_direct_deal.json"
+                ))
+            }
+
+            #[test]
+            fn native_ad() -> Result<(), Box<dyn std::error::Error>> {
+                round_trip::<openrtb2::BidRequest>(include_str!("json/6.3.6_native_ad.json"))
+            }
+
+            #[test]
+            fn ad_served_on_win_notice() -> Result<(), Box<dyn std::error::Error>> {
+                round_trip::<openrtb2::BidResponse>(include_str!(
+                    "json/6.4.1_ad_served_on_win_notice.json"
+                ))
+            }
+
+            #[test]
+            fn vast_xml_document_returned_inline() -> Result<(), Box<dyn std::error::Error>> {
+                round_trip::<openrtb2::BidResponse>(include_str!(
+                    "json/6.4.2_vast_xml_document_returned_inline.json"
+                ))
+            }
+
+            #[test]
+            fn direct_deal_ad_served_on_win_notice() -> Result<(), Box<dyn std::error::Error>> {
+                round_trip::<openrtb2::BidResponse>(include_str!(
+                    "json/6.4.3_direct_deal_ad_served_on_win_notice.json"
+                ))
+            }
+
+            #[test]
+            fn native_markup_returned_inline() -> Result<(), Box<dyn std::error::Error>> {
+                round_trip::<openrtb2::BidResponse>(include_str!(
+                    "json/6.4.4_native_markup_returned_inline.json"
+                ))
+            }
+
+            #[test]
+            fn custom_codes() -> Result<(), Box<dyn std::error::Error>> {
+                round_trip::<openrtb2::BidRequest>(
+                    r#"{
+                        "id": "1",
+                        "imp": [{
+                            "id": "1",
+                            "video": {"mimes": ["video/mp4"], "startdelay": 15, "maxextended": -1, "boxingallowed": 0},
+                            "secure": 1,
+                            "bidfloor": 0.5
+                        }, {
+                            "id": "2",
+                            "audio": {"mimes": ["audio/mp4"], "startdelay": -2, "maxextended": 30},
+                            "pmp": {"private_auction": 1, "deals": [{"id": "d", "at": 1}]}
+                        }],
+                        "app": {"id": "a", "paid": 1, "ext": {"k": [1, "v", null]}},
+                        "test": 1,
+                        "at": 501
+                    }"#,
+                )
+            }
+        }
+    };
+}
+
+test_format!(msgpack, "msgpack", to_msgpack, from_msgpack);
+test_format!(cbor, "cbor", to_cbor, from_cbor);
+
+#[cfg(feature = "msgpack")]
+#[test]
+fn msgpack_compact() -> Result<(), Box<dyn std::error::Error>> {
+    // The compact encoding writes a struct as an array. Omitted fields shift the ones after them,
+    // so it does not round-trip; `to_msgpack` writes maps instead.
+    let json = r#"{"id":"1","imp":[{"id":"1","bidfloor":1.5}]}"#;
+    let v = serde_json::from_str::<openrtb2::BidRequest>(json)?;
+    let buf = rmp_serde::to_vec(&v)?;
+    assert!(rmp_serde::from_slice::<openrtb2::BidRequest>(&buf).is_err());
+    let buf = openrtb2::to_msgpack(&v)?;
+    assert_eq!(openrtb2::from_msgpack::<openrtb2::BidRequest>(&buf)?, v);
+    Ok(())
+}
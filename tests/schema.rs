@@ -0,0 +1,59 @@
+#![cfg(feature = "schema")]
+
+use serde_json::{json, Value};
+
+fn schema<T: schemars::JsonSchema>() -> Value {
+    schemars::schema_for!(T).to_value()
+}
+
+#[test]
+fn bid_request() {
+    let s = schema::<openrtb2::BidRequest>();
+    assert_eq!(s["required"], json!(["id", "imp"]));
+    assert_eq!(s["x-recommended"], json!(["app", "device", "site", "user"]));
+    assert!(s["properties"]["site"]["description"]
+        .as_str()
+        .unwrap()
+        .contains("Site object"));
+    assert_eq!(s["properties"]["test"]["enum"], json!([0, 1]));
+    assert_eq!(s["properties"]["at"]["$ref"], "#/$defs/AuctionType");
+
+    let defs = &s["$defs"];
+    assert_eq!(
+        defs["Imp"]["properties"]["secure"]["enum"],
+        json!([0, 1, null])
+    );
+    assert_eq!(defs["Banner"]["properties"]["wmax"]["deprecated"], true);
+    assert_eq!(defs["Video"]["required"], json!(["mimes"]));
+
+    let pos = &defs["AdPosition"]["oneOf"];
+    assert_eq!(
+        pos[1],
+        json!({"description": "Above the Fold", "type": "integer", "const": 1})
+    );
+    assert_eq!(defs["StartDelay"]["oneOf"][0]["minimum"], 1);
+    assert_eq!(defs["AuctionType"]["oneOf"][3]["minimum"], 501);
+    assert_eq!(defs["MaxExtendedAdDuration"]["oneOf"][0]["const"], -1);
+    assert_eq!(defs["Gender"]["enum"], json!(["M", "F", "O"]));
+}
+
+#[test]
+fn bid_response() {
+    let s = schema::<openrtb2::BidResponse>();
+    assert_eq!(s["required"], json!(["id"]));
+
+    let nbr = &s["$defs"]["NoBidReason"]["oneOf"];
+    assert_eq!(nbr[0]["const"], 0);
+    assert_eq!(nbr[0]["description"], "Unknown Error");
+
+    let loss = schema::<openrtb2::LossReason>();
+    let values: Vec<_> = loss["oneOf"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|v| v["const"].as_i64())
+        .collect();
+    assert_eq!(&values[..3], &[0, 1, 2]);
+    assert!(values.contains(&100) && values.contains(&207));
+    assert_eq!(loss["oneOf"][values.len()]["minimum"], 1000);
+}
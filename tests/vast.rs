@@ -0,0 +1,30 @@
+#![cfg(feature = "vast")]
+
+use openrtb2::{
+    vast::{AdKind, Error, Vast, Version},
+    BidRequest, BidResponse,
+};
+
+#[test]
+fn spec_example() -> Result<(), Box<dyn std::error::Error>> {
+    let req: BidRequest = serde_json::from_str(include_str!("json/6.3.4_video.json"))?;
+    let res: BidResponse = serde_json::from_str(include_str!(
+        "json/6.4.2_vast_xml_document_returned_inline.json"
+    ))?;
+    let adm = res.seatbid.as_ref().unwrap()[0].bid[0]
+        .adm
+        .as_deref()
+        .unwrap();
+
+    // The example in the specification is not well-formed: `<![C DATA[`.
+    assert!(matches!(Vast::parse(adm), Err(Error::Xml(_))));
+
+    let vast = Vast::parse(&adm.replace("<![C DATA[", "<![CDATA["))?;
+    assert_eq!(vast.version, Version::V2_0);
+    assert!(matches!(&vast.ads[0].kind, AdKind::InLine(v) if v.ad_title == "Sample VAST"));
+    assert!(vast
+        .check_video(req.imp[0].video.as_ref().unwrap())
+        .is_empty());
+
+    Ok(())
+}